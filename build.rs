@@ -0,0 +1,21 @@
+use std::{env, fs};
+
+fn main() {
+    // Adds the linker flags a Node addon needs on macOS and Windows
+    #[cfg(feature = "napi")]
+    napi_build::setup();
+
+    // Generates the UniFFI scaffolding for the interface in src/bbs.udl
+    #[cfg(feature = "uniffi")]
+    uniffi::generate_scaffolding("src/bbs.udl").unwrap();
+
+    // The release version lives in package.json, which is missing when built from a crate archive
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=package.json");
+    let version = fs::read_to_string("package.json")
+        .ok()
+        .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
+        .and_then(|v| v["version"].as_str().map(str::to_string))
+        .unwrap_or_else(|| env::var("CARGO_PKG_VERSION").unwrap());
+    println!("cargo:rustc-env=BBS_LIBRARY_VERSION={}", version);
+}
@@ -0,0 +1,9 @@
+#![no_main]
+
+use bbs::api::{BlindSignatureContext, Commitment, Parse};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = Commitment::parse(data);
+    let _ = BlindSignatureContext::parse(data);
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use bbs::cose::{decode_proof, decode_signature, encode_proof};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = decode_signature(data);
+    if let Ok(cose) = decode_proof(data) {
+        assert_eq!(
+            decode_proof(&encode_proof(&cose.value, &cose.kid)).map(|c| c.value.to_bytes()),
+            Ok(cose.value.to_bytes())
+        );
+    }
+});
@@ -0,0 +1,13 @@
+#![no_main]
+
+use bbs::api::{Parse, Proof};
+use bbs::bbs_verify_proof::bbs_get_total_messages_count_for_proof;
+use bbs::ByteArray;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let count = bbs_get_total_messages_count_for_proof(ByteArray::from(data));
+    if let Ok(proof) = Proof::parse(data) {
+        assert_eq!(proof.message_count() as i32, count);
+    }
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use bbs::api::{DeterministicPublicKey, Parse, PublicKey};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = PublicKey::parse(data);
+    let _ = DeterministicPublicKey::parse(data);
+});
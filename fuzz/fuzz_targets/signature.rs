@@ -0,0 +1,9 @@
+#![no_main]
+
+use bbs::api::{BlindSignature, Parse, Signature};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = Signature::parse(data);
+    let _ = BlindSignature::parse(data);
+});
@@ -6,12 +6,53 @@
 #include <stdint.h>
 #include <stdlib.h>
 
+/**
+ * Incremented whenever bbs.h changes incompatibly: a function is removed from the default
+ * build or changes its parameters, return value or what happens to its context handle, or a
+ * struct or enum changes layout. Adding functions does not change it. 2 since finishing a BLS
+ * aggregate or threshold signing context deletes it on failure too, and the COSE and JWP
+ * functions moved behind the `cose` and `jwp` features.
+ * Wrappers compare it with `bbs_abi_version` before calling anything else
+ */
+#define BBS_ABI_VERSION 2
+
+/**
+ * The version of the proof framing written by `api::Proof::to_bytes`, as reported by `bbs_proof_inspect`
+ */
+#define BBS_PROOF_FORMAT_VERSION 1
+
+/**
+ * The kind of artifact held by an envelope
+ */
+typedef enum ArtifactType {
+  SignatureArtifact = 1,
+  BlindSignatureArtifact = 2,
+  ProofArtifact = 3,
+  CommitmentArtifact = 4,
+  BlindSignatureContextArtifact = 5,
+  PublicKeyArtifact = 6,
+} ArtifactType;
+
 typedef enum ProofMessageType {
   Revealed = 1,
   HiddenProofSpecificBlinding = 2,
   HiddenExternalBlinding = 3,
 } ProofMessageType;
 
+/**
+ * The reason a key or signature failed validation.
+ * The value is also the code returned by the validation functions.
+ */
+typedef enum ValidationResult {
+  Valid = 0,
+  InvalidLength = 1,
+  InvalidEncoding = 2,
+  NotOnCurve = 3,
+  NotInSubgroup = 4,
+  IdentityElement = 5,
+  InvalidScalar = 6,
+} ValidationResult;
+
 /**
  * ByteBuffer is a struct that represents an array of bytes to be sent over the FFI boundaries.
  * There are several cases when you might want to use this, but the primary one for us
@@ -288,6 +329,32 @@ typedef struct ByteArray {
   const uint8_t *data;
 } ByteArray;
 
+/**
+ * Computes `a` = `b` ^ (1 / (`x` + `e`)) where `x` is the secret key held by the signer.
+ * `b` and `a` are compressed G1 points and `e` is a field element, all big endian.
+ * `a` points to `a_len` writable bytes. Returns 0 on success
+ */
+typedef int32_t (*BbsSignerSignCallback)(void *user_data,
+                                         struct ByteArray b,
+                                         struct ByteArray e,
+                                         uint8_t *a,
+                                         uintptr_t a_len);
+
+/**
+ * An external signer that performs the secret key operation of signing and blind signing
+ * so the secret key never has to be passed to this library.
+ * `user_data` is passed unchanged to `sign`, which may be called from any thread
+ */
+typedef struct BbsSigner {
+  void *user_data;
+  BbsSignerSignCallback sign;
+} BbsSigner;
+
+/**
+ * Fills `buffer` with `length` random bytes. Returns 0 on success
+ */
+typedef int32_t (*BbsRngCallback)(void *user_data, uint8_t *buffer, uintptr_t length);
+
 /**
  * Public destructor for strings managed by the other side of the FFI.
  *
@@ -303,6 +370,12 @@ void bbs_string_free(char *s);
 
 void bbs_byte_buffer_free(struct ByteBuffer v);
 
+/**
+ * Overwrite a buffer holding secret material such as a secret key,
+ * blinding factor or commitment context with zeros before freeing it
+ */
+void bbs_byte_buffer_free_secure(struct ByteBuffer buffer);
+
 void free_bbs_blind_commitment(uint64_t v, struct ExternError *err);
 
 int32_t bbs_blind_signature_size(void);
@@ -375,6 +448,13 @@ int32_t bbs_blind_sign_context_set_secret_key(uint64_t handle,
                                               struct ByteArray value,
                                               struct ExternError *err);
 
+/**
+ * Use an external signer instead of a secret key
+ */
+int32_t bbs_blind_sign_context_set_signer(uint64_t handle,
+                                          struct BbsSigner signer,
+                                          struct ExternError *err);
+
 int32_t bbs_blind_sign_context_set_commitment(uint64_t handle,
                                               struct ByteArray value,
                                               struct ExternError *err);
@@ -436,6 +516,35 @@ int32_t bbs_create_proof_context_finish(uint64_t handle,
                                         struct ByteBuffer *proof,
                                         struct ExternError *err);
 
+#if defined(DEFINE_JWP)
+/**
+ * Prove knowledge of the signature in the issued JWP `value` instead of adding messages
+ */
+int32_t bbs_create_proof_context_set_jwp(uint64_t handle,
+                                         struct ByteArray value,
+                                         struct ExternError *err);
+#endif
+
+#if defined(DEFINE_JWP)
+/**
+ * Disclose the JWP payload at `index`, counting from 0. Other payloads are hidden
+ */
+int32_t bbs_create_proof_context_disclose_jwp_payload(uint64_t handle,
+                                                      uint32_t index,
+                                                      struct ExternError *err);
+#endif
+
+#if defined(DEFINE_JWP)
+/**
+ * Write the presented JWP for the issued JWP and `presentation_header` in compact form to `jwp`.
+ * The nonce is the hash of the presentation header, which must be a JSON object
+ */
+int32_t bbs_create_proof_context_finish_jwp(uint64_t handle,
+                                            struct ByteArray presentation_header,
+                                            struct ByteBuffer *jwp,
+                                            struct ExternError *err);
+#endif
+
 void free_bbs_sign(uint64_t v, struct ExternError *err);
 
 int32_t bbs_signature_size(void);
@@ -462,10 +571,104 @@ int32_t bbs_sign_context_set_public_key(uint64_t handle,
                                         struct ByteArray value,
                                         struct ExternError *err);
 
+/**
+ * Use an external signer instead of a secret key
+ */
+int32_t bbs_sign_context_set_signer(uint64_t handle,
+                                    struct BbsSigner signer,
+                                    struct ExternError *err);
+
 int32_t bbs_sign_context_finish(uint64_t handle,
                                 struct ByteBuffer *signature,
                                 struct ExternError *err);
 
+#if defined(DEFINE_JWP)
+/**
+ * Add the next JWP payload, signed as the message after the issuer header
+ */
+int32_t bbs_sign_context_add_jwp_payload(uint64_t handle,
+                                         struct ByteArray payload,
+                                         struct ExternError *err);
+#endif
+
+#if defined(DEFINE_JWP)
+/**
+ * Sign the issuer `header` and the JWP payloads and write the issued JWP in compact form to `jwp`.
+ * The header must be a JSON object with `alg` set to the ciphersuite id
+ */
+int32_t bbs_sign_context_finish_jwp(uint64_t handle,
+                                    struct ByteArray header,
+                                    struct ByteBuffer *jwp,
+                                    struct ExternError *err);
+#endif
+
+int32_t bbs_threshold_session_size(void);
+
+int32_t bbs_partial_signature_size(void);
+
+/**
+ * Size of one nonce share record: index (u32 big endian) || nonce share || zero mask share
+ */
+int32_t bbs_threshold_nonce_share_size(void);
+
+/**
+ * Generate the public values `e` and `s` for one threshold signature.
+ * Every party taking part in the signature must use the same session
+ */
+int32_t bbs_threshold_session_init(struct ByteBuffer *session, struct ExternError *err);
+
+/**
+ * Generate `total` shares of a random nonce contribution and of a random mask with threshold
+ * `threshold`. Every party taking part in a signature generates its own contribution and sends
+ * the share record for party `j` to party `j`, which adds it with `bbs_sign_context_add_nonce_share`.
+ * Use fresh shares for every signature, reusing them leaks the secret key
+ */
+int32_t bbs_threshold_generate_nonce_shares(uint32_t threshold,
+                                            uint32_t total,
+                                            struct ByteBuffer *shares,
+                                            struct ExternError *err);
+
+/**
+ * Set this party's secret key share as output by `bls_generate_g2_key_shares`
+ */
+int32_t bbs_sign_context_set_secret_key_share(uint64_t handle,
+                                              struct ByteArray share,
+                                              struct ExternError *err);
+
+/**
+ * Add the nonce share this party received from one of the signing parties
+ */
+int32_t bbs_sign_context_add_nonce_share(uint64_t handle,
+                                         struct ByteArray share,
+                                         struct ExternError *err);
+
+int32_t bbs_sign_context_set_session(uint64_t handle,
+                                     struct ByteArray session,
+                                     struct ExternError *err);
+
+/**
+ * Create this party's partial signature over the messages from its secret key share,
+ * nonce shares and the session. The context is deleted even if this fails
+ */
+int32_t bbs_sign_context_partial_finish(uint64_t handle,
+                                        struct ByteBuffer *partial_signature,
+                                        struct ExternError *err);
+
+int32_t bbs_sign_context_add_partial_signature(uint64_t handle,
+                                               struct ByteArray partial_signature,
+                                               struct ExternError *err);
+
+/**
+ * Combine the partial signatures into a signature over the messages.
+ * With threshold `t` at least `2t - 1` partial signatures are required, all from
+ * parties that added the nonce shares of the same set of parties.
+ * The signature is checked against the public key before it is returned.
+ * The context is deleted even if this fails
+ */
+int32_t bbs_sign_context_combine_finish(uint64_t handle,
+                                        struct ByteBuffer *signature,
+                                        struct ExternError *err);
+
 uint64_t bbs_verify_context_init(struct ExternError *err);
 
 int32_t bbs_verify_context_add_message_string(uint64_t handle,
@@ -488,12 +691,37 @@ int32_t bbs_verify_context_set_signature(uint64_t handle,
                                          struct ByteArray value,
                                          struct ExternError *err);
 
+#if defined(DEFINE_COSE)
+/**
+ * Set the signature from its COSE encoding.
+ * The issuer key id is not checked, set the public key it names
+ */
+int32_t bbs_verify_context_set_cose_signature(uint64_t handle,
+                                              struct ByteArray cose,
+                                              struct ExternError *err);
+#endif
+
 int32_t bbs_verify_context_finish(uint64_t handle, struct ExternError *err);
 
 void free_verify_proof(uint64_t v, struct ExternError *err);
 
+/**
+ * The number of messages signed by the signature behind `proof`,
+ * or -1 if `proof` is too short or an envelope for something else
+ */
 int32_t bbs_get_total_messages_count_for_proof(struct ByteArray proof);
 
+/**
+ * Read the framing of `proof` without verifying it.
+ * `revealed` receives the revealed message indices in ascending order, each a big-endian u32
+ */
+int32_t bbs_proof_inspect(struct ByteArray proof,
+                          uint32_t *message_count,
+                          struct ByteBuffer *revealed,
+                          uint32_t *hidden_count,
+                          uint32_t *version,
+                          struct ExternError *err);
+
 uint64_t bbs_verify_proof_context_init(struct ExternError *err);
 
 int32_t bbs_verify_proof_context_add_message_string(uint64_t handle,
@@ -508,10 +736,41 @@ int32_t bbs_verify_proof_context_add_message_prehashed(uint64_t handle,
                                                        struct ByteArray message,
                                                        struct ExternError *err);
 
+/**
+ * Hash and add the revealed `message` at `index`.
+ * Messages added this way are matched to the proof's revealed indices instead of by the order they were added
+ */
+int32_t bbs_verify_proof_context_add_revealed_message(uint64_t handle,
+                                                      uint32_t index,
+                                                      struct ByteArray message,
+                                                      struct ExternError *err);
+
 int32_t bbs_verify_proof_context_set_proof(uint64_t handle,
                                            struct ByteArray value,
                                            struct ExternError *err);
 
+#if defined(DEFINE_COSE)
+/**
+ * Set the proof from its COSE encoding.
+ * The issuer key id is not checked, set the public key it names
+ */
+int32_t bbs_verify_proof_context_set_cose_proof(uint64_t handle,
+                                                struct ByteArray cose,
+                                                struct ExternError *err);
+#endif
+
+#if defined(DEFINE_JWP)
+/**
+ * Set the proof, nonce and revealed messages from the presented JWP `value`,
+ * checking its presentation header carries the `nonce` the verifier sent.
+ * Set the issuer's public key then call `bbs_verify_proof_context_finish`
+ */
+int32_t bbs_verify_proof_context_set_jwp(uint64_t handle,
+                                         struct ByteArray value,
+                                         FfiStr nonce,
+                                         struct ExternError *err);
+#endif
+
 int32_t bbs_verify_proof_context_set_public_key(uint64_t handle,
                                                 struct ByteArray value,
                                                 struct ExternError *err);
@@ -590,6 +849,25 @@ int32_t bls_generate_blinded_g1_key(struct ByteArray seed,
                                     struct ByteBuffer *blinding_factor,
                                     struct ExternError *err);
 
+/**
+ * Size of one secret key share record: index (u32 big endian) || share
+ */
+int32_t bls_secret_key_share_size(void);
+
+/**
+ * Generate a BLS key pair in G2 like `bls_generate_g2_key` and split the secret key
+ * into `total` Shamir shares on a degree `threshold - 1` polynomial. Any `2 * threshold - 1`
+ * of them can sign together, so `threshold` can be at most (`total` + 1) / 2.
+ * `shares` is the concatenation of `total` share records for parties 1..=`total`.
+ * The secret key itself is not returned
+ */
+int32_t bls_generate_g2_key_shares(struct ByteArray seed,
+                                   uint32_t threshold,
+                                   uint32_t total,
+                                   struct ByteBuffer *public_key,
+                                   struct ByteBuffer *shares,
+                                   struct ExternError *err);
+
 int32_t bls_get_public_key(struct ByteArray secret_key,
                            struct ByteBuffer *public_key,
                            struct ExternError *err);
@@ -604,4 +882,301 @@ int32_t bls_public_key_to_bbs_key(struct ByteArray d_public_key,
                                   struct ByteBuffer *public_key,
                                   struct ExternError *err);
 
+/**
+ * Create a proof of possession for the secret key of a G2 public key
+ * as generated by `bls_generate_g2_key`. The proof is a G1 element
+ */
+int32_t bls_pop_prove_g2(struct ByteArray secret_key,
+                         struct ByteBuffer *proof,
+                         struct ExternError *err);
+
+/**
+ * Create a proof of possession for the secret key of a G1 public key
+ * as generated by `bls_generate_g1_key`. The proof is a G2 element
+ */
+int32_t bls_pop_prove_g1(struct ByteArray secret_key,
+                         struct ByteBuffer *proof,
+                         struct ExternError *err);
+
+/**
+ * Verify a proof of possession for a G2 public key.
+ * Returns 0 if the proof is valid
+ */
+int32_t bls_pop_verify_g2(struct ByteArray public_key,
+                          struct ByteArray proof,
+                          struct ExternError *err);
+
+/**
+ * Verify a proof of possession for a G1 public key.
+ * Returns 0 if the proof is valid
+ */
+int32_t bls_pop_verify_g1(struct ByteArray public_key,
+                          struct ByteArray proof,
+                          struct ExternError *err);
+
+int32_t bls_blinded_key_proof_size(void);
+
+/**
+ * Prove knowledge of the secret key `x` and blinding factor `r` of a blinded G2 public key
+ * `w` = `g2` ^ `x` * `blinding_g2` ^ `r` as generated by `bls_generate_blinded_g2_key`.
+ * `nonce` is supplied by the verifier. The proof does not reveal `g2` ^ `x`
+ */
+int32_t bls_blinded_key_prove_g2(struct ByteArray secret_key,
+                                 struct ByteArray blinding_factor,
+                                 struct ByteArray nonce,
+                                 struct ByteBuffer *proof,
+                                 struct ExternError *err);
+
+/**
+ * Prove knowledge of the secret key `x` and blinding factor `r` of a blinded G1 public key
+ * `w` = `g1` ^ `x` * `blinding_g1` ^ `r` as generated by `bls_generate_blinded_g1_key`.
+ * `nonce` is supplied by the verifier. The proof does not reveal `g1` ^ `x`
+ */
+int32_t bls_blinded_key_prove_g1(struct ByteArray secret_key,
+                                 struct ByteArray blinding_factor,
+                                 struct ByteArray nonce,
+                                 struct ByteBuffer *proof,
+                                 struct ExternError *err);
+
+/**
+ * Verify a proof of knowledge for a blinded G2 public key.
+ * Returns 0 if the proof is valid
+ */
+int32_t bls_blinded_key_verify_g2(struct ByteArray public_key,
+                                  struct ByteArray nonce,
+                                  struct ByteArray proof,
+                                  struct ExternError *err);
+
+/**
+ * Verify a proof of knowledge for a blinded G1 public key.
+ * Returns 0 if the proof is valid
+ */
+int32_t bls_blinded_key_verify_g1(struct ByteArray public_key,
+                                  struct ByteArray nonce,
+                                  struct ByteArray proof,
+                                  struct ExternError *err);
+
+/**
+ * Open a blinded G2 public key with its blinding factor, returning `g2` ^ `x`
+ */
+int32_t bls_blinded_key_unblind_g2(struct ByteArray blinded_public_key,
+                                   struct ByteArray blinding_factor,
+                                   struct ByteBuffer *public_key,
+                                   struct ExternError *err);
+
+/**
+ * Open a blinded G1 public key with its blinding factor, returning `g1` ^ `x`
+ */
+int32_t bls_blinded_key_unblind_g1(struct ByteArray blinded_public_key,
+                                   struct ByteArray blinding_factor,
+                                   struct ByteBuffer *public_key,
+                                   struct ExternError *err);
+
+void free_bls_aggregate(uint64_t v, struct ExternError *err);
+
+void free_bls_aggregate_verify(uint64_t v, struct ExternError *err);
+
+int32_t bls_signature_g1_size(void);
+
+int32_t bls_signature_g2_size(void);
+
+/**
+ * Sign `message` with the secret key of a G2 public key.
+ * The signature is a G1 element (minimal-signature-size)
+ */
+int32_t bls_sign_g2(struct ByteArray secret_key,
+                    struct ByteArray message,
+                    struct ByteBuffer *signature,
+                    struct ExternError *err);
+
+/**
+ * Sign `message` with the secret key of a G1 public key.
+ * The signature is a G2 element (minimal-pubkey-size)
+ */
+int32_t bls_sign_g1(struct ByteArray secret_key,
+                    struct ByteArray message,
+                    struct ByteBuffer *signature,
+                    struct ExternError *err);
+
+/**
+ * Verify a G1 signature on `message` with a G2 public key.
+ * Returns 0 if the signature is valid
+ */
+int32_t bls_verify_g2(struct ByteArray public_key,
+                      struct ByteArray message,
+                      struct ByteArray signature,
+                      struct ExternError *err);
+
+/**
+ * Verify a G2 signature on `message` with a G1 public key.
+ * Returns 0 if the signature is valid
+ */
+int32_t bls_verify_g1(struct ByteArray public_key,
+                      struct ByteArray message,
+                      struct ByteArray signature,
+                      struct ExternError *err);
+
+uint64_t bls_aggregate_context_init(struct ExternError *err);
+
+int32_t bls_aggregate_context_add_signature(uint64_t handle,
+                                            struct ByteArray signature,
+                                            struct ExternError *err);
+
+/**
+ * Aggregate all signatures in the context. The signatures must all
+ * be in the same group. The context is deleted even if aggregation fails
+ */
+int32_t bls_aggregate_context_finish(uint64_t handle,
+                                     struct ByteBuffer *aggregate,
+                                     struct ExternError *err);
+
+uint64_t bls_aggregate_verify_context_init(struct ExternError *err);
+
+int32_t bls_aggregate_verify_context_add_public_key(uint64_t handle,
+                                                    struct ByteArray public_key,
+                                                    struct ExternError *err);
+
+int32_t bls_aggregate_verify_context_add_message(uint64_t handle,
+                                                 struct ByteArray message,
+                                                 struct ExternError *err);
+
+int32_t bls_aggregate_verify_context_set_signature(uint64_t handle,
+                                                   struct ByteArray signature,
+                                                   struct ExternError *err);
+
+/**
+ * Verify an aggregate signature where each public key signed the message
+ * added at the same position. Every public key must have a verified proof of possession.
+ * Returns 0 if the signature is valid. The context is deleted either way
+ */
+int32_t bls_aggregate_verify_context_finish(uint64_t handle, struct ExternError *err);
+
+/**
+ * Verify an aggregate signature where every public key signed the single message
+ * in the context. Every public key must have a verified proof of possession.
+ * Returns 0 if the signature is valid. The context is deleted either way
+ */
+int32_t bls_fast_aggregate_verify_context_finish(uint64_t handle, struct ExternError *err);
+
+#if defined(DEFINE_COSE)
+/**
+ * Encode `value`, a signature or proof, with the issuer key id `kid`
+ */
+int32_t bbs_cose_encode(enum ArtifactType kind,
+                        struct ByteArray value,
+                        struct ByteArray kid,
+                        struct ByteBuffer *cose,
+                        struct ExternError *err);
+#endif
+
+#if defined(DEFINE_COSE)
+/**
+ * The type, issuer key id and raw bytes of the signature or proof in `cose`
+ */
+int32_t bbs_cose_decode(struct ByteArray cose,
+                        enum ArtifactType *kind,
+                        struct ByteBuffer *kid,
+                        struct ByteBuffer *value,
+                        struct ExternError *err);
+#endif
+
+/**
+ * Wrap `value`, an artifact of type `kind`, in an envelope.
+ * `value` is checked to be a valid `kind` first
+ */
+int32_t bbs_envelope_seal(enum ArtifactType kind,
+                          struct ByteArray value,
+                          struct ByteBuffer *envelope,
+                          struct ExternError *err);
+
+/**
+ * The type and raw bytes of `envelope`
+ */
+int32_t bbs_envelope_open(struct ByteArray envelope,
+                          enum ArtifactType *kind,
+                          struct ByteBuffer *value,
+                          struct ExternError *err);
+
+/**
+ * Run the operation named by `op` in the JSON object `request`. Keys, messages,
+ * signatures, commitments, nonces and proofs are unpadded base64url strings.
+ * Returns `{"result": {...}}` on success or `{"error": {"code": ..., "message": ...}}`.
+ * The response must be freed with `bbs_string_free`
+ */
+char *bbs_call_json(FfiStr request);
+
+/**
+ * Copy the error of the last function called on this thread that takes an `err` parameter
+ * into `err`. Those functions accept a null `err` so callers can check this instead.
+ * The message must be freed with `bbs_string_free`. Returns the error code, 0 on success
+ */
+int32_t bbs_get_last_error(struct ExternError *err);
+
+/**
+ * Use `callback` as the source of all randomness in this library, for example
+ * to supply a hardware entropy source. `callback` may be called from any thread.
+ * Pass a null `callback` to return to the operating system random number generator
+ */
+int32_t bbs_set_rng_callback(BbsRngCallback callback, void *user_data, struct ExternError *err);
+
+#if defined(DEFINE_DETERMINISTIC_RNG)
+/**
+ * Replace all randomness in this library with a ChaCha20 stream seeded from `seed`
+ * so keys, commitments, signatures and proofs are reproducible.
+ * Only for tests, results depend on the order of calls across all threads.
+ * Pass an empty `seed` to return to the operating system random number generator
+ */
+int32_t bbs_set_rng_seed(struct ByteArray seed, struct ExternError *err);
+#endif
+
+/**
+ * The secret key operation performed by a `BbsSigner`: `a` = `b` ^ (1 / (`x` + `e`)).
+ * For signers that run this library in another process or key store
+ */
+int32_t bbs_signer_compute(struct ByteArray secret_key,
+                           struct ByteArray b,
+                           struct ByteArray e,
+                           struct ByteBuffer *a,
+                           struct ExternError *err);
+
+/**
+ * Check that `public_key` is a BLS public key in G2 that
+ * is on the curve, in the prime order subgroup and not the identity.
+ * Returns a `ValidationResult` code
+ */
+int32_t bls_validate_public_key(struct ByteArray public_key, struct ExternError *err);
+
+/**
+ * Check that `public_key` is a well formed BBS public key where
+ * `w`, `h0` and every message generator is a valid non-identity subgroup element.
+ * Returns a `ValidationResult` code
+ */
+int32_t bbs_validate_public_key(struct ByteArray public_key, struct ExternError *err);
+
+/**
+ * Check that `signature` is a well formed BBS signature where `A` is a
+ * valid non-identity subgroup element and `e`, `s` are valid field elements.
+ * This does not verify the signature against any messages.
+ * Returns a `ValidationResult` code
+ */
+int32_t bbs_validate_signature(struct ByteArray signature, struct ExternError *err);
+
+/**
+ * The release version of this library, for example "0.2.0".
+ * The string is static and must not be freed
+ */
+const char *bbs_library_version(void);
+
+/**
+ * The `BBS_ABI_VERSION` this library was built with
+ */
+int32_t bbs_abi_version(void);
+
+/**
+ * A JSON object with the `libraryVersion`, `abiVersion`, supported `ciphersuites`,
+ * `proofExtensions` and which optional cargo `features` are compiled in.
+ * The string is static and must not be freed
+ */
+const char *bbs_supported_features(void);
+
 #endif /* __bbs__plus__included__ */
@@ -9,7 +9,7 @@ OUTPUT_LOCATION=$2
 
 if [ -z "$PLATFORM" ]
 then
-  echo "ERROR: PLATFORM argument must be supplied and must be one of the following: WINDOWS, LINUX, MACOS, IOS, ANDROID"
+  echo "ERROR: PLATFORM argument must be supplied and must be one of the following: WINDOWS, LINUX, MACOS, IOS, ANDROID, WASM, UNIFFI"
   exit 1
 fi
 
@@ -119,6 +119,22 @@ case $PLATFORM in
         cargo build --target x86_64-linux-android --release
         cp ./target/x86_64-linux-android/release/libbbs.so $OUTPUT_LOCATION/android/x86_64/
       ;;
+  WASM)
+      # Requires wasm-bindgen-cli matching the wasm-bindgen version in Cargo.lock
+      rustup target add wasm32-unknown-unknown
+      mkdir -p $OUTPUT_LOCATION/wasm
+      cargo build --target wasm32-unknown-unknown --release --features wasm
+      wasm-bindgen --target nodejs --out-dir $OUTPUT_LOCATION/wasm ./target/wasm32-unknown-unknown/release/bbs.wasm
+    ;;
+  UNIFFI)
+      # Generates the Kotlin and Swift bindings for the host library built with the uniffi feature
+      mkdir -p $OUTPUT_LOCATION/uniffi
+      cargo build --release --features uniffi
+      LIBRARY=$(ls ./target/release/libbbs.so ./target/release/libbbs.dylib 2>/dev/null | head -n 1)
+      cargo run --release --features uniffi-bindgen --bin uniffi-bindgen -- generate --library $LIBRARY --language kotlin --out-dir $OUTPUT_LOCATION/uniffi/kotlin
+      cargo run --release --features uniffi-bindgen --bin uniffi-bindgen -- generate --library $LIBRARY --language swift --out-dir $OUTPUT_LOCATION/uniffi/swift
+      cp $LIBRARY $OUTPUT_LOCATION/uniffi
+    ;;
   *)
     echo "ERROR: PLATFORM unknown: $1"
     exit 1
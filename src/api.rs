@@ -0,0 +1,1049 @@
+//! Safe Rust API for BBS+ signatures and proofs.
+//!
+//! Each operation is a builder that takes its required inputs in `new` and its
+//! messages through chained calls. The C, JNI and JSON interfaces are thin shims
+//! over these builders so every binding produces the same bytes.
+//!
+//! ```
+//! use bbs::api::*;
+//!
+//! let (dpk, sk) = generate_key(None).unwrap();
+//! let pk = dpk.to_public_key(2).unwrap();
+//! let signature = SignBuilder::new(&pk)
+//!     .secret_key(&sk)
+//!     .message(b"name")
+//!     .message(b"age")
+//!     .sign()
+//!     .unwrap();
+//!
+//! let nonce = ProofNonce::hash(b"nonce");
+//! let proof = CreateProofBuilder::new(&pk, &signature, &nonce)
+//!     .revealed(b"name")
+//!     .hidden(b"age")
+//!     .create()
+//!     .unwrap();
+//!
+//! let status = VerifyProofBuilder::new(&pk, &proof, &nonce)
+//!     .message(b"name")
+//!     .verify()
+//!     .unwrap();
+//! assert!(status.is_valid());
+//! ```
+
+use crate::envelope::{self, ArtifactType};
+use crate::signer::{self, BbsSigner, SigningKey};
+use crate::{proof, zeroize_with, BbsFfiError};
+use bbs::errors::BBSError;
+use serde::{
+    de::{Error as DError, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    convert::TryFrom,
+    fmt,
+};
+
+pub use crate::parse::Parse;
+pub use bbs::prelude::{
+    BlindSignature, BlindSignatureContext, Commitment, DeterministicPublicKey, HashElem,
+    HiddenMessage, PoKOfSignatureProof, PoKOfSignatureProofStatus, ProofChallenge, ProofMessage,
+    ProofNonce, PublicKey, SecretKey, Signature, SignatureBlinding, SignatureMessage,
+    ToVariableLengthBytes,
+};
+
+/// An error from any operation in this module
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error(String);
+
+impl Error {
+    /// The error message, as reported over the FFI
+    pub fn message(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<BbsFfiError> for Error {
+    fn from(e: BbsFfiError) -> Self {
+        Self(e.0)
+    }
+}
+
+impl From<Error> for BbsFfiError {
+    fn from(e: Error) -> Self {
+        Self(e.0)
+    }
+}
+
+impl From<BBSError> for Error {
+    fn from(e: BBSError) -> Self {
+        BbsFfiError::from(e).into()
+    }
+}
+
+/// The result of operations in this module
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Generate a BLS key pair with the public key in G2, deterministically from `seed` if set.
+/// Convert the public key for signing with `DeterministicPublicKey::to_public_key`
+pub fn generate_key(seed: Option<&[u8]>) -> Result<(DeterministicPublicKey, SecretKey)> {
+    let (pk, sk) = crate::bls_generate_g2_key(seed.map(|s| s.to_vec()));
+    Ok((
+        DeterministicPublicKey::try_from(pk)?,
+        SecretKey::try_from(sk)?,
+    ))
+}
+
+/// Signs messages with a secret key or an external signer
+pub struct SignBuilder<'a> {
+    public_key: &'a PublicKey,
+    key: Option<SigningKey<'a>>,
+    messages: Vec<SignatureMessage>,
+}
+
+impl<'a> SignBuilder<'a> {
+    /// Sign for `public_key`
+    pub fn new(public_key: &'a PublicKey) -> Self {
+        Self {
+            public_key,
+            key: None,
+            messages: Vec::new(),
+        }
+    }
+
+    /// Sign with `secret_key`
+    pub fn secret_key(mut self, secret_key: &'a SecretKey) -> Self {
+        self.key = Some(SigningKey::Secret(secret_key));
+        self
+    }
+
+    /// Sign with an external signer instead of a secret key
+    pub fn signer(mut self, signer: &'a BbsSigner) -> Self {
+        self.key = Some(SigningKey::External(signer));
+        self
+    }
+
+    /// Hash and append the next message
+    pub fn message<M: AsRef<[u8]>>(self, message: M) -> Self {
+        self.prehashed_message(SignatureMessage::hash(message))
+    }
+
+    /// Append the next message that is already hashed
+    pub fn prehashed_message(mut self, message: SignatureMessage) -> Self {
+        self.messages.push(message);
+        self
+    }
+
+    /// Append messages that are already hashed
+    pub fn prehashed_messages<I: IntoIterator<Item = SignatureMessage>>(
+        mut self,
+        messages: I,
+    ) -> Self {
+        self.messages.extend(messages);
+        self
+    }
+
+    /// Sign the messages
+    pub fn sign(self) -> Result<Signature> {
+        let key = self
+            .key
+            .ok_or_else(|| Error("Secret Key or signer must be set".to_string()))?;
+        Ok(signer::sign(key, &self.messages, self.public_key)?)
+    }
+}
+
+impl Drop for SignBuilder<'_> {
+    fn drop(&mut self) {
+        for m in self.messages.iter_mut() {
+            zeroize_with(m, SignatureMessage::default());
+        }
+    }
+}
+
+/// Verifies a signature over messages
+pub struct VerifyBuilder<'a> {
+    public_key: &'a PublicKey,
+    signature: &'a Signature,
+    messages: Vec<SignatureMessage>,
+}
+
+impl<'a> VerifyBuilder<'a> {
+    /// Verify `signature` for `public_key`
+    pub fn new(public_key: &'a PublicKey, signature: &'a Signature) -> Self {
+        Self {
+            public_key,
+            signature,
+            messages: Vec::new(),
+        }
+    }
+
+    /// Hash and append the next message
+    pub fn message<M: AsRef<[u8]>>(self, message: M) -> Self {
+        self.prehashed_message(SignatureMessage::hash(message))
+    }
+
+    /// Append the next message that is already hashed
+    pub fn prehashed_message(mut self, message: SignatureMessage) -> Self {
+        self.messages.push(message);
+        self
+    }
+
+    /// Append messages that are already hashed
+    pub fn prehashed_messages<I: IntoIterator<Item = SignatureMessage>>(
+        mut self,
+        messages: I,
+    ) -> Self {
+        self.messages.extend(messages);
+        self
+    }
+
+    /// `true` if the signature is valid for the messages
+    pub fn verify(self) -> Result<bool> {
+        Ok(self.signature.verify(&self.messages, self.public_key)?)
+    }
+}
+
+/// The holder's output from a blind commitment
+pub struct BlindCommitment {
+    /// The commitment to the hidden messages for the issuer
+    pub commitment: Commitment,
+    /// The commitment and the proof of knowledge of its messages for the issuer
+    pub context: BlindSignatureContext,
+    /// Kept by the holder to unblind the signature
+    pub blinding_factor: SignatureBlinding,
+}
+
+/// Commits to the messages the holder hides from the issuer
+pub struct BlindCommitmentBuilder<'a> {
+    public_key: &'a PublicKey,
+    nonce: &'a ProofNonce,
+    messages: BTreeMap<usize, SignatureMessage>,
+}
+
+impl<'a> BlindCommitmentBuilder<'a> {
+    /// Commit for `public_key` with the issuer's `nonce`
+    pub fn new(public_key: &'a PublicKey, nonce: &'a ProofNonce) -> Self {
+        Self {
+            public_key,
+            nonce,
+            messages: BTreeMap::new(),
+        }
+    }
+
+    /// Hash and hide the message at `index`
+    pub fn message<M: AsRef<[u8]>>(self, index: usize, message: M) -> Self {
+        self.prehashed_message(index, SignatureMessage::hash(message))
+    }
+
+    /// Hide the message at `index` that is already hashed
+    pub fn prehashed_message(mut self, index: usize, message: SignatureMessage) -> Self {
+        self.messages.insert(index, message);
+        self
+    }
+
+    /// Hide messages by index that are already hashed
+    pub fn prehashed_messages<I: IntoIterator<Item = (usize, SignatureMessage)>>(
+        mut self,
+        messages: I,
+    ) -> Self {
+        self.messages.extend(messages);
+        self
+    }
+
+    /// Create the commitment
+    pub fn commit(self) -> Result<BlindCommitment> {
+        let (context, blinding_factor) =
+            proof::blind_signature_context(self.public_key, &self.messages, self.nonce)?;
+        Ok(BlindCommitment {
+            commitment: context.commitment,
+            context,
+            blinding_factor,
+        })
+    }
+}
+
+impl Drop for BlindCommitmentBuilder<'_> {
+    fn drop(&mut self) {
+        for m in self.messages.values_mut() {
+            zeroize_with(m, SignatureMessage::default());
+        }
+    }
+}
+
+/// Verifies the holder's proof of knowledge of the committed messages
+pub struct VerifyBlindCommitmentBuilder<'a> {
+    public_key: &'a PublicKey,
+    context: &'a BlindSignatureContext,
+    nonce: &'a ProofNonce,
+    blinded: BTreeSet<usize>,
+}
+
+impl<'a> VerifyBlindCommitmentBuilder<'a> {
+    /// Verify `context` for `public_key` against the issuer's `nonce`
+    pub fn new(
+        public_key: &'a PublicKey,
+        context: &'a BlindSignatureContext,
+        nonce: &'a ProofNonce,
+    ) -> Self {
+        Self {
+            public_key,
+            context,
+            nonce,
+            blinded: BTreeSet::new(),
+        }
+    }
+
+    /// The message at `index` is hidden in the commitment
+    pub fn blinded(mut self, index: usize) -> Self {
+        self.blinded.insert(index);
+        self
+    }
+
+    /// `true` if the proof in the context is valid for the hidden indices
+    pub fn verify(self) -> Result<bool> {
+        let revealed = (0..self.public_key.message_count())
+            .filter(|i| !self.blinded.contains(i))
+            .collect();
+        Ok(self
+            .context
+            .verify(&revealed, self.public_key, self.nonce)?)
+    }
+}
+
+/// Signs the known messages and the holder's commitment
+pub struct BlindSignBuilder<'a> {
+    public_key: &'a PublicKey,
+    commitment: &'a Commitment,
+    key: Option<SigningKey<'a>>,
+    messages: BTreeMap<usize, SignatureMessage>,
+}
+
+impl<'a> BlindSignBuilder<'a> {
+    /// Sign `commitment` for `public_key`
+    pub fn new(public_key: &'a PublicKey, commitment: &'a Commitment) -> Self {
+        Self {
+            public_key,
+            commitment,
+            key: None,
+            messages: BTreeMap::new(),
+        }
+    }
+
+    /// Sign with `secret_key`
+    pub fn secret_key(mut self, secret_key: &'a SecretKey) -> Self {
+        self.key = Some(SigningKey::Secret(secret_key));
+        self
+    }
+
+    /// Sign with an external signer instead of a secret key
+    pub fn signer(mut self, signer: &'a BbsSigner) -> Self {
+        self.key = Some(SigningKey::External(signer));
+        self
+    }
+
+    /// Hash and sign the known message at `index`
+    pub fn message<M: AsRef<[u8]>>(self, index: usize, message: M) -> Self {
+        self.prehashed_message(index, SignatureMessage::hash(message))
+    }
+
+    /// Sign the known message at `index` that is already hashed
+    pub fn prehashed_message(mut self, index: usize, message: SignatureMessage) -> Self {
+        self.messages.insert(index, message);
+        self
+    }
+
+    /// Sign known messages by index that are already hashed
+    pub fn prehashed_messages<I: IntoIterator<Item = (usize, SignatureMessage)>>(
+        mut self,
+        messages: I,
+    ) -> Self {
+        self.messages.extend(messages);
+        self
+    }
+
+    /// Create the blind signature
+    pub fn sign(self) -> Result<BlindSignature> {
+        let key = self
+            .key
+            .ok_or_else(|| Error("Secret Key or signer must be set".to_string()))?;
+        Ok(signer::blind_sign(
+            key,
+            self.commitment,
+            &self.messages,
+            self.public_key,
+        )?)
+    }
+}
+
+impl Drop for BlindSignBuilder<'_> {
+    fn drop(&mut self) {
+        for m in self.messages.values_mut() {
+            zeroize_with(m, SignatureMessage::default());
+        }
+    }
+}
+
+/// Unblind `blind_signature` with the holder's `blinding_factor`
+pub fn unblind_signature(
+    blind_signature: &BlindSignature,
+    blinding_factor: &SignatureBlinding,
+) -> Signature {
+    blind_signature.to_unblinded(blinding_factor)
+}
+
+/// A proof of knowledge of a signature with the indices of its revealed messages.
+///
+/// The byte form is the message count as a big-endian u16, a big-endian bitvector
+/// of `(count / 8) + 1` bytes with bit `i` set if message `i` is revealed,
+/// then the compressed proof
+#[derive(Debug)]
+pub struct Proof {
+    bit_vector: Vec<u8>,
+    proof: PoKOfSignatureProof,
+}
+
+impl Proof {
+    /// Frame `proof` over `message_count` messages
+    pub fn new(
+        message_count: usize,
+        revealed: &BTreeSet<usize>,
+        proof: PoKOfSignatureProof,
+    ) -> Self {
+        let mut bit_vector = (message_count as u16).to_be_bytes().to_vec();
+        bit_vector.append(&mut revealed_to_bitvector(message_count, revealed));
+        Self { bit_vector, proof }
+    }
+
+    /// The number of messages signed
+    pub fn message_count(&self) -> usize {
+        u16::from_be_bytes(*array_ref![self.bit_vector, 0, 2]) as usize
+    }
+
+    /// The indices of the revealed messages
+    pub fn revealed(&self) -> BTreeSet<usize> {
+        bitvector_to_revealed(&self.bit_vector[2..])
+    }
+
+    /// The proof of knowledge without the framing
+    pub fn proof(&self) -> &PoKOfSignatureProof {
+        &self.proof
+    }
+
+    /// The revealed indices and the proof of knowledge
+    pub fn unpack(&self) -> (BTreeSet<usize>, PoKOfSignatureProof) {
+        (self.revealed(), self.proof.clone())
+    }
+
+    /// The framed byte form
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = self.bit_vector.to_vec();
+        data.append(&mut self.proof.to_bytes_compressed_form());
+        data
+    }
+}
+
+impl Parse for Proof {
+    fn parse(data: &[u8]) -> Result<Self> {
+        let data = envelope::open(ArtifactType::ProofArtifact, data)?;
+        if data.len() < 2 {
+            return Err(Error(format!(
+                "Proof must be at least 2 bytes, found {}",
+                data.len()
+            )));
+        }
+        let message_count = u16::from_be_bytes(*array_ref![data, 0, 2]) as usize;
+        let offset = (message_count / 8) + 3;
+        if data.len() < offset {
+            return Err(Error(format!(
+                "Proof for {} messages must be at least {} bytes, found {}",
+                message_count,
+                offset,
+                data.len()
+            )));
+        }
+        if let Some(i) = bitvector_to_revealed(&data[2..offset])
+            .range(message_count..)
+            .next()
+        {
+            return Err(Error(format!(
+                "Proof reveals message {} but has {} messages",
+                i, message_count
+            )));
+        }
+        Ok(Self {
+            bit_vector: data[..offset].to_vec(),
+            proof: PoKOfSignatureProof::parse(&data[offset..])?,
+        })
+    }
+}
+
+impl TryFrom<&[u8]> for Proof {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> std::result::Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl TryFrom<Vec<u8>> for Proof {
+    type Error = Error;
+
+    fn try_from(value: Vec<u8>) -> std::result::Result<Self, Self::Error> {
+        Self::try_from(value.as_slice())
+    }
+}
+
+impl Serialize for Proof {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(self.to_bytes().as_slice())
+    }
+}
+
+impl<'a> Deserialize<'a> for Proof {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'a>,
+    {
+        struct DeserializeVisitor;
+
+        impl Visitor<'_> for DeserializeVisitor {
+            type Value = Proof;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("expected byte array")
+            }
+
+            fn visit_bytes<E>(self, value: &[u8]) -> std::result::Result<Proof, E>
+            where
+                E: DError,
+            {
+                Proof::try_from(value)
+                    .map_err(|_| DError::invalid_value(serde::de::Unexpected::Bytes(value), &self))
+            }
+        }
+
+        deserializer.deserialize_bytes(DeserializeVisitor)
+    }
+}
+
+/// Proves knowledge of a signature, revealing only some of its messages
+pub struct CreateProofBuilder<'a> {
+    public_key: &'a PublicKey,
+    signature: &'a Signature,
+    nonce: &'a ProofNonce,
+    messages: Vec<ProofMessage>,
+}
+
+impl<'a> CreateProofBuilder<'a> {
+    /// Prove knowledge of `signature` for `public_key` bound to the verifier's `nonce`
+    pub fn new(public_key: &'a PublicKey, signature: &'a Signature, nonce: &'a ProofNonce) -> Self {
+        Self {
+            public_key,
+            signature,
+            nonce,
+            messages: Vec::new(),
+        }
+    }
+
+    /// Hash and append the next message, revealed to the verifier
+    pub fn revealed<M: AsRef<[u8]>>(self, message: M) -> Self {
+        self.proof_message(ProofMessage::Revealed(SignatureMessage::hash(message)))
+    }
+
+    /// Hash and append the next message, hidden from the verifier
+    pub fn hidden<M: AsRef<[u8]>>(self, message: M) -> Self {
+        self.proof_message(ProofMessage::Hidden(HiddenMessage::ProofSpecificBlinding(
+            SignatureMessage::hash(message),
+        )))
+    }
+
+    /// Hash and append the next message, hidden with a `blinding_factor` shared with another proof
+    pub fn hidden_with_blinding<M: AsRef<[u8]>>(
+        self,
+        message: M,
+        blinding_factor: ProofNonce,
+    ) -> Self {
+        self.proof_message(ProofMessage::Hidden(HiddenMessage::ExternalBlinding(
+            SignatureMessage::hash(message),
+            blinding_factor,
+        )))
+    }
+
+    /// Append the next message that is already hashed and classified
+    pub fn proof_message(mut self, message: ProofMessage) -> Self {
+        self.messages.push(message);
+        self
+    }
+
+    /// Append messages that are already hashed and classified
+    pub fn proof_messages<I: IntoIterator<Item = ProofMessage>>(mut self, messages: I) -> Self {
+        self.messages.extend(messages);
+        self
+    }
+
+    /// Create the proof
+    pub fn create(self) -> Result<Proof> {
+        let proof =
+            proof::create_proof(self.signature, self.public_key, &self.messages, self.nonce)?;
+        let revealed = self
+            .messages
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| matches!(m, ProofMessage::Revealed(_)))
+            .map(|(i, _)| i)
+            .collect();
+        Ok(Proof::new(self.messages.len(), &revealed, proof))
+    }
+}
+
+impl Drop for CreateProofBuilder<'_> {
+    fn drop(&mut self) {
+        for m in self.messages.iter_mut() {
+            match m {
+                ProofMessage::Revealed(m) => zeroize_with(m, SignatureMessage::default()),
+                ProofMessage::Hidden(HiddenMessage::ProofSpecificBlinding(m)) => {
+                    zeroize_with(m, SignatureMessage::default())
+                }
+                ProofMessage::Hidden(HiddenMessage::ExternalBlinding(m, b)) => {
+                    zeroize_with(m, SignatureMessage::default());
+                    zeroize_with(b, ProofNonce::default());
+                }
+            }
+        }
+    }
+}
+
+/// Verifies a proof against its revealed messages
+pub struct VerifyProofBuilder<'a> {
+    public_key: &'a PublicKey,
+    proof: &'a Proof,
+    nonce: &'a ProofNonce,
+    messages: Vec<SignatureMessage>,
+    indexed_messages: BTreeMap<usize, SignatureMessage>,
+}
+
+impl<'a> VerifyProofBuilder<'a> {
+    /// Verify `proof` for `public_key` against the verifier's `nonce`
+    pub fn new(public_key: &'a PublicKey, proof: &'a Proof, nonce: &'a ProofNonce) -> Self {
+        Self {
+            public_key,
+            proof,
+            nonce,
+            messages: Vec::new(),
+            indexed_messages: BTreeMap::new(),
+        }
+    }
+
+    /// Hash and append the next revealed message
+    pub fn message<M: AsRef<[u8]>>(self, message: M) -> Self {
+        self.prehashed_message(SignatureMessage::hash(message))
+    }
+
+    /// Append the next revealed message that is already hashed
+    pub fn prehashed_message(mut self, message: SignatureMessage) -> Self {
+        self.messages.push(message);
+        self
+    }
+
+    /// Append revealed messages that are already hashed
+    pub fn prehashed_messages<I: IntoIterator<Item = SignatureMessage>>(
+        mut self,
+        messages: I,
+    ) -> Self {
+        self.messages.extend(messages);
+        self
+    }
+
+    /// Hash and set the revealed message at `index`
+    pub fn revealed_message<M: AsRef<[u8]>>(self, index: usize, message: M) -> Self {
+        self.prehashed_revealed_message(index, SignatureMessage::hash(message))
+    }
+
+    /// Set the revealed message at `index` that is already hashed
+    pub fn prehashed_revealed_message(mut self, index: usize, message: SignatureMessage) -> Self {
+        self.indexed_messages.insert(index, message);
+        self
+    }
+
+    /// Set revealed messages by index that are already hashed
+    pub fn prehashed_revealed_messages<I: IntoIterator<Item = (usize, SignatureMessage)>>(
+        mut self,
+        messages: I,
+    ) -> Self {
+        self.indexed_messages.extend(messages);
+        self
+    }
+
+    /// Pair the proof's revealed indices with the messages given in order or by index
+    fn revealed_messages(
+        &self,
+        revealed: &BTreeSet<usize>,
+    ) -> Result<BTreeMap<usize, SignatureMessage>> {
+        if self.indexed_messages.is_empty() {
+            // These should be equal
+            if revealed.len() != self.messages.len() {
+                return Err(Error("Indices and messages are not equal".to_string()));
+            }
+            return Ok(revealed
+                .iter()
+                .copied()
+                .zip(self.messages.iter().copied())
+                .collect());
+        }
+        if !self.messages.is_empty() {
+            return Err(Error(
+                "Revealed messages must be given either in order or by index, not both".to_string(),
+            ));
+        }
+
+        let missing: Vec<_> = revealed
+            .iter()
+            .filter(|i| !self.indexed_messages.contains_key(i))
+            .collect();
+        let extra: Vec<_> = self
+            .indexed_messages
+            .keys()
+            .filter(|i| !revealed.contains(i))
+            .collect();
+        let mut problems = Vec::new();
+        if !missing.is_empty() {
+            problems.push(format!(
+                "missing messages for revealed indices {:?}",
+                missing
+            ));
+        }
+        if !extra.is_empty() {
+            problems.push(format!(
+                "unexpected messages for unrevealed indices {:?}",
+                extra
+            ));
+        }
+        if !problems.is_empty() {
+            return Err(Error(format!(
+                "Proof reveals indices {:?}: {}",
+                revealed.iter().collect::<Vec<_>>(),
+                problems.join(", ")
+            )));
+        }
+        Ok(self.indexed_messages.clone())
+    }
+
+    /// Check the proof. The messages must be given in the order they were revealed or by index
+    pub fn verify(self) -> Result<PoKOfSignatureProofStatus> {
+        let revealed = self.proof.revealed();
+
+        if self.proof.message_count() != self.public_key.message_count() {
+            return Err(Error(format!(
+                "Proof is for {} messages but the public key has {}",
+                self.proof.message_count(),
+                self.public_key.message_count()
+            )));
+        }
+
+        let proof_msgs = self.revealed_messages(&revealed)?;
+
+        let proof = self.proof.proof();
+        let mut challenge_bytes = proof.get_bytes_for_challenge(revealed.clone(), self.public_key);
+        challenge_bytes.extend_from_slice(&self.nonce.to_bytes_compressed_form()[..]);
+
+        let challenge_verifier = ProofChallenge::hash(&challenge_bytes);
+        Ok(proof.verify(self.public_key, &proof_msgs, &challenge_verifier)?)
+    }
+}
+
+/// Expects `revealed` to be sorted
+fn revealed_to_bitvector(total: usize, revealed: &BTreeSet<usize>) -> Vec<u8> {
+    let mut bytes = vec![0u8; (total / 8) + 1];
+
+    for r in revealed {
+        let idx = *r / 8;
+        let bit = (*r % 8) as u8;
+        bytes[idx] |= 1u8 << bit;
+    }
+
+    // Convert to big endian
+    bytes.reverse();
+    bytes
+}
+
+/// Convert big-endian vector to u32
+pub(crate) fn bitvector_to_revealed(data: &[u8]) -> BTreeSet<usize> {
+    let mut revealed_messages = BTreeSet::new();
+    let mut scalar = 0;
+
+    for b in data.iter().rev() {
+        let mut v = *b;
+        let mut remaining = 8;
+        while v > 0 {
+            let revealed = v & 1u8;
+            if revealed == 1 {
+                revealed_messages.insert(scalar);
+            }
+            v >>= 1;
+            scalar += 1;
+            remaining -= 1;
+        }
+        scalar += remaining;
+    }
+    revealed_messages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::lock_rng;
+
+    const MESSAGES: [&[u8]; 3] = [b"name", b"age", b"city"];
+
+    fn keys() -> (PublicKey, SecretKey) {
+        let (dpk, sk) = generate_key(Some(b"api test seed")).unwrap();
+        (dpk.to_public_key(MESSAGES.len()).unwrap(), sk)
+    }
+
+    fn sign(pk: &PublicKey, sk: &SecretKey) -> Signature {
+        MESSAGES
+            .iter()
+            .fold(SignBuilder::new(pk).secret_key(sk), |b, m| b.message(m))
+            .sign()
+            .unwrap()
+    }
+
+    #[test]
+    fn sign_verify() {
+        let _rng = lock_rng();
+        let (pk, sk) = keys();
+        let signature = sign(&pk, &sk);
+        let verify = |messages: &[&[u8]]| {
+            messages
+                .iter()
+                .fold(VerifyBuilder::new(&pk, &signature), |b, m| b.message(m))
+                .verify()
+        };
+        assert!(verify(&MESSAGES).unwrap());
+        assert!(!verify(&[b"name", b"age", b"town"]).unwrap());
+        assert!(verify(&MESSAGES[..2]).is_err());
+        assert!(SignBuilder::new(&pk).message(b"name").sign().is_err());
+    }
+
+    #[test]
+    fn blind_sign_unblind() {
+        let _rng = lock_rng();
+        let (pk, sk) = keys();
+        let nonce = ProofNonce::hash(b"issuer nonce");
+        let blind = BlindCommitmentBuilder::new(&pk, &nonce)
+            .message(0, MESSAGES[0])
+            .commit()
+            .unwrap();
+        assert!(
+            VerifyBlindCommitmentBuilder::new(&pk, &blind.context, &nonce)
+                .blinded(0)
+                .verify()
+                .unwrap()
+        );
+        assert!(
+            !VerifyBlindCommitmentBuilder::new(&pk, &blind.context, &ProofNonce::hash(b"other"))
+                .blinded(0)
+                .verify()
+                .unwrap()
+        );
+
+        let blind_signature = BlindSignBuilder::new(&pk, &blind.commitment)
+            .secret_key(&sk)
+            .message(1, MESSAGES[1])
+            .message(2, MESSAGES[2])
+            .sign()
+            .unwrap();
+        let signature = unblind_signature(&blind_signature, &blind.blinding_factor);
+        assert!(MESSAGES
+            .iter()
+            .fold(VerifyBuilder::new(&pk, &signature), |b, m| b.message(m))
+            .verify()
+            .unwrap());
+    }
+
+    #[test]
+    fn create_verify_proof() {
+        let _rng = lock_rng();
+        let (pk, sk) = keys();
+        let signature = sign(&pk, &sk);
+        let nonce = ProofNonce::hash(b"verifier nonce");
+        let proof = CreateProofBuilder::new(&pk, &signature, &nonce)
+            .revealed(MESSAGES[0])
+            .hidden(MESSAGES[1])
+            .revealed(MESSAGES[2])
+            .create()
+            .unwrap();
+        assert_eq!(proof.revealed(), [0, 2].iter().copied().collect());
+
+        let proof = Proof::parse(&proof.to_bytes()).unwrap();
+        let status = VerifyProofBuilder::new(&pk, &proof, &nonce)
+            .message(MESSAGES[0])
+            .message(MESSAGES[2])
+            .verify()
+            .unwrap();
+        assert!(status.is_valid());
+        let status = VerifyProofBuilder::new(&pk, &proof, &nonce)
+            .revealed_message(0, MESSAGES[0])
+            .revealed_message(2, b"town")
+            .verify()
+            .unwrap();
+        assert!(!status.is_valid());
+        let status = VerifyProofBuilder::new(&pk, &proof, &ProofNonce::hash(b"replayed"))
+            .message(MESSAGES[0])
+            .message(MESSAGES[2])
+            .verify()
+            .unwrap();
+        assert!(!status.is_valid());
+    }
+
+    fn other_public_key() -> PublicKey {
+        let (dpk, _) = generate_key(Some(b"another api test seed")).unwrap();
+        dpk.to_public_key(MESSAGES.len()).unwrap()
+    }
+
+    fn proof(pk: &PublicKey, sk: &SecretKey, nonce: &ProofNonce) -> Proof {
+        CreateProofBuilder::new(pk, &sign(pk, sk), nonce)
+            .revealed(MESSAGES[0])
+            .hidden(MESSAGES[1])
+            .revealed(MESSAGES[2])
+            .create()
+            .unwrap()
+    }
+
+    #[test]
+    fn wrong_public_key() {
+        let _rng = lock_rng();
+        let (pk, sk) = keys();
+        let other = other_public_key();
+        let signature = sign(&pk, &sk);
+        assert!(!MESSAGES
+            .iter()
+            .fold(VerifyBuilder::new(&other, &signature), |b, m| b.message(m))
+            .verify()
+            .unwrap());
+
+        let nonce = ProofNonce::hash(b"verifier nonce");
+        let proof = proof(&pk, &sk, &nonce);
+        let status = VerifyProofBuilder::new(&other, &proof, &nonce)
+            .message(MESSAGES[0])
+            .message(MESSAGES[2])
+            .verify()
+            .unwrap();
+        assert!(!status.is_valid());
+    }
+
+    #[test]
+    fn tampered_proof() {
+        let _rng = lock_rng();
+        let (pk, sk) = keys();
+        let nonce = ProofNonce::hash(b"verifier nonce");
+        let mut bytes = proof(&pk, &sk, &nonce).to_bytes();
+        // The low byte of the last response keeps it a valid field element
+        *bytes.last_mut().unwrap() ^= 1;
+        let proof = Proof::parse(&bytes).unwrap();
+        let status = VerifyProofBuilder::new(&pk, &proof, &nonce)
+            .message(MESSAGES[0])
+            .message(MESSAGES[2])
+            .verify()
+            .unwrap();
+        assert!(!status.is_valid());
+    }
+
+    #[test]
+    fn revealed_message_mismatch() {
+        let _rng = lock_rng();
+        let (pk, sk) = keys();
+        let nonce = ProofNonce::hash(b"verifier nonce");
+        let proof = proof(&pk, &sk, &nonce);
+        let verify = |messages: &[&[u8]]| {
+            messages
+                .iter()
+                .fold(VerifyProofBuilder::new(&pk, &proof, &nonce), |b, m| {
+                    b.message(m)
+                })
+                .verify()
+        };
+        assert!(verify(&MESSAGES[..1]).is_err());
+        assert!(verify(&MESSAGES).is_err());
+        assert!(VerifyProofBuilder::new(&pk, &proof, &nonce)
+            .revealed_message(0, MESSAGES[0])
+            .revealed_message(1, MESSAGES[1])
+            .verify()
+            .is_err());
+
+        let (dpk, _) = generate_key(Some(b"api test seed")).unwrap();
+        let wider = dpk.to_public_key(MESSAGES.len() + 1).unwrap();
+        assert!(VerifyProofBuilder::new(&wider, &proof, &nonce)
+            .message(MESSAGES[0])
+            .message(MESSAGES[2])
+            .verify()
+            .is_err());
+    }
+
+    #[test]
+    fn parse_truncated_proof() {
+        let _rng = lock_rng();
+        let (pk, sk) = keys();
+        let bytes = proof(&pk, &sk, &ProofNonce::hash(b"verifier nonce")).to_bytes();
+        for len in &[0, 1, 2, 3, bytes.len() / 2, bytes.len() - 1] {
+            assert!(Proof::parse(&bytes[..*len]).is_err(), "length {}", len);
+        }
+    }
+
+    #[test]
+    fn blind_commitment_does_not_verify() {
+        let _rng = lock_rng();
+        let (pk, sk) = keys();
+        let nonce = ProofNonce::hash(b"issuer nonce");
+        let blind = BlindCommitmentBuilder::new(&pk, &nonce)
+            .message(0, MESSAGES[0])
+            .commit()
+            .unwrap();
+        assert!(!VerifyBlindCommitmentBuilder::new(&pk, &blind.context, &nonce)
+            .blinded(1)
+            .verify()
+            .unwrap());
+        assert!(
+            !VerifyBlindCommitmentBuilder::new(&other_public_key(), &blind.context, &nonce)
+                .blinded(0)
+                .verify()
+                .unwrap()
+        );
+
+        let other = BlindCommitmentBuilder::new(&pk, &nonce)
+            .message(0, MESSAGES[1])
+            .commit()
+            .unwrap();
+        let mut context = blind.context.clone();
+        context.commitment = other.commitment;
+        assert!(!VerifyBlindCommitmentBuilder::new(&pk, &context, &nonce)
+            .blinded(0)
+            .verify()
+            .unwrap());
+
+        // Signing the swapped commitment gives a signature the holder cannot unblind
+        let blind_signature = BlindSignBuilder::new(&pk, &context.commitment)
+            .secret_key(&sk)
+            .message(1, MESSAGES[1])
+            .message(2, MESSAGES[2])
+            .sign()
+            .unwrap();
+        let signature = unblind_signature(&blind_signature, &blind.blinding_factor);
+        assert!(!MESSAGES
+            .iter()
+            .fold(VerifyBuilder::new(&pk, &signature), |b, m| b.message(m))
+            .verify()
+            .unwrap());
+    }
+}
@@ -1,30 +1,41 @@
-use crate::{BbsFfiError, ByteArray};
+use crate::api::BlindCommitmentBuilder;
+use crate::last_error::ErrorSlot;
+use crate::{zeroize_with, BbsFfiError, ByteArray};
 use bbs::prelude::*;
 use ffi_support::{ByteBuffer, ConcurrentHandleMap, ErrorCode, ExternError, FfiStr};
-use std::{collections::BTreeMap, convert::TryFrom};
+use std::collections::BTreeMap;
 
 lazy_static! {
-    static ref BLIND_COMMITMENT_CONTEXT: ConcurrentHandleMap<BlindCommitmentContext> =
+    pub(crate) static ref BLIND_COMMITMENT_CONTEXT: ConcurrentHandleMap<BlindCommitmentContext> =
         ConcurrentHandleMap::new();
     // static ref VERIFY_SIGN_PROOF_CONTEXT: ConcurrentHandleMap<VerifyBlindSignProofContext> =
     //     ConcurrentHandleMap::new();
 }
 
-define_handle_map_deleter!(BLIND_COMMITMENT_CONTEXT, free_bbs_blind_commitment);
+define_handle_deleter!(BLIND_COMMITMENT_CONTEXT, free_bbs_blind_commitment);
 
-struct BlindCommitmentContext {
+pub(crate) struct BlindCommitmentContext {
     messages: BTreeMap<usize, SignatureMessage>,
     public_key: Option<PublicKey>,
     nonce: Option<ProofNonce>,
 }
 
+impl Drop for BlindCommitmentContext {
+    fn drop(&mut self) {
+        for m in self.messages.values_mut() {
+            zeroize_with(m, SignatureMessage::default());
+        }
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn bbs_blind_signature_size() -> i32 {
     SIGNATURE_COMPRESSED_SIZE as i32
 }
 
 #[no_mangle]
-pub extern "C" fn bbs_blind_commitment_context_init(err: &mut ExternError) -> u64 {
+pub extern "C" fn bbs_blind_commitment_context_init(err: *mut ExternError) -> u64 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
     BLIND_COMMITMENT_CONTEXT.insert_with_output(err, || BlindCommitmentContext {
         messages: BTreeMap::new(),
         public_key: None,
@@ -62,8 +73,9 @@ pub extern "C" fn bbs_blind_commitment_context_finish(
     commitment: &mut ByteBuffer,
     out_context: &mut ByteBuffer,
     blinding_factor: &mut ByteBuffer,
-    err: &mut ExternError,
+    err: *mut ExternError,
 ) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
     let res = BLIND_COMMITMENT_CONTEXT.call_with_result(
         err,
         handle,
@@ -78,16 +90,16 @@ pub extern "C" fn bbs_blind_commitment_context_finish(
                 return Err(BbsFfiError::new("Messages cannot be empty"))
             }
 
-            match (ctx.nonce.as_ref(), ctx.public_key.as_ref()) {
-                (Some(n), Some(pk)) => {
-                    let (c, b) = Prover::new_blind_signature_context(pk, &ctx.messages, n)?;
-                    let mut output = Vec::new();
-                    output.append(&mut b.to_bytes_compressed_form().to_vec());
-                    output.append(&mut c.to_bytes_compressed_form());
-                    Ok(ByteBuffer::from_vec(output))
-                }
-                (_, _) => Ok(ByteBuffer::new_with_size(0)),
-            }
+            let res = BlindCommitmentBuilder::new(
+                ctx.public_key.as_ref().unwrap(),
+                ctx.nonce.as_ref().unwrap(),
+            )
+            .prehashed_messages(ctx.messages.iter().map(|(i, m)| (*i, *m)))
+            .commit()?;
+            let mut output = Vec::new();
+            output.append(&mut res.blinding_factor.to_bytes_compressed_form().to_vec());
+            output.append(&mut res.context.to_bytes_compressed_form());
+            Ok(ByteBuffer::from_vec(output))
         },
     );
 
@@ -1,22 +1,35 @@
-use crate::{BbsFfiError, ByteArray};
+use crate::api::{self, BlindSignBuilder, Parse};
+use crate::last_error::ErrorSlot;
+use crate::signer::BbsSigner;
+use crate::{zeroize_with, BbsFfiError, ByteArray};
 use bbs::prelude::*;
 use ffi_support::{
     call_with_result, ByteBuffer, ConcurrentHandleMap, ErrorCode, ExternError, FfiStr,
 };
-use std::{collections::BTreeMap, convert::TryFrom};
+use std::collections::BTreeMap;
 
 lazy_static! {
-    static ref BLIND_SIGN_CONTEXT: ConcurrentHandleMap<BlindSignContext> =
+    pub(crate) static ref BLIND_SIGN_CONTEXT: ConcurrentHandleMap<BlindSignContext> =
         ConcurrentHandleMap::new();
 }
 
-define_handle_map_deleter!(BLIND_SIGN_CONTEXT, free_bbs_blind_sign);
+define_handle_deleter!(BLIND_SIGN_CONTEXT, free_bbs_blind_sign);
 
-struct BlindSignContext {
+pub(crate) struct BlindSignContext {
     commitment: Option<Commitment>,
     messages: BTreeMap<usize, SignatureMessage>,
     public_key: Option<PublicKey>,
     secret_key: Option<SecretKey>,
+    signer: Option<BbsSigner>,
+}
+
+impl Drop for BlindSignContext {
+    fn drop(&mut self) {
+        // `SecretKey` zeroizes itself
+        for m in self.messages.values_mut() {
+            zeroize_with(m, SignatureMessage::default());
+        }
+    }
 }
 
 #[no_mangle]
@@ -25,12 +38,14 @@ pub extern "C" fn bbs_blinding_factor_size() -> i32 {
 }
 
 #[no_mangle]
-pub extern "C" fn bbs_blind_sign_context_init(err: &mut ExternError) -> u64 {
+pub extern "C" fn bbs_blind_sign_context_init(err: *mut ExternError) -> u64 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
     BLIND_SIGN_CONTEXT.insert_with_output(err, || BlindSignContext {
         commitment: None,
         messages: BTreeMap::new(),
         public_key: None,
         secret_key: None,
+        signer: None,
     })
 }
 
@@ -56,6 +71,22 @@ add_bytes_impl!(
     SecretKey
 );
 
+/// Use an external signer instead of a secret key
+#[no_mangle]
+pub extern "C" fn bbs_blind_sign_context_set_signer(
+    handle: u64,
+    signer: BbsSigner,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    BLIND_SIGN_CONTEXT.call_with_result_mut(err, handle, move |ctx| -> Result<(), BbsFfiError> {
+        signer.check()?;
+        ctx.signer = Some(signer);
+        Ok(())
+    });
+    err.get_code().code()
+}
+
 add_bytes_impl!(
     bbs_blind_sign_context_set_commitment,
     BLIND_SIGN_CONTEXT,
@@ -67,8 +98,9 @@ add_bytes_impl!(
 pub extern "C" fn bbs_blind_sign_context_finish(
     handle: u64,
     blinded_signature: &mut ByteBuffer,
-    err: &mut ExternError,
+    err: *mut ExternError,
 ) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
     let res = BLIND_SIGN_CONTEXT.call_with_result(
         err,
         handle,
@@ -76,8 +108,8 @@ pub extern "C" fn bbs_blind_sign_context_finish(
             if ctx.commitment.is_none() {
                 return Err(BbsFfiError::new("Commitment must be set"))
             }
-            if ctx.secret_key.is_none() {
-                return Err(BbsFfiError::new("Secret Key must be set"))
+            if ctx.secret_key.is_none() && ctx.signer.is_none() {
+                return Err(BbsFfiError::new("Secret Key or signer must be set"))
             }
             if ctx.public_key.is_none() {
                 return Err(BbsFfiError::new("Public Key must be set"))
@@ -85,10 +117,17 @@ pub extern "C" fn bbs_blind_sign_context_finish(
             if ctx.messages.is_empty() {
                 return Err(BbsFfiError::new("Messages cannot be empty"))
             }
-            let commitment = ctx.commitment.as_ref().unwrap();
-            let sk = ctx.secret_key.as_ref().unwrap();
-            let pk = ctx.public_key.as_ref().unwrap();
-            let sig = BlindSignature::new(commitment, &ctx.messages, sk, pk)?;
+            let mut builder = BlindSignBuilder::new(
+                ctx.public_key.as_ref().unwrap(),
+                ctx.commitment.as_ref().unwrap(),
+            )
+            .prehashed_messages(ctx.messages.iter().map(|(i, m)| (*i, *m)));
+            builder = match (ctx.secret_key.as_ref(), ctx.signer.as_ref()) {
+                (Some(sk), _) => builder.secret_key(sk),
+                (None, Some(s)) => builder.signer(s),
+                (None, None) => unreachable!(),
+            };
+            let sig = builder.sign()?;
             Ok(ByteBuffer::from_vec(
                 sig.to_bytes_compressed_form().to_vec(),
             ))
@@ -109,16 +148,17 @@ pub extern "C" fn bbs_unblind_signature(
     blind_signature: ByteArray,
     blinding_factor: ByteArray,
     unblind_signature: &mut ByteBuffer,
-    err: &mut ExternError,
+    err: *mut ExternError,
 ) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
     let res = call_with_result(err, || -> Result<ByteBuffer, BbsFfiError> {
-        let blinded_sig = BlindSignature::try_from(blind_signature.to_vec())?;
-        let bf = SignatureBlinding::try_from(blinding_factor.to_vec())?;
-        let sig = blinded_sig.to_unblinded(&bf);
+        let blinded_sig = BlindSignature::parse(&blind_signature.to_vec())?;
+        let bf = SignatureBlinding::parse(&blinding_factor.to_vec())?;
+        let sig = api::unblind_signature(&blinded_sig, &bf);
         Ok(ByteBuffer::from_vec(
             sig.to_bytes_compressed_form().to_vec(),
         ))
     });
     *unblind_signature = res;
-    0
+    err.get_code().code()
 }
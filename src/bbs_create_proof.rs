@@ -1,20 +1,46 @@
-use crate::{BbsFfiError, ByteArray, ProofMessageType};
+use crate::api::CreateProofBuilder;
+#[cfg(feature = "jwp")]
+use crate::jwp::IssuedJwp;
+use crate::last_error::ErrorSlot;
+use crate::{zeroize_with, BbsFfiError, ByteArray, ProofMessageType};
 use bbs::prelude::*;
 use ffi_support::*;
-use std::{collections::BTreeSet, convert::TryFrom};
+#[cfg(feature = "jwp")]
+use std::collections::BTreeSet;
 
 lazy_static! {
     pub static ref CREATE_PROOF_CONTEXT: ConcurrentHandleMap<CreateProofContext> =
         ConcurrentHandleMap::new();
 }
 
-define_handle_map_deleter!(CREATE_PROOF_CONTEXT, free_create_proof);
+define_handle_deleter!(CREATE_PROOF_CONTEXT, free_create_proof);
 
 pub struct CreateProofContext {
     pub signature: Option<Signature>,
     pub public_key: Option<PublicKey>,
     pub messages: Vec<ProofMessage>,
     pub nonce: Option<ProofNonce>,
+    #[cfg(feature = "jwp")]
+    pub jwp: Option<IssuedJwp>,
+    #[cfg(feature = "jwp")]
+    pub jwp_disclosed: BTreeSet<usize>,
+}
+
+impl Drop for CreateProofContext {
+    fn drop(&mut self) {
+        for m in self.messages.iter_mut() {
+            match m {
+                ProofMessage::Revealed(m) => zeroize_with(m, SignatureMessage::default()),
+                ProofMessage::Hidden(HiddenMessage::ProofSpecificBlinding(m)) => {
+                    zeroize_with(m, SignatureMessage::default())
+                }
+                ProofMessage::Hidden(HiddenMessage::ExternalBlinding(m, b)) => {
+                    zeroize_with(m, SignatureMessage::default());
+                    zeroize_with(b, ProofNonce::default());
+                }
+            }
+        }
+    }
 }
 
 struct USize(usize);
@@ -44,12 +70,17 @@ pub extern "C" fn bbs_create_proof_context_size(handle: u64) -> i32 {
 }
 
 #[no_mangle]
-pub extern "C" fn bbs_create_proof_context_init(err: &mut ExternError) -> u64 {
+pub extern "C" fn bbs_create_proof_context_init(err: *mut ExternError) -> u64 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
     CREATE_PROOF_CONTEXT.insert_with_output(err, || CreateProofContext {
         signature: None,
         messages: Vec::new(),
         public_key: None,
         nonce: None,
+        #[cfg(feature = "jwp")]
+        jwp: None,
+        #[cfg(feature = "jwp")]
+        jwp_disclosed: BTreeSet::new(),
     })
 }
 
@@ -87,8 +118,9 @@ add_bytes_impl!(
 pub extern "C" fn bbs_create_proof_context_finish(
     handle: u64,
     proof: &mut ByteBuffer,
-    err: &mut ExternError,
+    err: *mut ExternError,
 ) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
     let res = CREATE_PROOF_CONTEXT.call_with_result(
         err,
         handle,
@@ -105,29 +137,15 @@ pub extern "C" fn bbs_create_proof_context_finish(
             if ctx.messages.is_empty() {
                 return Err(BbsFfiError::new("Messages cannot be empty"))
             }
-            let signature = ctx.signature.as_ref().unwrap();
-            let public_key = &ctx.public_key.as_ref().unwrap();
-            let nonce = &ctx.nonce.as_ref().unwrap();
-
-            let pok = PoKOfSignature::init(signature, public_key, ctx.messages.as_slice())?;
-            let mut challenge_bytes = pok.to_bytes();
-            challenge_bytes.extend_from_slice(&nonce.to_bytes_compressed_form()[..]);
-            let challenge_hash = ProofChallenge::hash(&challenge_bytes);
-
-            let revealed = ctx
-                .messages
-                .iter()
-                .enumerate()
-                .filter(|(_, m)| matches!(m, ProofMessage::Revealed(_)))
-                .map(|(i, _)| i)
-                .collect();
-
-            let mut bitvector = (ctx.messages.len() as u16).to_be_bytes().to_vec();
-            bitvector.append(&mut revealed_to_bitvector(ctx.messages.len(), &revealed));
-            let proof = pok.gen_proof(&challenge_hash)?;
-            bitvector.append(&mut proof.to_bytes_compressed_form());
-
-            Ok(ByteBuffer::from_vec(bitvector))
+            let proof = CreateProofBuilder::new(
+                ctx.public_key.as_ref().unwrap(),
+                ctx.signature.as_ref().unwrap(),
+                ctx.nonce.as_ref().unwrap(),
+            )
+            .proof_messages(ctx.messages.iter().map(copy_proof_message))
+            .create()?;
+
+            Ok(ByteBuffer::from_vec(proof.to_bytes()))
         },
     );
 
@@ -140,17 +158,92 @@ pub extern "C" fn bbs_create_proof_context_finish(
     err.get_code().code()
 }
 
-/// Expects `revealed` to be sorted
-fn revealed_to_bitvector(total: usize, revealed: &BTreeSet<usize>) -> Vec<u8> {
-    let mut bytes = vec![0u8; (total / 8) + 1];
+#[cfg(feature = "jwp")]
+/// Prove knowledge of the signature in the issued JWP `value` instead of adding messages
+#[no_mangle]
+pub extern "C" fn bbs_create_proof_context_set_jwp(
+    handle: u64,
+    value: ByteArray,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let value = value.to_vec();
+    CREATE_PROOF_CONTEXT.call_with_result_mut(err, handle, move |ctx| -> Result<(), BbsFfiError> {
+        ctx.jwp = Some(IssuedJwp::parse(&value)?);
+        Ok(())
+    });
+    err.get_code().code()
+}
+
+#[cfg(feature = "jwp")]
+/// Disclose the JWP payload at `index`, counting from 0. Other payloads are hidden
+#[no_mangle]
+pub extern "C" fn bbs_create_proof_context_disclose_jwp_payload(
+    handle: u64,
+    index: u32,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    CREATE_PROOF_CONTEXT.call_with_output_mut(err, handle, |ctx| {
+        ctx.jwp_disclosed.insert(index as usize);
+    });
+    err.get_code().code()
+}
 
-    for r in revealed {
-        let idx = *r / 8;
-        let bit = (*r % 8) as u8;
-        bytes[idx] |= 1u8 << bit;
+#[cfg(feature = "jwp")]
+/// Write the presented JWP for the issued JWP and `presentation_header` in compact form to `jwp`.
+/// The nonce is the hash of the presentation header, which must be a JSON object
+#[no_mangle]
+pub extern "C" fn bbs_create_proof_context_finish_jwp(
+    handle: u64,
+    presentation_header: ByteArray,
+    jwp: &mut ByteBuffer,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let presentation_header = presentation_header.to_vec();
+    let res = CREATE_PROOF_CONTEXT.call_with_result(
+        err,
+        handle,
+        move |ctx| -> Result<ByteBuffer, BbsFfiError> {
+            if ctx.jwp.is_none() {
+                return Err(BbsFfiError::new("Issued JWP must be set"));
+            }
+            if ctx.public_key.is_none() {
+                return Err(BbsFfiError::new("Public key must be set"));
+            }
+            if ctx.signature.is_some() || ctx.nonce.is_some() || !ctx.messages.is_empty() {
+                return Err(BbsFfiError::new(
+                    "The signature, messages and nonce come from the JWP and cannot be set",
+                ));
+            }
+            let presented = ctx.jwp.as_ref().unwrap().present(
+                ctx.public_key.as_ref().unwrap(),
+                &presentation_header,
+                &ctx.jwp_disclosed,
+            )?;
+            Ok(ByteBuffer::from_vec(presented.to_compact().into_bytes()))
+        },
+    );
+
+    if err.get_code().is_success() {
+        *jwp = res;
+        if let Err(e) = CREATE_PROOF_CONTEXT.remove_u64(handle) {
+            *err = ExternError::new_error(ErrorCode::new(1), format!("{:?}", e))
+        }
     }
+    err.get_code().code()
+}
 
-    // Convert to big endian
-    bytes.reverse();
-    bytes
+/// `ProofMessage` is not `Clone`, its contents are `Copy`
+fn copy_proof_message(m: &ProofMessage) -> ProofMessage {
+    match m {
+        ProofMessage::Revealed(m) => ProofMessage::Revealed(*m),
+        ProofMessage::Hidden(HiddenMessage::ProofSpecificBlinding(m)) => {
+            ProofMessage::Hidden(HiddenMessage::ProofSpecificBlinding(*m))
+        }
+        ProofMessage::Hidden(HiddenMessage::ExternalBlinding(m, b)) => {
+            ProofMessage::Hidden(HiddenMessage::ExternalBlinding(*m, *b))
+        }
+    }
 }
@@ -1,19 +1,49 @@
-use crate::{BbsFfiError, ByteArray};
+use crate::api::{SignBuilder, VerifyBuilder};
+#[cfg(feature = "cose")]
+use crate::cose::decode_signature;
+#[cfg(feature = "jwp")]
+use crate::jwp::{self, IssuedJwp};
+use crate::last_error::ErrorSlot;
+use crate::rng::catch_rng_failure;
+use crate::signer::BbsSigner;
+use crate::threshold::{
+    combine, partial_sign, random_shares, NonceShare, PartialSignature, Session, Share,
+    ThresholdState, NONCE_SHARE_SIZE, PARTIAL_SIGNATURE_SIZE, SESSION_SIZE,
+};
+use crate::{zeroize_with, BbsFfiError, ByteArray};
 use bbs::prelude::*;
 use ffi_support::{ByteBuffer, ConcurrentHandleMap, ErrorCode, ExternError, FfiStr};
-use std::convert::TryFrom;
+#[cfg(feature = "jwp")]
+use zeroize::Zeroize;
 
 lazy_static! {
     pub static ref SIGN_CONTEXT: ConcurrentHandleMap<SignContext> = ConcurrentHandleMap::new();
 }
 
-define_handle_map_deleter!(SIGN_CONTEXT, free_bbs_sign);
+define_handle_deleter!(SIGN_CONTEXT, free_bbs_sign);
 
 pub struct SignContext {
     pub messages: Vec<SignatureMessage>,
     pub secret_key: Option<SecretKey>,
     pub public_key: Option<PublicKey>,
     pub signature: Option<Signature>,
+    pub signer: Option<BbsSigner>,
+    #[cfg(feature = "jwp")]
+    pub jwp_payloads: Vec<Vec<u8>>,
+    pub(crate) threshold: ThresholdState,
+}
+
+impl Drop for SignContext {
+    fn drop(&mut self) {
+        // `SecretKey` and threshold shares zeroize themselves
+        for m in self.messages.iter_mut() {
+            zeroize_with(m, SignatureMessage::default());
+        }
+        #[cfg(feature = "jwp")]
+        for p in self.jwp_payloads.iter_mut() {
+            p.zeroize();
+        }
+    }
 }
 
 #[no_mangle]
@@ -22,12 +52,17 @@ pub extern "C" fn bbs_signature_size() -> i32 {
 }
 
 #[no_mangle]
-pub extern "C" fn bbs_sign_context_init(err: &mut ExternError) -> u64 {
+pub extern "C" fn bbs_sign_context_init(err: *mut ExternError) -> u64 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
     SIGN_CONTEXT.insert_with_output(err, || SignContext {
         messages: Vec::new(),
         secret_key: None,
         signature: None,
         public_key: None,
+        signer: None,
+        #[cfg(feature = "jwp")]
+        jwp_payloads: Vec::new(),
+        threshold: ThresholdState::default(),
     })
 }
 
@@ -51,44 +86,344 @@ add_bytes_impl!(
     PublicKey
 );
 
+/// Use an external signer instead of a secret key
+#[no_mangle]
+pub extern "C" fn bbs_sign_context_set_signer(
+    handle: u64,
+    signer: BbsSigner,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    SIGN_CONTEXT.call_with_result_mut(err, handle, move |ctx| -> Result<(), BbsFfiError> {
+        signer.check()?;
+        ctx.signer = Some(signer);
+        Ok(())
+    });
+    err.get_code().code()
+}
+
 #[no_mangle]
 pub extern "C" fn bbs_sign_context_finish(
     handle: u64,
     signature: &mut ByteBuffer,
-    err: &mut ExternError,
+    err: *mut ExternError,
 ) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
     let sig =
         SIGN_CONTEXT.call_with_result(err, handle, move |ctx| -> Result<ByteBuffer, BbsFfiError> {
-            if ctx.secret_key.is_none() {
-                return Err(BbsFfiError::new("Secret Key must be set"))
+            check_signing_keys(ctx)?;
+            if ctx.messages.is_empty() {
+                return Err(BbsFfiError::new("Messages cannot be empty"))
             }
-            if ctx.public_key.is_none() {
-                return Err(BbsFfiError::new("Public Key must be set"))
+
+            let s = sign(ctx, ctx.messages.iter().copied())?;
+            Ok(ByteBuffer::from_vec(s.to_bytes_compressed_form().to_vec()))
+        });
+
+    if err.get_code().is_success() {
+        *signature = sig;
+        if let Err(e) = SIGN_CONTEXT.remove_u64(handle) { 
+            *err = ExternError::new_error(ErrorCode::new(1), format!("{:?}", e)) 
+        }
+    }
+    err.get_code().code()
+}
+
+fn check_signing_keys(ctx: &SignContext) -> Result<(), BbsFfiError> {
+    if ctx.secret_key.is_none() && ctx.signer.is_none() {
+        return Err(BbsFfiError::new("Secret Key or signer must be set"));
+    }
+    if ctx.public_key.is_none() {
+        return Err(BbsFfiError::new("Public Key must be set"));
+    }
+    Ok(())
+}
+
+/// Sign `messages` with the secret key or signer, after `check_signing_keys`
+fn sign<I: IntoIterator<Item = SignatureMessage>>(
+    ctx: &SignContext,
+    messages: I,
+) -> Result<Signature, BbsFfiError> {
+    let mut builder =
+        SignBuilder::new(ctx.public_key.as_ref().unwrap()).prehashed_messages(messages);
+    builder = match (ctx.secret_key.as_ref(), ctx.signer.as_ref()) {
+        (Some(sk), _) => builder.secret_key(sk),
+        (None, Some(s)) => builder.signer(s),
+        (None, None) => unreachable!(),
+    };
+    Ok(builder.sign()?)
+}
+
+#[cfg(feature = "jwp")]
+/// Add the next JWP payload, signed as the message after the issuer header
+#[no_mangle]
+pub extern "C" fn bbs_sign_context_add_jwp_payload(
+    handle: u64,
+    payload: ByteArray,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let payload = payload.to_vec();
+    if payload.is_empty() {
+        *err = ExternError::new_error(ErrorCode::new(1), "JWP payload cannot be empty");
+        return 1;
+    }
+    SIGN_CONTEXT.call_with_output_mut(err, handle, |ctx| {
+        ctx.jwp_payloads.push(payload);
+    });
+    err.get_code().code()
+}
+
+#[cfg(feature = "jwp")]
+/// Sign the issuer `header` and the JWP payloads and write the issued JWP in compact form to `jwp`.
+/// The header must be a JSON object with `alg` set to the ciphersuite id
+#[no_mangle]
+pub extern "C" fn bbs_sign_context_finish_jwp(
+    handle: u64,
+    header: ByteArray,
+    jwp: &mut ByteBuffer,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let header = header.to_vec();
+    let issued =
+        SIGN_CONTEXT.call_with_result(err, handle, move |ctx| -> Result<ByteBuffer, BbsFfiError> {
+            check_signing_keys(ctx)?;
+            if !ctx.messages.is_empty() {
+                return Err(BbsFfiError::new(
+                    "JWP payloads must be added with bbs_sign_context_add_jwp_payload",
+                ));
             }
+            let signature = sign(ctx, jwp::messages(&header, &ctx.jwp_payloads)?)?;
+            let issued = IssuedJwp {
+                header,
+                payloads: ctx.jwp_payloads.clone(),
+                signature,
+            };
+            Ok(ByteBuffer::from_vec(issued.to_compact().into_bytes()))
+        });
+
+    if err.get_code().is_success() {
+        *jwp = issued;
+        if let Err(e) = SIGN_CONTEXT.remove_u64(handle) {
+            *err = ExternError::new_error(ErrorCode::new(1), format!("{:?}", e))
+        }
+    }
+    err.get_code().code()
+}
+
+#[no_mangle]
+pub extern "C" fn bbs_threshold_session_size() -> i32 {
+    SESSION_SIZE as i32
+}
+
+#[no_mangle]
+pub extern "C" fn bbs_partial_signature_size() -> i32 {
+    PARTIAL_SIGNATURE_SIZE as i32
+}
+
+/// Size of one nonce share record: index (u32 big endian) || nonce share || zero mask share
+#[no_mangle]
+pub extern "C" fn bbs_threshold_nonce_share_size() -> i32 {
+    NONCE_SHARE_SIZE as i32
+}
+
+/// Generate the public values `e` and `s` for one threshold signature.
+/// Every party taking part in the signature must use the same session
+#[no_mangle]
+pub extern "C" fn bbs_threshold_session_init(session: &mut ByteBuffer, err: *mut ExternError) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    match catch_rng_failure(Session::random) {
+        Ok(s) => {
+            *session = ByteBuffer::from_vec(s.to_bytes());
+            *err = ExternError::success();
+        }
+        Err(e) => *err = e.into(),
+    }
+    err.get_code().code()
+}
+
+/// Generate `total` shares of a random nonce contribution and of a random mask with threshold
+/// `threshold`. Every party taking part in a signature generates its own contribution and sends
+/// the share record for party `j` to party `j`, which adds it with `bbs_sign_context_add_nonce_share`.
+/// Use fresh shares for every signature, reusing them leaks the secret key
+#[no_mangle]
+pub extern "C" fn bbs_threshold_generate_nonce_shares(
+    threshold: u32,
+    total: u32,
+    shares: &mut ByteBuffer,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    match catch_rng_failure(|| random_shares(threshold, total)).and_then(|r| r) {
+        Ok(s) => {
+            *shares = ByteBuffer::from_vec(s.iter().flat_map(|s| s.to_bytes()).collect());
+            *err = ExternError::success();
+        }
+        Err(e) => *err = e.into(),
+    }
+    err.get_code().code()
+}
+
+/// Set this party's secret key share as output by `bls_generate_g2_key_shares`
+#[no_mangle]
+pub extern "C" fn bbs_sign_context_set_secret_key_share(
+    handle: u64,
+    share: ByteArray,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let share = share.to_vec();
+    SIGN_CONTEXT.call_with_result_mut(err, handle, move |ctx| -> Result<(), BbsFfiError> {
+        ctx.threshold.key_share = Some(Share::from_bytes(&share)?);
+        Ok(())
+    });
+    err.get_code().code()
+}
+
+/// Add the nonce share this party received from one of the signing parties
+#[no_mangle]
+pub extern "C" fn bbs_sign_context_add_nonce_share(
+    handle: u64,
+    share: ByteArray,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let share = share.to_vec();
+    SIGN_CONTEXT.call_with_result_mut(err, handle, move |ctx| -> Result<(), BbsFfiError> {
+        ctx.threshold.nonce_shares.push(NonceShare::from_bytes(&share)?);
+        Ok(())
+    });
+    err.get_code().code()
+}
+
+#[no_mangle]
+pub extern "C" fn bbs_sign_context_set_session(
+    handle: u64,
+    session: ByteArray,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let session = session.to_vec();
+    SIGN_CONTEXT.call_with_result_mut(err, handle, move |ctx| -> Result<(), BbsFfiError> {
+        ctx.threshold.session = Some(Session::from_bytes(&session)?);
+        Ok(())
+    });
+    err.get_code().code()
+}
+
+/// Create this party's partial signature over the messages from its secret key share,
+/// nonce shares and the session. The context is deleted even if this fails
+#[no_mangle]
+pub extern "C" fn bbs_sign_context_partial_finish(
+    handle: u64,
+    partial_signature: &mut ByteBuffer,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let sig =
+        SIGN_CONTEXT.call_with_result(err, handle, move |ctx| -> Result<ByteBuffer, BbsFfiError> {
+            let pk = match ctx.public_key.as_ref() {
+                Some(pk) => pk,
+                None => return Err(BbsFfiError::new("Public Key must be set")),
+            };
             if ctx.messages.is_empty() {
                 return Err(BbsFfiError::new("Messages cannot be empty"))
             }
 
-            match (ctx.secret_key.as_ref(), ctx.public_key.as_ref()) {
-                (Some(sk), Some(pk)) => {
-                    let s = Signature::new(ctx.messages.as_slice(), sk, pk)?;
-                    Ok(ByteBuffer::from_vec(s.to_bytes_compressed_form().to_vec()))
+            match (ctx.threshold.key_share.as_ref(), ctx.threshold.session.as_ref()) {
+                (Some(share), Some(session)) => {
+                    let p = partial_sign(
+                        ctx.messages.as_slice(),
+                        pk,
+                        share,
+                        ctx.threshold.nonce_shares.as_slice(),
+                        session,
+                    )?;
+                    Ok(ByteBuffer::from_vec(p.to_bytes()))
                 }
-                (_, _) => Ok(ByteBuffer::new_with_size(0)),
+                (None, _) => Err(BbsFfiError::new("Secret Key share must be set")),
+                (_, None) => Err(BbsFfiError::new("Session must be set")),
+            }
+        });
+
+    // The nonce shares must not be used again, so the context is deleted even on failure
+    let removed = SIGN_CONTEXT.remove_u64(handle);
+    if err.get_code().is_success() {
+        *partial_signature = sig;
+        if let Err(e) = removed {
+            *err = ExternError::new_error(ErrorCode::new(1), format!("{:?}", e))
+        }
+    }
+    err.get_code().code()
+}
+
+#[no_mangle]
+pub extern "C" fn bbs_sign_context_add_partial_signature(
+    handle: u64,
+    partial_signature: ByteArray,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let partial_signature = partial_signature.to_vec();
+    SIGN_CONTEXT.call_with_result_mut(err, handle, move |ctx| -> Result<(), BbsFfiError> {
+        ctx.threshold
+            .partial_signatures
+            .push(PartialSignature::from_bytes(&partial_signature)?);
+        Ok(())
+    });
+    err.get_code().code()
+}
+
+/// Combine the partial signatures into a signature over the messages.
+/// With threshold `t` at least `2t - 1` partial signatures are required, all from
+/// parties that added the nonce shares of the same set of parties.
+/// The signature is checked against the public key before it is returned.
+/// The context is deleted even if this fails
+#[no_mangle]
+pub extern "C" fn bbs_sign_context_combine_finish(
+    handle: u64,
+    signature: &mut ByteBuffer,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let sig =
+        SIGN_CONTEXT.call_with_result(err, handle, move |ctx| -> Result<ByteBuffer, BbsFfiError> {
+            let pk = match ctx.public_key.as_ref() {
+                Some(pk) => pk,
+                None => return Err(BbsFfiError::new("Public Key must be set")),
+            };
+            if ctx.messages.is_empty() {
+                return Err(BbsFfiError::new("Messages cannot be empty"))
+            }
+            let session = match ctx.threshold.session.as_ref() {
+                Some(session) => session,
+                None => return Err(BbsFfiError::new("Session must be set")),
+            };
+
+            let s = combine(ctx.threshold.partial_signatures.as_slice(), session)?;
+            if !s.verify(ctx.messages.as_slice(), pk)? {
+                return Err(BbsFfiError::new(
+                    "Partial signatures do not combine to a valid signature",
+                ));
             }
+            Ok(ByteBuffer::from_vec(s.to_bytes_compressed_form().to_vec()))
         });
 
+    // The context is used up whether or not the partial signatures combined
+    let removed = SIGN_CONTEXT.remove_u64(handle);
     if err.get_code().is_success() {
         *signature = sig;
-        if let Err(e) = SIGN_CONTEXT.remove_u64(handle) { 
-            *err = ExternError::new_error(ErrorCode::new(1), format!("{:?}", e)) 
+        if let Err(e) = removed {
+            *err = ExternError::new_error(ErrorCode::new(1), format!("{:?}", e))
         }
     }
     err.get_code().code()
 }
 
 #[no_mangle]
-pub extern "C" fn bbs_verify_context_init(err: &mut ExternError) -> u64 {
+pub extern "C" fn bbs_verify_context_init(err: *mut ExternError) -> u64 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
     bbs_sign_context_init(err)
 }
 
@@ -96,8 +431,9 @@ pub extern "C" fn bbs_verify_context_init(err: &mut ExternError) -> u64 {
 pub extern "C" fn bbs_verify_context_add_message_string(
     handle: u64,
     message: FfiStr<'_>,
-    err: &mut ExternError,
+    err: *mut ExternError,
 ) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
     bbs_sign_context_add_message_string(handle, message, err)
 }
 
@@ -105,8 +441,9 @@ pub extern "C" fn bbs_verify_context_add_message_string(
 pub extern "C" fn bbs_verify_context_add_message_bytes(
     handle: u64,
     message: ByteArray,
-    err: &mut ExternError,
+    err: *mut ExternError,
 ) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
     bbs_sign_context_add_message_bytes(handle, message, err)
 }
 
@@ -114,8 +451,9 @@ pub extern "C" fn bbs_verify_context_add_message_bytes(
 pub extern "C" fn bbs_verify_context_add_message_prehashed(
     handle: u64,
     message: ByteArray,
-    err: &mut ExternError,
+    err: *mut ExternError,
 ) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
     bbs_sign_context_add_message_prehashed(handle, message, err)
 }
 
@@ -123,8 +461,9 @@ pub extern "C" fn bbs_verify_context_add_message_prehashed(
 pub extern "C" fn bbs_verify_context_set_public_key(
     handle: u64,
     public_key: ByteArray,
-    err: &mut ExternError,
+    err: *mut ExternError,
 ) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
     bbs_sign_context_set_public_key(handle, public_key, err)
 }
 
@@ -135,8 +474,27 @@ add_bytes_impl!(
     Signature
 );
 
+#[cfg(feature = "cose")]
+/// Set the signature from its COSE encoding.
+/// The issuer key id is not checked, set the public key it names
+#[no_mangle]
+pub extern "C" fn bbs_verify_context_set_cose_signature(
+    handle: u64,
+    cose: ByteArray,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let cose = cose.to_vec();
+    SIGN_CONTEXT.call_with_result_mut(err, handle, move |ctx| -> Result<(), BbsFfiError> {
+        ctx.signature = Some(decode_signature(&cose)?.value);
+        Ok(())
+    });
+    err.get_code().code()
+}
+
 #[no_mangle]
-pub extern "C" fn bbs_verify_context_finish(handle: u64, err: &mut ExternError) -> i32 {
+pub extern "C" fn bbs_verify_context_finish(handle: u64, err: *mut ExternError) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
     SIGN_CONTEXT.call_with_result(err, handle, move |ctx| -> Result<i32, BbsFfiError> {
         if ctx.signature.is_none() {
             return Err(BbsFfiError::new("Signature must be set"))
@@ -148,12 +506,12 @@ pub extern "C" fn bbs_verify_context_finish(handle: u64, err: &mut ExternError)
             return Err(BbsFfiError::new("Messages cannot be empty"))
         }
 
-        match (ctx.signature.as_ref(), ctx.public_key.as_ref()) {
-            (Some(sig), Some(pk)) => match sig.verify(ctx.messages.as_slice(), pk) {
-                Ok(b) => Ok(if b { 0 } else { 1 }),
-                Err(e) => Err(BbsFfiError(format!("{:?}", e))),
-            },
-            (_, _) => Err(BbsFfiError::new("")),
-        }
+        let verified = VerifyBuilder::new(
+            ctx.public_key.as_ref().unwrap(),
+            ctx.signature.as_ref().unwrap(),
+        )
+        .prehashed_messages(ctx.messages.iter().copied())
+        .verify()?;
+        Ok(if verified { 0 } else { 1 })
     })
 }
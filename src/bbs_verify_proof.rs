@@ -1,125 +1,98 @@
+use crate::api::{Parse, Proof, VerifyProofBuilder};
+#[cfg(feature = "cose")]
+use crate::cose::decode_proof;
+use crate::envelope::{self, ArtifactType};
+#[cfg(feature = "jwp")]
+use crate::jwp::PresentedJwp;
+use crate::last_error::ErrorSlot;
+use crate::version::BBS_PROOF_FORMAT_VERSION;
 use crate::{BbsFfiError, ByteArray};
 use bbs::prelude::*;
 use ffi_support::*;
-use serde::{
-    de::{Error as DError, Visitor},
-    Deserialize, Deserializer, Serialize, Serializer,
-};
-use std::{
-    collections::BTreeSet,
-    convert::TryFrom,
-};
+use std::collections::BTreeMap;
 
 lazy_static! {
     pub static ref VERIFY_PROOF_CONTEXT: ConcurrentHandleMap<VerifyProofContext> =
         ConcurrentHandleMap::new();
 }
 
-define_handle_map_deleter!(VERIFY_PROOF_CONTEXT, free_verify_proof);
+define_handle_deleter!(VERIFY_PROOF_CONTEXT, free_verify_proof);
 
 pub struct VerifyProofContext {
     pub messages: Vec<SignatureMessage>,
+    pub revealed: BTreeMap<usize, SignatureMessage>,
     pub nonce: Option<ProofNonce>,
-    pub proof: Option<PoKOfSignatureProofWrapper>,
+    pub proof: Option<Proof>,
     pub public_key: Option<PublicKey>,
+    /// Where the revealed messages came from when they cannot be added
+    pub messages_from: Option<&'static str>,
 }
 
-#[derive(Debug)]
-pub struct PoKOfSignatureProofWrapper {
-    bit_vector: Vec<u8>,
-    proof: PoKOfSignatureProof,
-}
-
-impl TryFrom<&[u8]> for PoKOfSignatureProofWrapper {
-    type Error = BBSError;
-
-    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
-        let message_count = u16::from_be_bytes(*array_ref![value, 0, 2]) as usize;
-        let bitvector_length = (message_count / 8) + 1;
-        let offset = bitvector_length + 2;
-        let proof = PoKOfSignatureProof::try_from(&value[offset..])?;
-        Ok(Self {
-            bit_vector: value[..offset].to_vec(),
-            proof,
-        })
-    }
-}
-
-impl TryFrom<Vec<u8>> for PoKOfSignatureProofWrapper {
-    type Error = BBSError;
-
-    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
-        Self::try_from(value.as_slice())
-    }
-}
-
-impl PoKOfSignatureProofWrapper {
-    pub fn unpack(&self) -> (BTreeSet<usize>, PoKOfSignatureProof) {
-        let message_count = u16::from_be_bytes(*array_ref![self.bit_vector, 0, 2]) as usize;
-        let bitvector_length = (message_count / 8) + 1;
-        let offset = bitvector_length + 2;
-        (
-            bitvector_to_revealed(&self.bit_vector[2..offset]),
-            self.proof.clone(),
-        )
-    }
-
-    pub fn to_bytes(&self) -> Vec<u8> {
-        let mut data = self.bit_vector.to_vec();
-        data.append(&mut self.proof.to_bytes_compressed_form());
-        data
+fn check_messages(ctx: &VerifyProofContext) -> Result<(), BbsFfiError> {
+    match ctx.messages_from {
+        Some(from) => Err(BbsFfiError(format!(
+            "Messages come from {} and cannot be added",
+            from
+        ))),
+        None => Ok(()),
     }
 }
 
-impl Serialize for PoKOfSignatureProofWrapper {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_bytes(self.to_bytes().as_slice())
+/// The number of messages signed by the signature behind `proof`,
+/// or -1 if `proof` is too short or an envelope for something else
+#[no_mangle]
+pub extern "C" fn bbs_get_total_messages_count_for_proof(proof: ByteArray) -> i32 {
+    let data = proof.to_vec();
+    match envelope::open(ArtifactType::ProofArtifact, &data) {
+        Ok(proof) if proof.len() >= 2 => u16::from_be_bytes(*array_ref![proof, 0, 2]) as i32,
+        _ => -1,
     }
 }
 
-impl<'a> Deserialize<'a> for PoKOfSignatureProofWrapper {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'a>,
-    {
-        struct DeserializeVisitor;
-
-        impl Visitor<'_> for DeserializeVisitor {
-            type Value = PoKOfSignatureProofWrapper;
-
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                formatter.write_str("expected byte array")
-            }
-
-            fn visit_bytes<E>(self, value: &[u8]) -> Result<PoKOfSignatureProofWrapper, E>
-            where
-                E: DError,
-            {
-                PoKOfSignatureProofWrapper::try_from(value)
-                    .map_err(|_| DError::invalid_value(serde::de::Unexpected::Bytes(value), &self))
-            }
+/// Read the framing of `proof` without verifying it.
+/// `revealed` receives the revealed message indices in ascending order, each a big-endian u32
+#[no_mangle]
+pub extern "C" fn bbs_proof_inspect(
+    proof: ByteArray,
+    message_count: &mut u32,
+    revealed: &mut ByteBuffer,
+    hidden_count: &mut u32,
+    version: &mut u32,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    match Proof::parse(&proof.to_vec()) {
+        Ok(proof) => {
+            let indices = proof.revealed();
+            *message_count = proof.message_count() as u32;
+            *hidden_count = (proof.message_count() - indices.len()) as u32;
+            *version = BBS_PROOF_FORMAT_VERSION;
+            *revealed = ByteBuffer::from_vec(
+                indices
+                    .iter()
+                    .flat_map(|i| (*i as u32).to_be_bytes())
+                    .collect(),
+            );
+            *err = ExternError::success();
+            0
+        }
+        Err(e) => {
+            *err = ExternError::new_error(ErrorCode::new(1), e.message());
+            1
         }
-
-        deserializer.deserialize_bytes(DeserializeVisitor)
     }
 }
 
 #[no_mangle]
-pub extern "C" fn bbs_get_total_messages_count_for_proof(proof: ByteArray) -> i32 {
-    let proof = proof.to_vec();
-    u16::from_be_bytes(*array_ref![proof, 0, 2]) as i32
-}
-
-#[no_mangle]
-pub extern "C" fn bbs_verify_proof_context_init(err: &mut ExternError) -> u64 {
+pub extern "C" fn bbs_verify_proof_context_init(err: *mut ExternError) -> u64 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
     VERIFY_PROOF_CONTEXT.insert_with_output(err, || VerifyProofContext {
         messages: Vec::new(),
+        revealed: BTreeMap::new(),
         nonce: None,
         public_key: None,
         proof: None,
+        messages_from: None,
     })
 }
 
@@ -127,16 +100,97 @@ add_message_impl!(
     bbs_verify_proof_context_add_message_string,
     bbs_verify_proof_context_add_message_bytes,
     bbs_verify_proof_context_add_message_prehashed,
-    VERIFY_PROOF_CONTEXT
+    VERIFY_PROOF_CONTEXT,
+    check = check_messages
 );
 
+/// Hash and add the revealed `message` at `index`.
+/// Messages added this way are matched to the proof's revealed indices instead of by the order they were added
+#[no_mangle]
+pub extern "C" fn bbs_verify_proof_context_add_revealed_message(
+    handle: u64,
+    index: u32,
+    message: ByteArray,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let message = message.to_vec();
+    if message.is_empty() {
+        *err = ExternError::new_error(ErrorCode::new(1), "Message cannot be empty");
+        return 1;
+    }
+    VERIFY_PROOF_CONTEXT.call_with_result_mut(err, handle, |ctx| -> Result<(), BbsFfiError> {
+        check_messages(ctx)?;
+        let index = index as usize;
+        if ctx.revealed.contains_key(&index) {
+            return Err(BbsFfiError(format!("Message {} was already added", index)));
+        }
+        ctx.revealed.insert(index, SignatureMessage::hash(message));
+        Ok(())
+    });
+    err.get_code().code()
+}
+
 add_bytes_impl!(
     bbs_verify_proof_context_set_proof,
     VERIFY_PROOF_CONTEXT,
     proof,
-    PoKOfSignatureProofWrapper
+    Proof
 );
 
+#[cfg(feature = "cose")]
+/// Set the proof from its COSE encoding.
+/// The issuer key id is not checked, set the public key it names
+#[no_mangle]
+pub extern "C" fn bbs_verify_proof_context_set_cose_proof(
+    handle: u64,
+    cose: ByteArray,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let cose = cose.to_vec();
+    VERIFY_PROOF_CONTEXT.call_with_result_mut(err, handle, move |ctx| -> Result<(), BbsFfiError> {
+        ctx.proof = Some(decode_proof(&cose)?.value);
+        Ok(())
+    });
+    err.get_code().code()
+}
+
+#[cfg(feature = "jwp")]
+/// Set the proof, nonce and revealed messages from the presented JWP `value`,
+/// checking its presentation header carries the `nonce` the verifier sent.
+/// Set the issuer's public key then call `bbs_verify_proof_context_finish`
+#[no_mangle]
+pub extern "C" fn bbs_verify_proof_context_set_jwp(
+    handle: u64,
+    value: ByteArray,
+    nonce: FfiStr<'_>,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let value = value.to_vec();
+    VERIFY_PROOF_CONTEXT.call_with_result_mut(err, handle, move |ctx| -> Result<(), BbsFfiError> {
+        let jwp = PresentedJwp::parse(&value)?;
+        jwp.check_nonce(nonce.as_str())?;
+        if ctx.nonce.is_some() {
+            return Err(BbsFfiError::new(
+                "The nonce comes from the JWP and cannot be set",
+            ));
+        }
+        if !ctx.messages.is_empty() || !ctx.revealed.is_empty() {
+            return Err(BbsFfiError::new(
+                "Messages come from the JWP and cannot be added",
+            ));
+        }
+        ctx.revealed = jwp.revealed_messages();
+        ctx.nonce = Some(jwp.nonce());
+        ctx.proof = Some(jwp.proof);
+        ctx.messages_from = Some("the JWP");
+        Ok(())
+    });
+    err.get_code().code()
+}
+
 add_bytes_impl!(
     bbs_verify_proof_context_set_public_key,
     VERIFY_PROOF_CONTEXT,
@@ -153,42 +207,41 @@ add_bytes_impl!(
     ProofNonce
 );
 
+/// Verify the proof set on `ctx`, a proof that does not verify is not an error
+pub(crate) fn verify_context(
+    ctx: &VerifyProofContext,
+) -> Result<PoKOfSignatureProofStatus, BbsFfiError> {
+    if ctx.proof.is_none() {
+        return Err(BbsFfiError::new("Proof must be set"))
+    }
+    if ctx.public_key.is_none() {
+        return Err(BbsFfiError::new("Public key must be set"))
+    }
+    if ctx.nonce.is_none() {
+        return Err(BbsFfiError::new("Nonce must be set"))
+    }
+    if ctx.messages.is_empty() && ctx.revealed.is_empty() {
+        return Err(BbsFfiError::new("Messages cannot be empty"))
+    }
+    VerifyProofBuilder::new(
+        ctx.public_key.as_ref().unwrap(),
+        ctx.proof.as_ref().unwrap(),
+        ctx.nonce.as_ref().unwrap(),
+    )
+    .prehashed_messages(ctx.messages.iter().copied())
+    .prehashed_revealed_messages(ctx.revealed.iter().map(|(i, m)| (*i, *m)))
+    .verify()
+    .map_err(BbsFfiError::from)
+}
+
 #[no_mangle]
-pub extern "C" fn bbs_verify_proof_context_finish(handle: u64, err: &mut ExternError) -> i32 {
+pub extern "C" fn bbs_verify_proof_context_finish(handle: u64, err: *mut ExternError) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
     let _ = VERIFY_PROOF_CONTEXT.call_with_result(
         err,
         handle,
         move |ctx| -> Result<i32, BbsFfiError> {
-            if ctx.proof.is_none() {
-                return Err(BbsFfiError::new("Proof must be set"))
-            }
-            if ctx.public_key.is_none() {
-                return Err(BbsFfiError::new("Public key must be set"))
-            }
-            if ctx.nonce.is_none() {
-                return Err(BbsFfiError::new("Nonce must be set"))
-            }
-            if ctx.messages.is_empty() {
-                return Err(BbsFfiError::new("Messages cannot be empty"))
-            }
-            let public_key = &ctx.public_key.as_ref().unwrap();
-            let nonce = &ctx.nonce.as_ref().unwrap();
-            let proofwrapper = ctx.proof.as_ref().unwrap();
-
-            let (revealed, proof) = proofwrapper.unpack();
-
-            // These should be equal
-            if revealed.len() != ctx.messages.len() {
-                return Err(BbsFfiError::new("Indices and messages are not equal"))
-            }
-
-            let mut challenge_bytes = proof.get_bytes_for_challenge(revealed.clone(), public_key);
-            challenge_bytes.extend_from_slice(&nonce.to_bytes_compressed_form()[..]);
-
-            let proof_msgs = revealed.iter().zip(ctx.messages.iter()).map(|(i, m)| (*i, *m)).collect();
-
-            let challenge_verifier = ProofChallenge::hash(&challenge_bytes);
-            let res = proof.verify(public_key, &proof_msgs, &challenge_verifier)?;
+            let res = verify_context(ctx)?;
             match res
             {
                 PoKOfSignatureProofStatus::Success => Ok(i32::ffi_default()),
@@ -208,24 +261,3 @@ pub extern "C" fn bbs_verify_proof_context_finish(handle: u64, err: &mut ExternE
     err.get_code().code()
 
 }
-/// Convert big-endian vector to u32
-pub(crate) fn bitvector_to_revealed(data: &[u8]) -> BTreeSet<usize> {
-    let mut revealed_messages = BTreeSet::new();
-    let mut scalar = 0;
-
-    for b in data.iter().rev() {
-        let mut v = *b;
-        let mut remaining = 8;
-        while v > 0 {
-            let revealed = v & 1u8;
-            if revealed == 1 {
-                revealed_messages.insert(scalar);
-            }
-            v >>= 1;
-            scalar += 1;
-            remaining -= 1;
-        }
-        scalar += remaining;
-    }
-    revealed_messages
-}
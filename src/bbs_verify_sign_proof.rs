@@ -1,14 +1,16 @@
+use crate::api::VerifyBlindCommitmentBuilder;
+use crate::last_error::ErrorSlot;
 use crate::{BbsFfiError, ByteArray};
 use bbs::prelude::*;
 use ffi_support::*;
-use std::{collections::BTreeSet, convert::TryFrom};
+use std::collections::BTreeSet;
 
 lazy_static! {
     static ref VERIFY_SIGN_PROOF_CONTEXT: ConcurrentHandleMap<VerifyBlindSignProofContext> =
         ConcurrentHandleMap::new();
 }
 
-define_handle_map_deleter!(VERIFY_SIGN_PROOF_CONTEXT, free_verify_sign_proof);
+define_handle_deleter!(VERIFY_SIGN_PROOF_CONTEXT, free_verify_sign_proof);
 
 struct VerifyBlindSignProofContext {
     blinded: BTreeSet<usize>,
@@ -18,7 +20,8 @@ struct VerifyBlindSignProofContext {
 }
 
 #[no_mangle]
-pub extern "C" fn bbs_verify_blind_commitment_context_init(err: &mut ExternError) -> u64 {
+pub extern "C" fn bbs_verify_blind_commitment_context_init(err: *mut ExternError) -> u64 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
     VERIFY_SIGN_PROOF_CONTEXT.insert_with_output(err, || VerifyBlindSignProofContext {
         blinded: BTreeSet::new(),
         nonce: None,
@@ -31,8 +34,9 @@ pub extern "C" fn bbs_verify_blind_commitment_context_init(err: &mut ExternError
 pub extern "C" fn bbs_verify_blind_commitment_context_add_blinded(
     handle: u64,
     index: u32,
-    err: &mut ExternError,
+    err: *mut ExternError,
 ) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
     VERIFY_SIGN_PROOF_CONTEXT.call_with_output_mut(err, handle, |ctx| {
         ctx.blinded.insert(index as usize);
     });
@@ -65,8 +69,9 @@ add_bytes_impl!(
 #[no_mangle]
 pub extern "C" fn bbs_verify_blind_commitment_context_finish(
     handle: u64,
-    err: &mut ExternError,
+    err: *mut ExternError,
 ) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
     let _ = VERIFY_SIGN_PROOF_CONTEXT.call_with_result(
         err,
         handle,
@@ -84,17 +89,16 @@ pub extern "C" fn bbs_verify_blind_commitment_context_finish(
                 return Err(BbsFfiError::new("Public Key must be set"))
             }
 
-            let nonce = ctx.nonce.as_ref().unwrap();
-            let proof = ctx.proof.as_ref().unwrap();
-            let public_key = ctx.public_key.as_ref().unwrap();
-            let mut revealed = BTreeSet::new();
-            for i in 0..public_key.message_count() {
-                if !ctx.blinded.contains(&i) {
-                    revealed.insert(i);
-                }
-            }
+            let verified = ctx.blinded.iter().fold(
+                VerifyBlindCommitmentBuilder::new(
+                    ctx.public_key.as_ref().unwrap(),
+                    ctx.proof.as_ref().unwrap(),
+                    ctx.nonce.as_ref().unwrap(),
+                ),
+                |builder, i| builder.blinded(*i),
+            );
 
-            if proof.verify(&revealed, public_key, nonce)? {
+            if verified.verify()? {
                 Ok(i32::ffi_default())
             } else {
                 Err(BbsFfiError::new("Bad hidden message in proof"))
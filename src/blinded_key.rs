@@ -0,0 +1,140 @@
+use crate::bls_ciphersuite::point_to_bytes;
+use crate::rng::BbsRng;
+use crate::BbsFfiError;
+use bbs::FR_COMPRESSED_SIZE;
+use ff_zeroize::Field;
+use pairing_plus::{
+    bls12_381::{Bls12, Fr},
+    hash_to_field::{hash_to_field, ExpandMsgXmd},
+    serdes::SerDes,
+    CurveProjective,
+};
+use zeroize::Zeroize;
+
+/// Size of a proof of knowledge for a blinded key: c || z_x || z_r
+pub(crate) const PROOF_SIZE: usize = 3 * FR_COMPRESSED_SIZE;
+
+/// A blinded key group with its blinding generator and challenge domain separation tag
+pub(crate) trait BlindedKeyGroup:
+    CurveProjective<Engine = Bls12, Scalar = Fr> + SerDes
+{
+    const BLINDING_GENERATOR: &'static [u8];
+    const DST: &'static [u8];
+}
+
+impl BlindedKeyGroup for pairing_plus::bls12_381::G2 {
+    const BLINDING_GENERATOR: &'static [u8] = crate::BLINDING_G2;
+    const DST: &'static [u8] = b"BLS_BLINDED_KEY_POK_BLS12381G2_XMD:SHA-256_";
+}
+
+impl BlindedKeyGroup for pairing_plus::bls12_381::G1 {
+    const BLINDING_GENERATOR: &'static [u8] = crate::BLINDING_G1;
+    const DST: &'static [u8] = b"BLS_BLINDED_KEY_POK_BLS12381G1_XMD:SHA-256_";
+}
+
+fn blinding_generator<G: BlindedKeyGroup>() -> G {
+    let mut g = G::BLINDING_GENERATOR;
+    G::deserialize(&mut g, true).unwrap()
+}
+
+/// `g` ^ `a` * `h` ^ `b`
+fn commit<G: BlindedKeyGroup>(a: Fr, b: Fr) -> G {
+    let mut p = G::one();
+    p.mul_assign(a);
+    let mut h = blinding_generator::<G>();
+    h.mul_assign(b);
+    p.add_assign(&h);
+    p
+}
+
+fn challenge<G: BlindedKeyGroup>(w: &G, t: &G, nonce: &[u8]) -> Fr {
+    let mut data = point_to_bytes(w);
+    data.extend_from_slice(&point_to_bytes(t));
+    data.extend_from_slice(nonce);
+    hash_to_field::<Fr, ExpandMsgXmd<sha2::Sha256>>(&data, G::DST, 1)[0]
+}
+
+fn fr_from_bytes(data: &[u8], name: &str) -> Result<Fr, BbsFfiError> {
+    if data.len() != FR_COMPRESSED_SIZE {
+        return Err(BbsFfiError(format!(
+            "{} must be {} bytes, found {}",
+            name,
+            FR_COMPRESSED_SIZE,
+            data.len()
+        )));
+    }
+    let mut data = data;
+    Fr::deserialize(&mut data, true).map_err(|e| BbsFfiError(format!("{:?}", e)))
+}
+
+pub(crate) fn blinding_factor_from_bytes(data: &[u8]) -> Result<Fr, BbsFfiError> {
+    fr_from_bytes(data, "Blinding factor")
+}
+
+/// Prove knowledge of `x` and `r` such that `w` = `g` ^ `x` * `h` ^ `r`
+/// bound to the verifier's `nonce`
+pub(crate) fn prove<G: BlindedKeyGroup>(mut x: Fr, mut r: Fr, nonce: &[u8]) -> Vec<u8> {
+    let mut k_x = Fr::random(&mut BbsRng);
+    let mut k_r = Fr::random(&mut BbsRng);
+    let w = commit::<G>(x, r);
+    let t = commit::<G>(k_x, k_r);
+    let c = challenge(&w, &t, nonce);
+
+    let mut z_x = x;
+    z_x.mul_assign(&c);
+    z_x.add_assign(&k_x);
+    let mut z_r = r;
+    z_r.mul_assign(&c);
+    z_r.add_assign(&k_r);
+    // Either nonce reveals the secret key from the proof
+    k_x.zeroize();
+    k_r.zeroize();
+    x.zeroize();
+    r.zeroize();
+
+    let mut out = Vec::with_capacity(PROOF_SIZE);
+    c.serialize(&mut out, true).unwrap();
+    z_x.serialize(&mut out, true).unwrap();
+    z_r.serialize(&mut out, true).unwrap();
+    out
+}
+
+/// Check a proof from `prove` against the blinded public key `w`
+pub(crate) fn verify<G: BlindedKeyGroup>(
+    w: &G,
+    nonce: &[u8],
+    proof: &[u8],
+) -> Result<bool, BbsFfiError> {
+    if proof.len() != PROOF_SIZE {
+        return Err(BbsFfiError(format!(
+            "Proof must be {} bytes, found {}",
+            PROOF_SIZE,
+            proof.len()
+        )));
+    }
+    let c = fr_from_bytes(&proof[..FR_COMPRESSED_SIZE], "Proof challenge")?;
+    let z_x = fr_from_bytes(
+        &proof[FR_COMPRESSED_SIZE..2 * FR_COMPRESSED_SIZE],
+        "Proof response",
+    )?;
+    let z_r = fr_from_bytes(&proof[2 * FR_COMPRESSED_SIZE..], "Proof response")?;
+
+    // t = g ^ z_x * h ^ z_r * w ^ -c
+    let mut t = commit::<G>(z_x, z_r);
+    let mut wc = *w;
+    wc.mul_assign(c);
+    t.sub_assign(&wc);
+    Ok(challenge(w, &t, nonce) == c)
+}
+
+/// Remove the blinding from `w` leaving the public key `g` ^ `x`
+pub(crate) fn unblind<G: BlindedKeyGroup>(w: &G, r: Fr) -> Result<G, BbsFfiError> {
+    let mut h = blinding_generator::<G>();
+    h.mul_assign(r);
+    let mut pk = *w;
+    pk.sub_assign(&h);
+    if pk.is_zero() {
+        return Err(BbsFfiError::new("Unblinded public key is the identity"));
+    }
+    Ok(pk)
+}
@@ -1,7 +1,18 @@
-use crate::ByteArray;
+use crate::bls_ciphersuite::{
+    point_from_bytes, point_to_bytes, pop_prove, pop_verify, secret_key_from_bytes, sk_to_pk,
+    MinPkSize, MinSigSize,
+};
+use crate::blinded_key::{self, blinding_factor_from_bytes, BlindedKeyGroup};
+use crate::last_error::ErrorSlot;
+use crate::parse::Parse;
+use crate::rng::{catch_rng_failure, BbsRng};
+use crate::threshold::{split_secret_key, SHARE_SIZE};
+use crate::{BbsFfiError, ByteArray};
 use bbs::prelude::*;
-use ffi_support::{ByteBuffer, ErrorCode, ExternError};
-use std::convert::TryFrom;
+use ffi_support::{call_with_result, ByteBuffer, ErrorCode, ExternError};
+use pairing_plus::bls12_381::{G1, G2};
+use rand::RngCore;
+use zeroize::Zeroize;
 
 #[no_mangle]
 pub extern "C" fn bls_secret_key_size() -> i32 {
@@ -28,13 +39,18 @@ pub extern "C" fn bls_generate_g2_key(
     seed: ByteArray,
     public_key: &mut ByteBuffer,
     secret_key: &mut ByteBuffer,
-    err: &mut ExternError,
+    err: *mut ExternError,
 ) -> i32 {
-    let (p, s) = crate::bls_generate_g2_key(seed.to_opt_vec());
-    *public_key = ByteBuffer::from_vec(p);
-    *secret_key = ByteBuffer::from_vec(s);
-    *err = ExternError::success();
-    0
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    match catch_rng_failure(|| crate::bls_generate_g2_key(seed.to_opt_vec())) {
+        Ok((p, s)) => {
+            *public_key = ByteBuffer::from_vec(p);
+            *secret_key = ByteBuffer::from_vec(s);
+            *err = ExternError::success();
+        }
+        Err(e) => *err = e.into(),
+    }
+    err.get_code().code()
 }
 
 #[no_mangle]
@@ -42,13 +58,18 @@ pub extern "C" fn bls_generate_g1_key(
     seed: ByteArray,
     public_key: &mut ByteBuffer,
     secret_key: &mut ByteBuffer,
-    err: &mut ExternError,
+    err: *mut ExternError,
 ) -> i32 {
-    let (p, s) = crate::bls_generate_g1_key(seed.to_opt_vec());
-    *public_key = ByteBuffer::from_vec(p);
-    *secret_key = ByteBuffer::from_vec(s);
-    *err = ExternError::success();
-    0
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    match catch_rng_failure(|| crate::bls_generate_g1_key(seed.to_opt_vec())) {
+        Ok((p, s)) => {
+            *public_key = ByteBuffer::from_vec(p);
+            *secret_key = ByteBuffer::from_vec(s);
+            *err = ExternError::success();
+        }
+        Err(e) => *err = e.into(),
+    }
+    err.get_code().code()
 }
 
 #[no_mangle]
@@ -57,14 +78,19 @@ pub extern "C" fn bls_generate_blinded_g2_key(
     public_key: &mut ByteBuffer,
     secret_key: &mut ByteBuffer,
     blinding_factor: &mut ByteBuffer,
-    err: &mut ExternError,
+    err: *mut ExternError,
 ) -> i32 {
-    let (b, p, s) = crate::bls_generate_blinded_g2_key(seed.to_opt_vec());
-    *public_key = ByteBuffer::from_vec(p);
-    *secret_key = ByteBuffer::from_vec(s);
-    *blinding_factor = ByteBuffer::from_vec(b);
-    *err = ExternError::success();
-    0
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    match catch_rng_failure(|| crate::bls_generate_blinded_g2_key(seed.to_opt_vec())) {
+        Ok((b, p, s)) => {
+            *public_key = ByteBuffer::from_vec(p);
+            *secret_key = ByteBuffer::from_vec(s);
+            *blinding_factor = ByteBuffer::from_vec(b);
+            *err = ExternError::success();
+        }
+        Err(e) => *err = e.into(),
+    }
+    err.get_code().code()
 }
 
 #[no_mangle]
@@ -73,23 +99,74 @@ pub extern "C" fn bls_generate_blinded_g1_key(
     public_key: &mut ByteBuffer,
     secret_key: &mut ByteBuffer,
     blinding_factor: &mut ByteBuffer,
-    err: &mut ExternError,
+    err: *mut ExternError,
 ) -> i32 {
-    let (b, p, s) = crate::bls_generate_blinded_g1_key(seed.to_opt_vec());
-    *public_key = ByteBuffer::from_vec(p);
-    *secret_key = ByteBuffer::from_vec(s);
-    *blinding_factor = ByteBuffer::from_vec(b);
-    *err = ExternError::success();
-    0
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    match catch_rng_failure(|| crate::bls_generate_blinded_g1_key(seed.to_opt_vec())) {
+        Ok((b, p, s)) => {
+            *public_key = ByteBuffer::from_vec(p);
+            *secret_key = ByteBuffer::from_vec(s);
+            *blinding_factor = ByteBuffer::from_vec(b);
+            *err = ExternError::success();
+        }
+        Err(e) => *err = e.into(),
+    }
+    err.get_code().code()
+}
+
+/// Size of one secret key share record: index (u32 big endian) || share
+#[no_mangle]
+pub extern "C" fn bls_secret_key_share_size() -> i32 {
+    SHARE_SIZE as i32
+}
+
+/// Generate a BLS key pair in G2 like `bls_generate_g2_key` and split the secret key
+/// into `total` Shamir shares on a degree `threshold - 1` polynomial. Any `2 * threshold - 1`
+/// of them can sign together, so `threshold` can be at most (`total` + 1) / 2.
+/// `shares` is the concatenation of `total` share records for parties 1..=`total`.
+/// The secret key itself is not returned
+#[no_mangle]
+pub extern "C" fn bls_generate_g2_key_shares(
+    seed: ByteArray,
+    threshold: u32,
+    total: u32,
+    public_key: &mut ByteBuffer,
+    shares: &mut ByteBuffer,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let seed = match seed.to_opt_vec() {
+        Some(seed) => seed,
+        None => {
+            let mut seed_data = vec![0u8; 32];
+            if let Err(e) = BbsRng.try_fill_bytes(seed_data.as_mut_slice()) {
+                *err = BbsFfiError(e.to_string()).into();
+                return err.get_code().code();
+            }
+            seed_data
+        }
+    };
+    match split_secret_key(&seed, threshold, total) {
+        Ok((mut sk, s)) => {
+            let pk = sk_to_pk::<MinSigSize>(sk);
+            sk.zeroize();
+            *public_key = ByteBuffer::from_vec(point_to_bytes(&pk));
+            *shares = ByteBuffer::from_vec(s.iter().flat_map(|s| s.to_bytes()).collect());
+            *err = ExternError::success();
+        }
+        Err(e) => *err = e.into(),
+    }
+    err.get_code().code()
 }
 
 #[no_mangle]
 pub extern "C" fn bls_get_public_key(
     secret_key: ByteArray,
     public_key: &mut ByteBuffer,
-    err: &mut ExternError,
+    err: *mut ExternError,
 ) -> i32 {
-    let sk = SecretKey::try_from(secret_key.to_vec());
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let sk = SecretKey::parse(&secret_key.to_vec());
     match sk {
         Ok(s) => {
             let (dpk, _) = DeterministicPublicKey::new(Some(KeyGenOption::FromSecretKey(s)));
@@ -98,7 +175,7 @@ pub extern "C" fn bls_get_public_key(
             0
         }
         Err(e) => {
-            *err = ExternError::new_error(ErrorCode::new(1), format!("{:?}", e));
+            *err = ExternError::new_error(ErrorCode::new(1), e.message());
             1
         }
     }
@@ -109,9 +186,10 @@ pub extern "C" fn bls_secret_key_to_bbs_key(
     secret_key: ByteArray,
     message_count: u32,
     public_key: &mut ByteBuffer,
-    err: &mut ExternError,
+    err: *mut ExternError,
 ) -> i32 {
-    let sk = SecretKey::try_from(secret_key.to_vec());
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let sk = SecretKey::parse(&secret_key.to_vec());
     match sk {
         Ok(s) => {
             let (dpk, _) = DeterministicPublicKey::new(Some(KeyGenOption::FromSecretKey(s)));
@@ -129,7 +207,7 @@ pub extern "C" fn bls_secret_key_to_bbs_key(
             }
         }
         Err(e) => {
-            *err = ExternError::new_error(ErrorCode::new(1), format!("{:?}", e));
+            *err = ExternError::new_error(ErrorCode::new(1), e.message());
             1
         }
     }
@@ -140,15 +218,16 @@ pub extern "C" fn bls_public_key_to_bbs_key(
     d_public_key: ByteArray,
     message_count: u32,
     public_key: &mut ByteBuffer,
-    err: &mut ExternError,
+    err: *mut ExternError,
 ) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
     let bytes = d_public_key.to_vec();
-    let res = DeterministicPublicKey::try_from(bytes.clone());
+    let res = DeterministicPublicKey::parse(&bytes);
     match res {
         Err(e) => {
             *err = ExternError::new_error(
                 ErrorCode::new(1),
-                format!("{:?}. Found length {} with {:?}", e, bytes.len(), bytes),
+                e.message(),
             );
             1
         }
@@ -168,3 +247,240 @@ pub extern "C" fn bls_public_key_to_bbs_key(
         }
     }
 }
+
+/// Create a proof of possession for the secret key of a G2 public key
+/// as generated by `bls_generate_g2_key`. The proof is a G1 element
+#[no_mangle]
+pub extern "C" fn bls_pop_prove_g2(
+    secret_key: ByteArray,
+    proof: &mut ByteBuffer,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let res = call_with_result(err, || -> Result<ByteBuffer, BbsFfiError> {
+        let sk = secret_key_from_bytes(&secret_key.to_vec())?;
+        let pop = pop_prove::<MinSigSize>(sk);
+        Ok(ByteBuffer::from_vec(point_to_bytes(&pop)))
+    });
+    if err.get_code().is_success() {
+        *proof = res;
+    }
+    err.get_code().code()
+}
+
+/// Create a proof of possession for the secret key of a G1 public key
+/// as generated by `bls_generate_g1_key`. The proof is a G2 element
+#[no_mangle]
+pub extern "C" fn bls_pop_prove_g1(
+    secret_key: ByteArray,
+    proof: &mut ByteBuffer,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let res = call_with_result(err, || -> Result<ByteBuffer, BbsFfiError> {
+        let sk = secret_key_from_bytes(&secret_key.to_vec())?;
+        let pop = pop_prove::<MinPkSize>(sk);
+        Ok(ByteBuffer::from_vec(point_to_bytes(&pop)))
+    });
+    if err.get_code().is_success() {
+        *proof = res;
+    }
+    err.get_code().code()
+}
+
+/// Verify a proof of possession for a G2 public key.
+/// Returns 0 if the proof is valid
+#[no_mangle]
+pub extern "C" fn bls_pop_verify_g2(
+    public_key: ByteArray,
+    proof: ByteArray,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let _ = call_with_result(err, || -> Result<i32, BbsFfiError> {
+        let pk = point_from_bytes(&public_key.to_vec(), G2_COMPRESSED_SIZE, "Public key")?;
+        let pop = point_from_bytes(&proof.to_vec(), G1_COMPRESSED_SIZE, "Proof of possession")?;
+        if pop_verify::<MinSigSize>(&pk, &pop) {
+            Ok(0)
+        } else {
+            Err(BbsFfiError::new("Invalid proof of possession"))
+        }
+    });
+    err.get_code().code()
+}
+
+/// Verify a proof of possession for a G1 public key.
+/// Returns 0 if the proof is valid
+#[no_mangle]
+pub extern "C" fn bls_pop_verify_g1(
+    public_key: ByteArray,
+    proof: ByteArray,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let _ = call_with_result(err, || -> Result<i32, BbsFfiError> {
+        let pk = point_from_bytes(&public_key.to_vec(), G1_COMPRESSED_SIZE, "Public key")?;
+        let pop = point_from_bytes(&proof.to_vec(), G2_COMPRESSED_SIZE, "Proof of possession")?;
+        if pop_verify::<MinPkSize>(&pk, &pop) {
+            Ok(0)
+        } else {
+            Err(BbsFfiError::new("Invalid proof of possession"))
+        }
+    });
+    err.get_code().code()
+}
+
+#[no_mangle]
+pub extern "C" fn bls_blinded_key_proof_size() -> i32 {
+    blinded_key::PROOF_SIZE as i32
+}
+
+fn blinded_key_prove<G: BlindedKeyGroup>(
+    secret_key: ByteArray,
+    blinding_factor: ByteArray,
+    nonce: ByteArray,
+    proof: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32 {
+    let res = call_with_result(err, || -> Result<ByteBuffer, BbsFfiError> {
+        let mut sk = secret_key_from_bytes(&secret_key.to_vec())?;
+        let mut r = blinding_factor_from_bytes(&blinding_factor.to_vec())?;
+        let proof = blinded_key::prove::<G>(sk, r, &nonce.to_vec());
+        sk.zeroize();
+        r.zeroize();
+        Ok(ByteBuffer::from_vec(proof))
+    });
+    if err.get_code().is_success() {
+        *proof = res;
+    }
+    err.get_code().code()
+}
+
+fn blinded_key_verify<G: BlindedKeyGroup>(
+    public_key: ByteArray,
+    size: usize,
+    nonce: ByteArray,
+    proof: ByteArray,
+    err: &mut ExternError,
+) -> i32 {
+    let _ = call_with_result(err, || -> Result<i32, BbsFfiError> {
+        let w = point_from_bytes::<G>(&public_key.to_vec(), size, "Public key")?;
+        if blinded_key::verify(&w, &nonce.to_vec(), &proof.to_vec())? {
+            Ok(0)
+        } else {
+            Err(BbsFfiError::new("Invalid blinded key proof"))
+        }
+    });
+    err.get_code().code()
+}
+
+fn blinded_key_unblind<G: BlindedKeyGroup>(
+    blinded_public_key: ByteArray,
+    size: usize,
+    blinding_factor: ByteArray,
+    public_key: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32 {
+    let res = call_with_result(err, || -> Result<ByteBuffer, BbsFfiError> {
+        let w = point_from_bytes::<G>(&blinded_public_key.to_vec(), size, "Public key")?;
+        let r = blinding_factor_from_bytes(&blinding_factor.to_vec())?;
+        let pk = blinded_key::unblind(&w, r)?;
+        Ok(ByteBuffer::from_vec(point_to_bytes(&pk)))
+    });
+    if err.get_code().is_success() {
+        *public_key = res;
+    }
+    err.get_code().code()
+}
+
+/// Prove knowledge of the secret key `x` and blinding factor `r` of a blinded G2 public key
+/// `w` = `g2` ^ `x` * `blinding_g2` ^ `r` as generated by `bls_generate_blinded_g2_key`.
+/// `nonce` is supplied by the verifier. The proof does not reveal `g2` ^ `x`
+#[no_mangle]
+pub extern "C" fn bls_blinded_key_prove_g2(
+    secret_key: ByteArray,
+    blinding_factor: ByteArray,
+    nonce: ByteArray,
+    proof: &mut ByteBuffer,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    blinded_key_prove::<G2>(secret_key, blinding_factor, nonce, proof, err)
+}
+
+/// Prove knowledge of the secret key `x` and blinding factor `r` of a blinded G1 public key
+/// `w` = `g1` ^ `x` * `blinding_g1` ^ `r` as generated by `bls_generate_blinded_g1_key`.
+/// `nonce` is supplied by the verifier. The proof does not reveal `g1` ^ `x`
+#[no_mangle]
+pub extern "C" fn bls_blinded_key_prove_g1(
+    secret_key: ByteArray,
+    blinding_factor: ByteArray,
+    nonce: ByteArray,
+    proof: &mut ByteBuffer,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    blinded_key_prove::<G1>(secret_key, blinding_factor, nonce, proof, err)
+}
+
+/// Verify a proof of knowledge for a blinded G2 public key.
+/// Returns 0 if the proof is valid
+#[no_mangle]
+pub extern "C" fn bls_blinded_key_verify_g2(
+    public_key: ByteArray,
+    nonce: ByteArray,
+    proof: ByteArray,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    blinded_key_verify::<G2>(public_key, G2_COMPRESSED_SIZE, nonce, proof, err)
+}
+
+/// Verify a proof of knowledge for a blinded G1 public key.
+/// Returns 0 if the proof is valid
+#[no_mangle]
+pub extern "C" fn bls_blinded_key_verify_g1(
+    public_key: ByteArray,
+    nonce: ByteArray,
+    proof: ByteArray,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    blinded_key_verify::<G1>(public_key, G1_COMPRESSED_SIZE, nonce, proof, err)
+}
+
+/// Open a blinded G2 public key with its blinding factor, returning `g2` ^ `x`
+#[no_mangle]
+pub extern "C" fn bls_blinded_key_unblind_g2(
+    blinded_public_key: ByteArray,
+    blinding_factor: ByteArray,
+    public_key: &mut ByteBuffer,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    blinded_key_unblind::<G2>(
+        blinded_public_key,
+        G2_COMPRESSED_SIZE,
+        blinding_factor,
+        public_key,
+        err,
+    )
+}
+
+/// Open a blinded G1 public key with its blinding factor, returning `g1` ^ `x`
+#[no_mangle]
+pub extern "C" fn bls_blinded_key_unblind_g1(
+    blinded_public_key: ByteArray,
+    blinding_factor: ByteArray,
+    public_key: &mut ByteBuffer,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    blinded_key_unblind::<G1>(
+        blinded_public_key,
+        G1_COMPRESSED_SIZE,
+        blinding_factor,
+        public_key,
+        err,
+    )
+}
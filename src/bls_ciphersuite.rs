@@ -0,0 +1,203 @@
+use crate::BbsFfiError;
+use bbs::{G1_COMPRESSED_SIZE, G2_COMPRESSED_SIZE};
+use ff_zeroize::Field;
+use pairing_plus::{
+    bls12_381::{Bls12, Fq12, Fr, G1Affine, G2Affine, G1, G2},
+    hash_to_curve::HashToCurve,
+    hash_to_field::ExpandMsgXmd,
+    serdes::SerDes,
+    CurveProjective, Engine,
+};
+
+/// The groups and domain separation tags for a BLS proof of possession ciphersuite
+/// from <https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04>
+pub(crate) trait BlsCiphersuite {
+    type PublicKey: CurveProjective<Engine = Bls12, Scalar = Fr> + SerDes;
+    type Signature: CurveProjective<Engine = Bls12, Scalar = Fr>
+        + SerDes
+        + HashToCurve<ExpandMsgXmd<sha2::Sha256>>;
+
+    /// Domain separation tag for signatures
+    const SIG_DST: &'static [u8];
+    /// Domain separation tag for proofs of possession
+    const POP_DST: &'static [u8];
+    /// Size of a compressed public key
+    const PUBLIC_KEY_SIZE: usize;
+    /// Size of a compressed signature
+    const SIGNATURE_SIZE: usize;
+
+    /// Order the public key group and signature group elements for the pairing
+    fn pair(pk: &Self::PublicKey, sig: &Self::Signature) -> (G1Affine, G2Affine);
+}
+
+/// Public keys in G2, signatures in G1
+pub(crate) struct MinSigSize;
+
+impl BlsCiphersuite for MinSigSize {
+    type PublicKey = G2;
+    type Signature = G1;
+
+    const SIG_DST: &'static [u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+    const POP_DST: &'static [u8] = b"BLS_POP_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+    const PUBLIC_KEY_SIZE: usize = G2_COMPRESSED_SIZE;
+    const SIGNATURE_SIZE: usize = G1_COMPRESSED_SIZE;
+
+    fn pair(pk: &G2, sig: &G1) -> (G1Affine, G2Affine) {
+        (sig.into_affine(), pk.into_affine())
+    }
+}
+
+/// Public keys in G1, signatures in G2
+pub(crate) struct MinPkSize;
+
+impl BlsCiphersuite for MinPkSize {
+    type PublicKey = G1;
+    type Signature = G2;
+
+    const SIG_DST: &'static [u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+    const POP_DST: &'static [u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+    const PUBLIC_KEY_SIZE: usize = G1_COMPRESSED_SIZE;
+    const SIGNATURE_SIZE: usize = G2_COMPRESSED_SIZE;
+
+    fn pair(pk: &G1, sig: &G2) -> (G1Affine, G2Affine) {
+        (pk.into_affine(), sig.into_affine())
+    }
+}
+
+pub(crate) fn secret_key_from_bytes(data: &[u8]) -> Result<Fr, BbsFfiError> {
+    if data.len() != bbs::FR_COMPRESSED_SIZE {
+        return Err(BbsFfiError(format!(
+            "Secret key must be {} bytes, found {}",
+            bbs::FR_COMPRESSED_SIZE,
+            data.len()
+        )));
+    }
+    let mut data = data;
+    let sk = Fr::deserialize(&mut data, true).map_err(|e| BbsFfiError(format!("{:?}", e)))?;
+    if sk.is_zero() {
+        return Err(BbsFfiError::new("Secret key cannot be zero"));
+    }
+    Ok(sk)
+}
+
+/// Decode a compressed point, rejecting the identity. Subgroup membership
+/// is checked during decompression
+pub(crate) fn point_from_bytes<G: CurveProjective + SerDes>(
+    data: &[u8],
+    size: usize,
+    name: &str,
+) -> Result<G, BbsFfiError> {
+    if data.len() != size {
+        return Err(BbsFfiError(format!(
+            "{} must be {} bytes, found {}",
+            name,
+            size,
+            data.len()
+        )));
+    }
+    let mut data = data;
+    let p = G::deserialize(&mut data, true).map_err(|e| BbsFfiError(format!("{:?}", e)))?;
+    if p.is_zero() {
+        return Err(BbsFfiError(format!("{} cannot be the identity", name)));
+    }
+    Ok(p)
+}
+
+pub(crate) fn point_to_bytes<G: CurveProjective + SerDes>(p: &G) -> Vec<u8> {
+    let mut out = Vec::new();
+    p.serialize(&mut out, true).unwrap();
+    out
+}
+
+pub(crate) fn sk_to_pk<C: BlsCiphersuite>(sk: Fr) -> C::PublicKey {
+    let mut pk = C::PublicKey::one();
+    pk.mul_assign(sk);
+    pk
+}
+
+fn core_sign<C: BlsCiphersuite>(sk: Fr, msg: &[u8], dst: &[u8]) -> C::Signature {
+    let mut q = C::Signature::hash_to_curve(msg, dst);
+    q.mul_assign(sk);
+    q
+}
+
+/// Check e(pk_1, Q_1) * ... * e(pk_n, Q_n) == e(generator, sig)
+fn pairing_check<C: BlsCiphersuite>(
+    pairs: &[(C::PublicKey, C::Signature)],
+    sig: &C::Signature,
+) -> bool {
+    let mut g1s = Vec::with_capacity(pairs.len() + 1);
+    let mut g2s = Vec::with_capacity(pairs.len() + 1);
+    for (pk, q) in pairs {
+        let (p1, p2) = C::pair(pk, q);
+        g1s.push(p1);
+        g2s.push(p2);
+    }
+    let mut neg_generator = C::PublicKey::one();
+    neg_generator.negate();
+    let (p1, p2) = C::pair(&neg_generator, sig);
+    g1s.push(p1);
+    g2s.push(p2);
+    Bls12::pairing_multi_product(&g1s, &g2s) == Fq12::one()
+}
+
+pub(crate) fn pop_prove<C: BlsCiphersuite>(sk: Fr) -> C::Signature {
+    let pk = sk_to_pk::<C>(sk);
+    core_sign::<C>(sk, &point_to_bytes(&pk), C::POP_DST)
+}
+
+pub(crate) fn pop_verify<C: BlsCiphersuite>(pk: &C::PublicKey, proof: &C::Signature) -> bool {
+    let q = C::Signature::hash_to_curve(point_to_bytes(pk), C::POP_DST);
+    pairing_check::<C>(&[(*pk, q)], proof)
+}
+
+pub(crate) fn sign<C: BlsCiphersuite>(sk: Fr, msg: &[u8]) -> C::Signature {
+    core_sign::<C>(sk, msg, C::SIG_DST)
+}
+
+pub(crate) fn verify<C: BlsCiphersuite>(pk: &C::PublicKey, msg: &[u8], sig: &C::Signature) -> bool {
+    aggregate_verify::<C, _>(&[*pk], &[msg], sig)
+}
+
+pub(crate) fn aggregate<C: BlsCiphersuite>(sigs: &[C::Signature]) -> C::Signature {
+    let mut agg = C::Signature::zero();
+    for s in sigs {
+        agg.add_assign(s);
+    }
+    agg
+}
+
+/// Verify an aggregate signature over distinct (public key, message) pairs.
+/// Callers must have verified a proof of possession for every public key
+pub(crate) fn aggregate_verify<C: BlsCiphersuite, M: AsRef<[u8]>>(
+    pks: &[C::PublicKey],
+    msgs: &[M],
+    sig: &C::Signature,
+) -> bool {
+    if pks.is_empty() || pks.len() != msgs.len() {
+        return false;
+    }
+    let pairs: Vec<(C::PublicKey, C::Signature)> = pks
+        .iter()
+        .zip(msgs.iter())
+        .map(|(pk, m)| (*pk, C::Signature::hash_to_curve(m.as_ref(), C::SIG_DST)))
+        .collect();
+    pairing_check::<C>(&pairs, sig)
+}
+
+/// Verify an aggregate signature where every signer signed the same message.
+/// Callers must have verified a proof of possession for every public key
+pub(crate) fn fast_aggregate_verify<C: BlsCiphersuite>(
+    pks: &[C::PublicKey],
+    msg: &[u8],
+    sig: &C::Signature,
+) -> bool {
+    if pks.is_empty() {
+        return false;
+    }
+    let mut agg = C::PublicKey::zero();
+    for pk in pks {
+        agg.add_assign(pk);
+    }
+    verify::<C>(&agg, msg, sig)
+}
@@ -0,0 +1,342 @@
+use crate::bls_ciphersuite::{
+    aggregate, aggregate_verify, fast_aggregate_verify, point_from_bytes, point_to_bytes,
+    secret_key_from_bytes, sign, verify, BlsCiphersuite, MinPkSize, MinSigSize,
+};
+use crate::last_error::ErrorSlot;
+use crate::{BbsFfiError, ByteArray};
+use bbs::{G1_COMPRESSED_SIZE, G2_COMPRESSED_SIZE};
+use ffi_support::{call_with_result, ByteBuffer, ConcurrentHandleMap, ErrorCode, ExternError};
+
+lazy_static! {
+    static ref BLS_AGGREGATE_CONTEXT: ConcurrentHandleMap<AggregateContext> =
+        ConcurrentHandleMap::new();
+    static ref BLS_AGGREGATE_VERIFY_CONTEXT: ConcurrentHandleMap<AggregateVerifyContext> =
+        ConcurrentHandleMap::new();
+}
+
+define_handle_deleter!(BLS_AGGREGATE_CONTEXT, free_bls_aggregate);
+define_handle_deleter!(BLS_AGGREGATE_VERIFY_CONTEXT, free_bls_aggregate_verify);
+
+struct AggregateContext {
+    signatures: Vec<Vec<u8>>,
+}
+
+struct AggregateVerifyContext {
+    public_keys: Vec<Vec<u8>>,
+    messages: Vec<Vec<u8>>,
+    signature: Option<Vec<u8>>,
+}
+
+fn parse_all<C: BlsCiphersuite>(public_keys: &[Vec<u8>]) -> Result<Vec<C::PublicKey>, BbsFfiError> {
+    public_keys
+        .iter()
+        .map(|pk| point_from_bytes(pk, C::PUBLIC_KEY_SIZE, "Public key"))
+        .collect()
+}
+
+fn sign_impl<C: BlsCiphersuite>(secret_key: &[u8], message: &[u8]) -> Result<Vec<u8>, BbsFfiError> {
+    let sk = secret_key_from_bytes(secret_key)?;
+    Ok(point_to_bytes(&sign::<C>(sk, message)))
+}
+
+fn verify_impl<C: BlsCiphersuite>(
+    public_key: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> Result<i32, BbsFfiError> {
+    let pk = point_from_bytes(public_key, C::PUBLIC_KEY_SIZE, "Public key")?;
+    let sig = point_from_bytes(signature, C::SIGNATURE_SIZE, "Signature")?;
+    if verify::<C>(&pk, message, &sig) {
+        Ok(0)
+    } else {
+        Err(BbsFfiError::new("Invalid signature"))
+    }
+}
+
+fn aggregate_impl<C: BlsCiphersuite>(signatures: &[Vec<u8>]) -> Result<Vec<u8>, BbsFfiError> {
+    let sigs = signatures
+        .iter()
+        .map(|s| point_from_bytes(s, C::SIGNATURE_SIZE, "Signature"))
+        .collect::<Result<Vec<C::Signature>, BbsFfiError>>()?;
+    Ok(point_to_bytes(&aggregate::<C>(&sigs)))
+}
+
+fn aggregate_verify_impl<C: BlsCiphersuite>(
+    ctx: &AggregateVerifyContext,
+    signature: &[u8],
+    fast: bool,
+) -> Result<bool, BbsFfiError> {
+    let pks = parse_all::<C>(&ctx.public_keys)?;
+    let sig = point_from_bytes(signature, C::SIGNATURE_SIZE, "Signature")?;
+    if fast {
+        Ok(fast_aggregate_verify::<C>(&pks, &ctx.messages[0], &sig))
+    } else {
+        Ok(aggregate_verify::<C, _>(&pks, &ctx.messages, &sig))
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn bls_signature_g1_size() -> i32 {
+    G1_COMPRESSED_SIZE as i32
+}
+
+#[no_mangle]
+pub extern "C" fn bls_signature_g2_size() -> i32 {
+    G2_COMPRESSED_SIZE as i32
+}
+
+/// Sign `message` with the secret key of a G2 public key.
+/// The signature is a G1 element (minimal-signature-size)
+#[no_mangle]
+pub extern "C" fn bls_sign_g2(
+    secret_key: ByteArray,
+    message: ByteArray,
+    signature: &mut ByteBuffer,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let res = call_with_result(err, || -> Result<ByteBuffer, BbsFfiError> {
+        let sig = sign_impl::<MinSigSize>(&secret_key.to_vec(), &message.to_vec())?;
+        Ok(ByteBuffer::from_vec(sig))
+    });
+    if err.get_code().is_success() {
+        *signature = res;
+    }
+    err.get_code().code()
+}
+
+/// Sign `message` with the secret key of a G1 public key.
+/// The signature is a G2 element (minimal-pubkey-size)
+#[no_mangle]
+pub extern "C" fn bls_sign_g1(
+    secret_key: ByteArray,
+    message: ByteArray,
+    signature: &mut ByteBuffer,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let res = call_with_result(err, || -> Result<ByteBuffer, BbsFfiError> {
+        let sig = sign_impl::<MinPkSize>(&secret_key.to_vec(), &message.to_vec())?;
+        Ok(ByteBuffer::from_vec(sig))
+    });
+    if err.get_code().is_success() {
+        *signature = res;
+    }
+    err.get_code().code()
+}
+
+/// Verify a G1 signature on `message` with a G2 public key.
+/// Returns 0 if the signature is valid
+#[no_mangle]
+pub extern "C" fn bls_verify_g2(
+    public_key: ByteArray,
+    message: ByteArray,
+    signature: ByteArray,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let _ = call_with_result(err, || -> Result<i32, BbsFfiError> {
+        verify_impl::<MinSigSize>(&public_key.to_vec(), &message.to_vec(), &signature.to_vec())
+    });
+    err.get_code().code()
+}
+
+/// Verify a G2 signature on `message` with a G1 public key.
+/// Returns 0 if the signature is valid
+#[no_mangle]
+pub extern "C" fn bls_verify_g1(
+    public_key: ByteArray,
+    message: ByteArray,
+    signature: ByteArray,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let _ = call_with_result(err, || -> Result<i32, BbsFfiError> {
+        verify_impl::<MinPkSize>(&public_key.to_vec(), &message.to_vec(), &signature.to_vec())
+    });
+    err.get_code().code()
+}
+
+#[no_mangle]
+pub extern "C" fn bls_aggregate_context_init(err: *mut ExternError) -> u64 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    BLS_AGGREGATE_CONTEXT.insert_with_output(err, || AggregateContext {
+        signatures: Vec::new(),
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn bls_aggregate_context_add_signature(
+    handle: u64,
+    signature: ByteArray,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let signature = signature.to_vec();
+    if signature.is_empty() {
+        *err = ExternError::new_error(ErrorCode::new(1), "Signature cannot be empty");
+        return 1;
+    }
+    BLS_AGGREGATE_CONTEXT.call_with_output_mut(err, handle, |ctx| {
+        ctx.signatures.push(signature);
+    });
+    err.get_code().code()
+}
+
+/// Aggregate all signatures in the context. The signatures must all
+/// be in the same group. The context is deleted even if aggregation fails
+#[no_mangle]
+pub extern "C" fn bls_aggregate_context_finish(
+    handle: u64,
+    aggregate: &mut ByteBuffer,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let res = BLS_AGGREGATE_CONTEXT.call_with_result(
+        err,
+        handle,
+        move |ctx| -> Result<ByteBuffer, BbsFfiError> {
+            if ctx.signatures.is_empty() {
+                return Err(BbsFfiError::new("Signatures cannot be empty"));
+            }
+            let sig = match ctx.signatures[0].len() {
+                G1_COMPRESSED_SIZE => aggregate_impl::<MinSigSize>(&ctx.signatures)?,
+                G2_COMPRESSED_SIZE => aggregate_impl::<MinPkSize>(&ctx.signatures)?,
+                l => return Err(BbsFfiError(format!("Invalid signature length {}", l))),
+            };
+            Ok(ByteBuffer::from_vec(sig))
+        },
+    );
+
+    // The context is used up whether or not aggregation succeeded
+    let removed = BLS_AGGREGATE_CONTEXT.remove_u64(handle);
+    if err.get_code().is_success() {
+        *aggregate = res;
+        if let Err(e) = removed {
+            *err = ExternError::new_error(ErrorCode::new(1), format!("{:?}", e))
+        }
+    }
+    err.get_code().code()
+}
+
+#[no_mangle]
+pub extern "C" fn bls_aggregate_verify_context_init(err: *mut ExternError) -> u64 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    BLS_AGGREGATE_VERIFY_CONTEXT.insert_with_output(err, || AggregateVerifyContext {
+        public_keys: Vec::new(),
+        messages: Vec::new(),
+        signature: None,
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn bls_aggregate_verify_context_add_public_key(
+    handle: u64,
+    public_key: ByteArray,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let public_key = public_key.to_vec();
+    if public_key.is_empty() {
+        *err = ExternError::new_error(ErrorCode::new(1), "Public key cannot be empty");
+        return 1;
+    }
+    BLS_AGGREGATE_VERIFY_CONTEXT.call_with_output_mut(err, handle, |ctx| {
+        ctx.public_keys.push(public_key);
+    });
+    err.get_code().code()
+}
+
+#[no_mangle]
+pub extern "C" fn bls_aggregate_verify_context_add_message(
+    handle: u64,
+    message: ByteArray,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let message = message.to_vec();
+    BLS_AGGREGATE_VERIFY_CONTEXT.call_with_output_mut(err, handle, |ctx| {
+        ctx.messages.push(message);
+    });
+    err.get_code().code()
+}
+
+#[no_mangle]
+pub extern "C" fn bls_aggregate_verify_context_set_signature(
+    handle: u64,
+    signature: ByteArray,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let signature = signature.to_vec();
+    if signature.is_empty() {
+        *err = ExternError::new_error(ErrorCode::new(1), "Signature cannot be empty");
+        return 1;
+    }
+    BLS_AGGREGATE_VERIFY_CONTEXT.call_with_output_mut(err, handle, |ctx| {
+        ctx.signature = Some(signature);
+    });
+    err.get_code().code()
+}
+
+fn aggregate_verify_finish(handle: u64, fast: bool, err: &mut ExternError) -> i32 {
+    let _ = BLS_AGGREGATE_VERIFY_CONTEXT.call_with_result(
+        err,
+        handle,
+        move |ctx| -> Result<i32, BbsFfiError> {
+            if ctx.public_keys.is_empty() {
+                return Err(BbsFfiError::new("Public keys cannot be empty"));
+            }
+            if fast && ctx.messages.len() != 1 {
+                return Err(BbsFfiError::new("Exactly one message must be set"));
+            }
+            if !fast && ctx.messages.len() != ctx.public_keys.len() {
+                return Err(BbsFfiError::new("Public keys and messages are not equal"));
+            }
+            let signature = match ctx.signature.as_ref() {
+                Some(s) => s,
+                None => return Err(BbsFfiError::new("Signature must be set")),
+            };
+            let valid = match ctx.public_keys[0].len() {
+                G2_COMPRESSED_SIZE => aggregate_verify_impl::<MinSigSize>(ctx, signature, fast)?,
+                G1_COMPRESSED_SIZE => aggregate_verify_impl::<MinPkSize>(ctx, signature, fast)?,
+                l => return Err(BbsFfiError(format!("Invalid public key length {}", l))),
+            };
+            if valid {
+                Ok(0)
+            } else {
+                Err(BbsFfiError::new("Invalid signature"))
+            }
+        },
+    );
+
+    // The context is used up whether or not the signature verified
+    let removed = BLS_AGGREGATE_VERIFY_CONTEXT.remove_u64(handle);
+    if err.get_code().is_success() {
+        if let Err(e) = removed {
+            *err = ExternError::from(e)
+        }
+    }
+    err.get_code().code()
+}
+
+/// Verify an aggregate signature where each public key signed the message
+/// added at the same position. Every public key must have a verified proof of possession.
+/// Returns 0 if the signature is valid. The context is deleted either way
+#[no_mangle]
+pub extern "C" fn bls_aggregate_verify_context_finish(handle: u64, err: *mut ExternError) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    aggregate_verify_finish(handle, false, err)
+}
+
+/// Verify an aggregate signature where every public key signed the single message
+/// in the context. Every public key must have a verified proof of possession.
+/// Returns 0 if the signature is valid. The context is deleted either way
+#[no_mangle]
+pub extern "C" fn bls_fast_aggregate_verify_context_finish(
+    handle: u64,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    aggregate_verify_finish(handle, true, err)
+}
@@ -0,0 +1,347 @@
+//! COSE encoding of signatures and proofs.
+//!
+//! A signature or proof is carried as the signature of a tagged `COSE_Sign1` with a detached
+//! payload. The protected header holds the algorithm (the ciphersuite id), a content type telling
+//! signatures and proofs apart, the issuer key id and, for proofs, the revealed message indices.
+//! BBS does not sign these headers, so decoding checks the algorithm and revealed indices against
+//! the signature or proof itself. The key id only tells the verifier which public key to use, and
+//! the messages are added to the verify contexts as usual.
+
+use crate::api::{Parse, Proof, Result, Signature};
+use crate::envelope::{ArtifactType, ENVELOPE_MARKER};
+use crate::last_error::ErrorSlot;
+use crate::version::BBS_CIPHERSUITE;
+use crate::{BbsFfiError, ByteArray};
+use ciborium::value::Value;
+use ffi_support::{ByteBuffer, ErrorCode, ExternError};
+use std::convert::TryFrom;
+
+/// The CBOR tag of a `COSE_Sign1`
+pub const COSE_SIGN1_TAG: u64 = 18;
+/// The private use header label for the revealed message indices of a proof
+pub const REVEALED_INDICES_LABEL: i64 = -65537;
+/// The content type of an encoded signature
+pub const SIGNATURE_CONTENT_TYPE: &str = "application/bbs-signature";
+/// The content type of an encoded proof
+pub const PROOF_CONTENT_TYPE: &str = "application/bbs-proof";
+
+const ALG_LABEL: i64 = 1;
+const CONTENT_TYPE_LABEL: i64 = 3;
+const KID_LABEL: i64 = 4;
+
+/// A decoded signature or proof with the issuer key id from its protected header
+#[derive(Debug)]
+pub struct Cose<T> {
+    pub kid: Vec<u8>,
+    pub value: T,
+}
+
+/// The fields of a `COSE_Sign1` before the signature or proof is parsed
+struct Sign1 {
+    kind: ArtifactType,
+    kid: Vec<u8>,
+    revealed: Option<Vec<usize>>,
+    value: Vec<u8>,
+}
+
+fn error(message: String) -> crate::api::Error {
+    BbsFfiError(message).into()
+}
+
+fn label(l: i64) -> Value {
+    Value::Integer(l.into())
+}
+
+fn to_cbor(value: &Value) -> Vec<u8> {
+    let mut data = Vec::new();
+    ciborium::ser::into_writer(value, &mut data).expect("writing to a Vec cannot fail");
+    data
+}
+
+fn from_cbor(data: &[u8], what: &str) -> Result<Value> {
+    let mut reader = data;
+    let value = ciborium::de::from_reader(&mut reader)
+        .map_err(|e| error(format!("Invalid COSE {}: {}", what, e)))?;
+    if !reader.is_empty() {
+        return Err(error(format!(
+            "Invalid COSE {}: {} trailing bytes",
+            what,
+            reader.len()
+        )));
+    }
+    Ok(value)
+}
+
+fn encode(sign1: Sign1) -> Vec<u8> {
+    let content_type = match sign1.kind {
+        ArtifactType::ProofArtifact => PROOF_CONTENT_TYPE,
+        _ => SIGNATURE_CONTENT_TYPE,
+    };
+    let mut protected = vec![
+        (label(ALG_LABEL), Value::Text(BBS_CIPHERSUITE.to_string())),
+        (
+            label(CONTENT_TYPE_LABEL),
+            Value::Text(content_type.to_string()),
+        ),
+        (label(KID_LABEL), Value::Bytes(sign1.kid)),
+    ];
+    if let Some(revealed) = sign1.revealed {
+        protected.push((
+            label(REVEALED_INDICES_LABEL),
+            Value::Array(
+                revealed
+                    .into_iter()
+                    .map(|i| Value::Integer((i as u64).into()))
+                    .collect(),
+            ),
+        ));
+    }
+    to_cbor(&Value::Tag(
+        COSE_SIGN1_TAG,
+        Box::new(Value::Array(vec![
+            Value::Bytes(to_cbor(&Value::Map(protected))),
+            Value::Map(Vec::new()),
+            Value::Null,
+            Value::Bytes(sign1.value),
+        ])),
+    ))
+}
+
+fn header(protected: &[(Value, Value)], l: i64) -> Option<&Value> {
+    protected
+        .iter()
+        .find(|(k, _)| matches!(k, Value::Integer(i) if i128::from(*i) == l as i128))
+        .map(|(_, v)| v)
+}
+
+fn read(data: &[u8]) -> Result<Sign1> {
+    let fields = match from_cbor(data, "structure")? {
+        Value::Tag(COSE_SIGN1_TAG, inner) => *inner,
+        Value::Tag(tag, _) => {
+            return Err(error(format!(
+                "Expected COSE_Sign1 tag {}, found {}",
+                COSE_SIGN1_TAG, tag
+            )))
+        }
+        untagged => untagged,
+    };
+    let (protected, payload, value) = match fields {
+        Value::Array(f) if f.len() == 4 => {
+            let mut f = f.into_iter();
+            let protected = f.next().unwrap();
+            let _unprotected = f.next();
+            (protected, f.next().unwrap(), f.next().unwrap())
+        }
+        _ => return Err(error("COSE_Sign1 must be an array of 4 items".to_string())),
+    };
+    if payload != Value::Null {
+        return Err(error("COSE payload must be detached".to_string()));
+    }
+    let value = match value {
+        Value::Bytes(v) if v.starts_with(&ENVELOPE_MARKER) => {
+            return Err(error(
+                "COSE signature must hold raw bytes, not an envelope".to_string(),
+            ))
+        }
+        Value::Bytes(v) => v,
+        _ => return Err(error("COSE signature must be a byte string".to_string())),
+    };
+    let protected = match protected {
+        Value::Bytes(p) => match from_cbor(&p, "protected header")? {
+            Value::Map(m) => m,
+            _ => return Err(error("COSE protected header must be a map".to_string())),
+        },
+        _ => {
+            return Err(error(
+                "COSE protected header must be a byte string".to_string(),
+            ))
+        }
+    };
+
+    match header(&protected, ALG_LABEL) {
+        Some(Value::Text(alg)) if alg == BBS_CIPHERSUITE => {}
+        Some(Value::Text(alg)) => {
+            return Err(error(format!(
+                "COSE algorithm is {}, expected {}",
+                alg, BBS_CIPHERSUITE
+            )))
+        }
+        _ => return Err(error("COSE algorithm must be set".to_string())),
+    }
+    let kind = match header(&protected, CONTENT_TYPE_LABEL) {
+        Some(Value::Text(t)) if t == SIGNATURE_CONTENT_TYPE => ArtifactType::SignatureArtifact,
+        Some(Value::Text(t)) if t == PROOF_CONTENT_TYPE => ArtifactType::ProofArtifact,
+        Some(Value::Text(t)) => return Err(error(format!("Unknown COSE content type {}", t))),
+        _ => return Err(error("COSE content type must be set".to_string())),
+    };
+    let kid = match header(&protected, KID_LABEL) {
+        Some(Value::Bytes(kid)) => kid.clone(),
+        Some(_) => return Err(error("COSE key id must be a byte string".to_string())),
+        None => Vec::new(),
+    };
+    let revealed = match header(&protected, REVEALED_INDICES_LABEL) {
+        None => None,
+        Some(Value::Array(indices)) => Some(
+            indices
+                .iter()
+                .map(|i| match i {
+                    Value::Integer(i) => usize::try_from(i128::from(*i)).ok(),
+                    _ => None,
+                })
+                .collect::<Option<Vec<usize>>>()
+                .ok_or_else(|| {
+                    error("COSE revealed indices must be unsigned integers".to_string())
+                })?,
+        ),
+        Some(_) => return Err(error("COSE revealed indices must be an array".to_string())),
+    };
+    Ok(Sign1 {
+        kind,
+        kid,
+        revealed,
+        value,
+    })
+}
+
+fn expect(sign1: &Sign1, kind: ArtifactType) -> Result<()> {
+    if sign1.kind == kind {
+        Ok(())
+    } else {
+        Err(error(format!(
+            "Expected a COSE {}, found a {}",
+            kind.name(),
+            sign1.kind.name()
+        )))
+    }
+}
+
+/// Encode `signature` with the issuer key id `kid`
+pub fn encode_signature(signature: &Signature, kid: &[u8]) -> Vec<u8> {
+    encode(Sign1 {
+        kind: ArtifactType::SignatureArtifact,
+        kid: kid.to_vec(),
+        revealed: None,
+        value: signature.to_bytes_compressed_form().to_vec(),
+    })
+}
+
+/// Encode `proof` with the issuer key id `kid` and the indices it reveals
+pub fn encode_proof(proof: &Proof, kid: &[u8]) -> Vec<u8> {
+    encode(Sign1 {
+        kind: ArtifactType::ProofArtifact,
+        kid: kid.to_vec(),
+        revealed: Some(proof.revealed().into_iter().collect()),
+        value: proof.to_bytes(),
+    })
+}
+
+fn signature(sign1: Sign1) -> Result<Cose<Signature>> {
+    expect(&sign1, ArtifactType::SignatureArtifact)?;
+    if sign1.revealed.is_some() {
+        return Err(error(
+            "A COSE signature cannot have revealed indices".to_string(),
+        ));
+    }
+    Ok(Cose {
+        value: Signature::parse(&sign1.value)?,
+        kid: sign1.kid,
+    })
+}
+
+fn proof(sign1: Sign1) -> Result<Cose<Proof>> {
+    expect(&sign1, ArtifactType::ProofArtifact)?;
+    let header = sign1
+        .revealed
+        .ok_or_else(|| error("COSE proof must have revealed indices".to_string()))?;
+    let proof = Proof::parse(&sign1.value)?;
+    let revealed: Vec<usize> = proof.revealed().into_iter().collect();
+    if header != revealed {
+        return Err(error(format!(
+            "COSE header reveals indices {:?} but the proof reveals {:?}",
+            header, revealed
+        )));
+    }
+    Ok(Cose {
+        value: proof,
+        kid: sign1.kid,
+    })
+}
+
+/// Decode a signature encoded by `encode_signature`
+pub fn decode_signature(data: &[u8]) -> Result<Cose<Signature>> {
+    signature(read(data)?)
+}
+
+/// Decode a proof encoded by `encode_proof`, checking the revealed indices
+/// in the header match the proof
+pub fn decode_proof(data: &[u8]) -> Result<Cose<Proof>> {
+    proof(read(data)?)
+}
+
+/// Encode `value`, a signature or proof, with the issuer key id `kid`
+#[no_mangle]
+pub extern "C" fn bbs_cose_encode(
+    kind: ArtifactType,
+    value: ByteArray,
+    kid: ByteArray,
+    cose: &mut ByteBuffer,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let value = value.to_vec();
+    let kid = kid.to_vec();
+    let res = match kind {
+        ArtifactType::SignatureArtifact => {
+            Signature::parse(&value).map(|s| encode_signature(&s, &kid))
+        }
+        ArtifactType::ProofArtifact => Proof::parse(&value).map(|p| encode_proof(&p, &kid)),
+        _ => Err(error(format!(
+            "Only signatures and proofs can be COSE encoded, not a {}",
+            kind.name()
+        ))),
+    };
+    match res {
+        Ok(data) => {
+            *cose = ByteBuffer::from_vec(data);
+            *err = ExternError::success();
+            0
+        }
+        Err(e) => {
+            *err = ExternError::new_error(ErrorCode::new(1), e.message());
+            1
+        }
+    }
+}
+
+/// The type, issuer key id and raw bytes of the signature or proof in `cose`
+#[no_mangle]
+pub extern "C" fn bbs_cose_decode(
+    cose: ByteArray,
+    kind: &mut ArtifactType,
+    kid: &mut ByteBuffer,
+    value: &mut ByteBuffer,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let res = read(&cose.to_vec()).and_then(|sign1| match sign1.kind {
+        ArtifactType::ProofArtifact => {
+            proof(sign1).map(|c| (ArtifactType::ProofArtifact, c.kid, c.value.to_bytes()))
+        }
+        kind => {
+            signature(sign1).map(|c| (kind, c.kid, c.value.to_bytes_compressed_form().to_vec()))
+        }
+    });
+    match res {
+        Ok((found, k, raw)) => {
+            *kind = found;
+            *kid = ByteBuffer::from_vec(k);
+            *value = ByteBuffer::from_vec(raw);
+            *err = ExternError::success();
+            0
+        }
+        Err(e) => {
+            *err = ExternError::new_error(ErrorCode::new(1), e.message());
+            1
+        }
+    }
+}
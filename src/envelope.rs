@@ -0,0 +1,208 @@
+//! Optional self-describing wrapper for serialized artifacts.
+//!
+//! An envelope is the marker `0xFF "BBS"`, a type tag, the format version of the payload,
+//! the ciphersuite id as a length-prefixed ASCII string, then the raw bytes. No raw key,
+//! signature or commitment starts with `0xFF` since it is not a valid compressed point, and a
+//! raw proof would need 65346 messages to start with the marker, so `Parse` accepts either
+//! form and rejects envelopes for another type, version or ciphersuite.
+
+use crate::api::{
+    BlindSignature, BlindSignatureContext, Commitment, Parse, Proof, PublicKey, Result, Signature,
+    ToVariableLengthBytes,
+};
+use crate::last_error::ErrorSlot;
+use crate::version::{BBS_CIPHERSUITE, BBS_PROOF_FORMAT_VERSION};
+use crate::{BbsFfiError, ByteArray};
+use ffi_support::{ByteBuffer, ErrorCode, ExternError};
+
+/// The bytes every envelope starts with
+pub const ENVELOPE_MARKER: [u8; 4] = [0xFF, b'B', b'B', b'S'];
+
+/// The kind of artifact held by an envelope
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ArtifactType {
+    SignatureArtifact = 1,
+    BlindSignatureArtifact = 2,
+    ProofArtifact = 3,
+    CommitmentArtifact = 4,
+    BlindSignatureContextArtifact = 5,
+    PublicKeyArtifact = 6,
+}
+
+impl ArtifactType {
+    fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            1 => Some(ArtifactType::SignatureArtifact),
+            2 => Some(ArtifactType::BlindSignatureArtifact),
+            3 => Some(ArtifactType::ProofArtifact),
+            4 => Some(ArtifactType::CommitmentArtifact),
+            5 => Some(ArtifactType::BlindSignatureContextArtifact),
+            6 => Some(ArtifactType::PublicKeyArtifact),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            ArtifactType::SignatureArtifact => "signature",
+            ArtifactType::BlindSignatureArtifact => "blind signature",
+            ArtifactType::ProofArtifact => "proof",
+            ArtifactType::CommitmentArtifact => "commitment",
+            ArtifactType::BlindSignatureContextArtifact => "commitment context",
+            ArtifactType::PublicKeyArtifact => "public key",
+        }
+    }
+
+    /// The payload format written by this library
+    pub fn format_version(self) -> u8 {
+        match self {
+            ArtifactType::ProofArtifact => BBS_PROOF_FORMAT_VERSION as u8,
+            _ => 1,
+        }
+    }
+}
+
+/// Wrap the raw bytes of an artifact of type `kind`
+pub fn seal(kind: ArtifactType, raw: &[u8]) -> Vec<u8> {
+    let mut data = ENVELOPE_MARKER.to_vec();
+    data.push(kind as u8);
+    data.push(kind.format_version());
+    data.push(BBS_CIPHERSUITE.len() as u8);
+    data.extend_from_slice(BBS_CIPHERSUITE.as_bytes());
+    data.extend_from_slice(raw);
+    data
+}
+
+/// The type and raw bytes of `data` if it is an envelope, `None` for raw bytes.
+/// The format version and ciphersuite must be ones this library writes
+pub fn unseal(data: &[u8]) -> Result<Option<(ArtifactType, &[u8])>> {
+    if !data.starts_with(&ENVELOPE_MARKER) {
+        return Ok(None);
+    }
+    let header = ENVELOPE_MARKER.len();
+    if data.len() < header + 3 {
+        return Err(BbsFfiError(format!(
+            "Envelope must be at least {} bytes, found {}",
+            header + 3,
+            data.len()
+        ))
+        .into());
+    }
+    let kind = ArtifactType::from_tag(data[header])
+        .ok_or_else(|| BbsFfiError(format!("Unknown envelope type {}", data[header])))?;
+    let version = data[header + 1];
+    if version != kind.format_version() {
+        return Err(BbsFfiError(format!(
+            "Unsupported {} format version {}, expected {}",
+            kind.name(),
+            version,
+            kind.format_version()
+        ))
+        .into());
+    }
+    let start = header + 3;
+    let end = start + data[header + 2] as usize;
+    let ciphersuite = data.get(start..end).ok_or_else(|| {
+        BbsFfiError(format!(
+            "Envelope ciphersuite id is {} bytes but only {} remain",
+            end - start,
+            data.len() - start
+        ))
+    })?;
+    if ciphersuite != BBS_CIPHERSUITE.as_bytes() {
+        return Err(BbsFfiError(format!(
+            "The {} is for ciphersuite {}, expected {}",
+            kind.name(),
+            String::from_utf8_lossy(ciphersuite),
+            BBS_CIPHERSUITE
+        ))
+        .into());
+    }
+    Ok(Some((kind, &data[end..])))
+}
+
+/// The raw bytes of `data` if it is raw or an envelope holding `kind`
+pub fn open(kind: ArtifactType, data: &[u8]) -> Result<&[u8]> {
+    match unseal(data)? {
+        None => Ok(data),
+        Some((found, raw)) if found == kind => Ok(raw),
+        Some((found, _)) => Err(BbsFfiError(format!(
+            "Expected a {} envelope, found a {}",
+            kind.name(),
+            found.name()
+        ))
+        .into()),
+    }
+}
+
+/// Parse `data` as `kind`, raw or enveloped, and return its canonical raw bytes
+fn canonical(kind: ArtifactType, data: &[u8]) -> Result<Vec<u8>> {
+    Ok(match kind {
+        ArtifactType::SignatureArtifact => {
+            Signature::parse(data)?.to_bytes_compressed_form().to_vec()
+        }
+        ArtifactType::BlindSignatureArtifact => BlindSignature::parse(data)?
+            .to_bytes_compressed_form()
+            .to_vec(),
+        ArtifactType::ProofArtifact => Proof::parse(data)?.to_bytes(),
+        ArtifactType::CommitmentArtifact => {
+            Commitment::parse(data)?.to_bytes_compressed_form().to_vec()
+        }
+        ArtifactType::BlindSignatureContextArtifact => {
+            BlindSignatureContext::parse(data)?.to_bytes_compressed_form()
+        }
+        ArtifactType::PublicKeyArtifact => PublicKey::parse(data)?.to_bytes_compressed_form(),
+    })
+}
+
+/// Wrap `value`, an artifact of type `kind`, in an envelope.
+/// `value` is checked to be a valid `kind` first
+#[no_mangle]
+pub extern "C" fn bbs_envelope_seal(
+    kind: ArtifactType,
+    value: ByteArray,
+    envelope: &mut ByteBuffer,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    match canonical(kind, &value.to_vec()) {
+        Ok(raw) => {
+            *envelope = ByteBuffer::from_vec(seal(kind, &raw));
+            *err = ExternError::success();
+            0
+        }
+        Err(e) => {
+            *err = ExternError::new_error(ErrorCode::new(1), e.message());
+            1
+        }
+    }
+}
+
+/// The type and raw bytes of `envelope`
+#[no_mangle]
+pub extern "C" fn bbs_envelope_open(
+    envelope: ByteArray,
+    kind: &mut ArtifactType,
+    value: &mut ByteBuffer,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let data = envelope.to_vec();
+    let res = unseal(&data).and_then(|opened| {
+        let (found, _) = opened.ok_or_else(|| BbsFfiError::new("Not an envelope"))?;
+        Ok((found, canonical(found, &data)?))
+    });
+    match res {
+        Ok((found, raw)) => {
+            *kind = found;
+            *value = ByteBuffer::from_vec(raw);
+            *err = ExternError::success();
+            0
+        }
+        Err(e) => {
+            *err = ExternError::new_error(ErrorCode::new(1), e.message());
+            1
+        }
+    }
+}
@@ -10,68 +10,176 @@ use jni::objects::{JObject, JString};
 // This is just a pointer. We'll be returning it from our function.
 // We can't return one of the objects with lifetime information because the
 // lifetime checker won't let us.
-use jni::sys::{jbyte, jbyteArray, jint, jlong};
+use jni::sys::{jbyte, jbyteArray, jint, jintArray, jlong, jstring};
 
 use crate::bbs_blind_commitment::{
     bbs_blind_commitment_context_add_message_bytes,
-    bbs_blind_commitment_context_add_message_prehashed, bbs_blind_commitment_context_finish,
+    bbs_blind_commitment_context_add_message_prehashed,
+    bbs_blind_commitment_context_add_message_string, bbs_blind_commitment_context_finish,
     bbs_blind_commitment_context_init, bbs_blind_commitment_context_set_nonce_bytes,
-    bbs_blind_commitment_context_set_public_key, bbs_blind_signature_size,
+    bbs_blind_commitment_context_set_nonce_prehashed,
+    bbs_blind_commitment_context_set_nonce_string, bbs_blind_commitment_context_set_public_key,
+    bbs_blind_signature_size,
 };
 use crate::bbs_blind_sign::{
     bbs_blind_sign_context_add_message_bytes, bbs_blind_sign_context_add_message_prehashed,
-    bbs_blind_sign_context_finish, bbs_blind_sign_context_init,
-    bbs_blind_sign_context_set_commitment, bbs_blind_sign_context_set_public_key,
-    bbs_blind_sign_context_set_secret_key, bbs_blinding_factor_size, bbs_unblind_signature,
+    bbs_blind_sign_context_add_message_string, bbs_blind_sign_context_finish,
+    bbs_blind_sign_context_init, bbs_blind_sign_context_set_commitment,
+    bbs_blind_sign_context_set_public_key, bbs_blind_sign_context_set_secret_key,
+    bbs_blinding_factor_size, bbs_unblind_signature,
 };
 use crate::bbs_create_proof::{
-    CREATE_PROOF_CONTEXT,
-    bbs_create_proof_context_add_proof_message_bytes, bbs_create_proof_context_finish,
+    bbs_create_proof_context_add_proof_message_bytes,
+    bbs_create_proof_context_add_proof_message_prehashed,
+    bbs_create_proof_context_add_proof_message_string, bbs_create_proof_context_finish,
     bbs_create_proof_context_init, bbs_create_proof_context_set_nonce_bytes,
-    bbs_create_proof_context_set_signature,
+    bbs_create_proof_context_set_nonce_prehashed, bbs_create_proof_context_set_nonce_string,
+    bbs_create_proof_context_set_public_key, bbs_create_proof_context_set_signature,
     bbs_create_proof_context_size,
 };
 use crate::bbs_sign::*;
 use crate::bbs_verify_proof::{
-    VERIFY_PROOF_CONTEXT,
-    bbs_verify_proof_context_add_message_bytes, bbs_verify_proof_context_add_message_prehashed,
-    bbs_verify_proof_context_finish, bbs_verify_proof_context_init,
-    bbs_verify_proof_context_set_nonce_bytes, bbs_verify_proof_context_set_proof,
+    bbs_proof_inspect, bbs_verify_proof_context_add_message_bytes,
+    bbs_verify_proof_context_add_message_prehashed, bbs_verify_proof_context_add_message_string,
+    bbs_verify_proof_context_add_revealed_message, bbs_verify_proof_context_finish,
+    bbs_verify_proof_context_init, bbs_verify_proof_context_set_nonce_bytes,
+    bbs_verify_proof_context_set_nonce_prehashed, bbs_verify_proof_context_set_nonce_string,
+    bbs_verify_proof_context_set_proof, bbs_verify_proof_context_set_public_key,
+};
+use crate::bbs_verify_sign_proof::{
+    bbs_verify_blind_commitment_context_add_blinded, bbs_verify_blind_commitment_context_finish,
+    bbs_verify_blind_commitment_context_init, bbs_verify_blind_commitment_context_set_nonce_bytes,
+    bbs_verify_blind_commitment_context_set_nonce_prehashed,
+    bbs_verify_blind_commitment_context_set_nonce_string,
+    bbs_verify_blind_commitment_context_set_proof,
+    bbs_verify_blind_commitment_context_set_public_key,
 };
 use crate::bls::{bls_public_key_g1_size, bls_public_key_g2_size, bls_secret_key_size};
+use crate::envelope::{self, ArtifactType};
+use crate::rng::catch_rng_failure;
 use crate::*;
 use crate::{
     bls_generate_blinded_g1_key, bls_generate_blinded_g2_key, bls_generate_g1_key,
     bls_generate_g2_key,
 };
-use bbs::keys::{DeterministicPublicKey, KeyGenOption, SecretKey, DETERMINISTIC_PUBLIC_KEY_COMPRESSED_SIZE, PublicKey};
-use bbs::{ToVariableLengthBytes, FR_COMPRESSED_SIZE, G1_COMPRESSED_SIZE};
+use bbs::keys::{
+    DeterministicPublicKey, KeyGenOption, SecretKey, DETERMINISTIC_PUBLIC_KEY_COMPRESSED_SIZE,
+};
+use bbs::{ToVariableLengthBytes, FR_COMPRESSED_SIZE};
+use ffi_support::FfiStr;
+use std::{ffi::CString, ptr};
+
+/// The Java exception thrown for a failed call, by the kind of input that was rejected
+#[derive(Copy, Clone)]
+enum BbsErrorKind {
+    General,
+    InvalidKey,
+    InvalidSignature,
+    ProofVerification,
+}
+
+impl BbsErrorKind {
+    fn class(self) -> &'static str {
+        match self {
+            BbsErrorKind::General => "bbs/signatures/BbsException",
+            BbsErrorKind::InvalidKey => "bbs/signatures/BbsInvalidKeyException",
+            BbsErrorKind::InvalidSignature => "bbs/signatures/BbsInvalidSignatureException",
+            BbsErrorKind::ProofVerification => "bbs/signatures/BbsProofVerificationException",
+        }
+    }
+}
+
+/// Throw `kind` with `message`, replacing the exception left pending by a failed JNI call
+fn throw(env: &JNIEnv, kind: BbsErrorKind, message: &str) {
+    if env.exception_check().unwrap_or(false) {
+        let _ = env.exception_clear();
+    }
+    let _ = env.throw_new(kind.class(), message);
+}
 
-use std::cell::RefCell;
+/// Throw `kind` if `error` is set, the message is `context` followed by the library error.
+/// Returns the error code
+fn check(env: &JNIEnv, kind: BbsErrorKind, context: &str, error: ExternError) -> jint {
+    let code = error.get_code().code();
+    if code != 0 {
+        let message = error.get_message();
+        throw(env, kind, &format!("{}: {}", context, message.as_str()));
+    }
+    unsafe { error.manually_release() };
+    code
+}
 
-thread_local! {
-    #[allow(clippy::missing_const_for_thread_local)]
-    static LAST_ERROR: RefCell<Option<String>> = RefCell::new(None);
+fn read_bytes(
+    env: &JNIEnv,
+    kind: BbsErrorKind,
+    context: &str,
+    array: jbyteArray,
+) -> Result<Vec<u8>, jint> {
+    env.convert_byte_array(array).map_err(|e| {
+        throw(env, kind, &format!("{}: {}", context, e));
+        1
+    })
 }
 
-fn update_last_error(m: &str) {
-    LAST_ERROR.with(|prev| {
-        *prev.borrow_mut() = Some(m.to_string());
+fn read_string(
+    env: &JNIEnv,
+    kind: BbsErrorKind,
+    context: &str,
+    s: JString,
+) -> Result<CString, jint> {
+    let s: String = match env.get_string(s) {
+        Err(e) => {
+            throw(env, kind, &format!("{}: {}", context, e));
+            return Err(1);
+        }
+        Ok(s) => s.into(),
+    };
+    CString::new(s).map_err(|e| {
+        throw(env, kind, &format!("{}: {}", context, e));
+        1
     })
 }
 
+fn to_jbytes(bytes: &[u8]) -> Vec<jbyte> {
+    bytes.iter().map(|b| *b as jbyte).collect()
+}
+
+/// A new Java array holding `bytes`, null when it cannot be allocated
+fn new_byte_array(env: &JNIEnv, context: &str, bytes: &[u8]) -> jbyteArray {
+    env.byte_array_from_slice(bytes).unwrap_or_else(|e| {
+        throw(env, BbsErrorKind::General, &format!("{}: {}", context, e));
+        ptr::null_mut()
+    })
+}
+
+fn new_string(env: &JNIEnv, s: &str) -> jstring {
+    env.new_string(s)
+        .map(|s| s.into_inner())
+        .unwrap_or(ptr::null_mut())
+}
+
 #[allow(non_snake_case)]
 #[no_mangle]
-pub extern "C" fn Java_bbs_signatures_Bbs_get_1last_1error<'a>(env: JNIEnv<'a>, _: JObject) -> JString<'a> {
-    let mut res = env.new_string("").unwrap();
-    LAST_ERROR.with(|prev| {
-        #[allow(clippy::single_match)]
-        match &*prev.borrow() {
-            Some(s) => res = env.new_string(s).unwrap(),
-            None => ()
-        };
-    });
-    res
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1library_1version(
+    env: JNIEnv,
+    _: JObject,
+) -> jstring {
+    new_string(&env, crate::version::LIBRARY_VERSION)
+}
+
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1abi_1version(_: JNIEnv, _: JObject) -> jint {
+    crate::version::bbs_abi_version()
+}
+
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1supported_1features(
+    env: JNIEnv,
+    _: JObject,
+) -> jstring {
+    new_string(&env, &crate::version::supported_features().to_string())
 }
 
 #[allow(non_snake_case)]
@@ -128,16 +236,29 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bls_1generate_1g1_1key(
     public_key: jbyteArray,
     secret_key: jbyteArray,
 ) -> jint {
-    let ikm = match env.convert_byte_array(seed) {
-        Err(_) => return 1,
+    let ikm = match read_bytes(
+        &env,
+        BbsErrorKind::InvalidKey,
+        "Unable to generate keys",
+        seed,
+    ) {
+        Err(e) => return e,
         Ok(s) => s,
     };
     let s = if ikm.is_empty() { None } else { Some(ikm) };
-    let (pk_bytes, sk_bytes) = bls_generate_g1_key(s);
-    let pk: Vec<i8> = pk_bytes.iter().map(|b| *b as jbyte).collect();
-    let sk: Vec<i8> = sk_bytes.iter().map(|b| *b as jbyte).collect();
-    copy_to_jni!(env, public_key, pk.as_slice());
-    copy_to_jni!(env, secret_key, sk.as_slice());
+    let (pk_bytes, sk_bytes) = match catch_rng_failure(|| bls_generate_g1_key(s)) {
+        Err(e) => {
+            throw(
+                &env,
+                BbsErrorKind::InvalidKey,
+                &format!("Unable to generate keys: {}", e.0),
+            );
+            return 1;
+        }
+        Ok(k) => k,
+    };
+    copy_to_jni!(env, public_key, to_jbytes(&pk_bytes).as_slice());
+    copy_to_jni!(env, secret_key, to_jbytes(&sk_bytes).as_slice());
     0
 }
 
@@ -150,16 +271,29 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bls_1generate_1g2_1key(
     public_key: jbyteArray,
     secret_key: jbyteArray,
 ) -> jint {
-    let ikm = match env.convert_byte_array(seed) {
-        Err(_) => return 1,
+    let ikm = match read_bytes(
+        &env,
+        BbsErrorKind::InvalidKey,
+        "Unable to generate keys",
+        seed,
+    ) {
+        Err(e) => return e,
         Ok(s) => s,
     };
     let s = if ikm.is_empty() { None } else { Some(ikm) };
-    let (pk_bytes, sk_bytes) = bls_generate_g2_key(s);
-    let pk: Vec<i8> = pk_bytes.iter().map(|b| *b as jbyte).collect();
-    let sk: Vec<i8> = sk_bytes.iter().map(|b| *b as jbyte).collect();
-    copy_to_jni!(env, public_key, pk.as_slice());
-    copy_to_jni!(env, secret_key, sk.as_slice());
+    let (pk_bytes, sk_bytes) = match catch_rng_failure(|| bls_generate_g2_key(s)) {
+        Err(e) => {
+            throw(
+                &env,
+                BbsErrorKind::InvalidKey,
+                &format!("Unable to generate keys: {}", e.0),
+            );
+            return 1;
+        }
+        Ok(k) => k,
+    };
+    copy_to_jni!(env, public_key, to_jbytes(&pk_bytes).as_slice());
+    copy_to_jni!(env, secret_key, to_jbytes(&sk_bytes).as_slice());
     0
 }
 
@@ -173,18 +307,30 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bls_1generate_1blinded_1g1_1key(
     public_key: jbyteArray,
     secret_key: jbyteArray,
 ) -> jint {
-    let ikm =match env.convert_byte_array(seed) {
-        Err(_) => return 1,
+    let ikm = match read_bytes(
+        &env,
+        BbsErrorKind::InvalidKey,
+        "Unable to generate keys",
+        seed,
+    ) {
+        Err(e) => return e,
         Ok(s) => s,
     };
     let s = if ikm.is_empty() { None } else { Some(ikm) };
-    let (r_bytes, pk_bytes, sk_bytes) = bls_generate_blinded_g1_key(s);
-    let pk: Vec<i8> = pk_bytes.iter().map(|b| *b as jbyte).collect();
-    let sk: Vec<i8> = sk_bytes.iter().map(|b| *b as jbyte).collect();
-    let r: Vec<i8> = r_bytes.iter().map(|b| *b as jbyte).collect();
-    copy_to_jni!(env, public_key, pk.as_slice());
-    copy_to_jni!(env, secret_key, sk.as_slice());
-    copy_to_jni!(env, bf, r.as_slice());
+    let (r_bytes, pk_bytes, sk_bytes) = match catch_rng_failure(|| bls_generate_blinded_g1_key(s)) {
+        Err(e) => {
+            throw(
+                &env,
+                BbsErrorKind::InvalidKey,
+                &format!("Unable to generate keys: {}", e.0),
+            );
+            return 1;
+        }
+        Ok(k) => k,
+    };
+    copy_to_jni!(env, public_key, to_jbytes(&pk_bytes).as_slice());
+    copy_to_jni!(env, secret_key, to_jbytes(&sk_bytes).as_slice());
+    copy_to_jni!(env, bf, to_jbytes(&r_bytes).as_slice());
     0
 }
 
@@ -198,18 +344,30 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bls_1generate_1blinded_1g2_1key(
     public_key: jbyteArray,
     secret_key: jbyteArray,
 ) -> jint {
-    let ikm = match env.convert_byte_array(seed) {
-        Err(_) => return 1,
+    let ikm = match read_bytes(
+        &env,
+        BbsErrorKind::InvalidKey,
+        "Unable to generate keys",
+        seed,
+    ) {
+        Err(e) => return e,
         Ok(s) => s,
     };
     let s = if ikm.is_empty() { None } else { Some(ikm) };
-    let (r_bytes, pk_bytes, sk_bytes) = bls_generate_blinded_g2_key(s);
-    let pk: Vec<i8> = pk_bytes.iter().map(|b| *b as jbyte).collect();
-    let sk: Vec<i8> = sk_bytes.iter().map(|b| *b as jbyte).collect();
-    let r: Vec<i8> = r_bytes.iter().map(|b| *b as jbyte).collect();
-    copy_to_jni!(env, public_key, pk.as_slice());
-    copy_to_jni!(env, secret_key, sk.as_slice());
-    copy_to_jni!(env, bf, r.as_slice());
+    let (r_bytes, pk_bytes, sk_bytes) = match catch_rng_failure(|| bls_generate_blinded_g2_key(s)) {
+        Err(e) => {
+            throw(
+                &env,
+                BbsErrorKind::InvalidKey,
+                &format!("Unable to generate keys: {}", e.0),
+            );
+            return 1;
+        }
+        Ok(k) => k,
+    };
+    copy_to_jni!(env, public_key, to_jbytes(&pk_bytes).as_slice());
+    copy_to_jni!(env, secret_key, to_jbytes(&sk_bytes).as_slice());
+    copy_to_jni!(env, bf, to_jbytes(&r_bytes).as_slice());
     0
 }
 
@@ -221,30 +379,12 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bls_1secret_1key_1to_1bbs_1key(
     secret_key: jbyteArray,
     message_count: jint,
 ) -> jbyteArray {
-    let bad_res = env.new_byte_array(0).unwrap();
-    let sk = get_secret_key(&env, secret_key);
-    if sk.is_err() {
-        return bad_res;
-    }
-    let sk = sk.unwrap();
-    let (dpk, _) = DeterministicPublicKey::new(Some(KeyGenOption::FromSecretKey(sk)));
-    let pk = match dpk.to_public_key(message_count as usize) {
-        Err(_) => return bad_res,
-        Ok(p) => p,
+    let sk = match get_secret_key(&env, secret_key) {
+        Err(_) => return ptr::null_mut(),
+        Ok(sk) => sk,
     };
-    if pk.validate().is_err() {
-        return bad_res;
-    }
-
-    let pk_bytes = pk.to_bytes_compressed_form();
-    match env.new_byte_array(pk_bytes.len() as jint) {
-        Err(_) => bad_res,
-        Ok(out) => {
-            let pp: Vec<jbyte> = pk_bytes.iter().map(|b| *b as jbyte).collect();
-            copy_to_jni!(env, out, pp.as_slice(), bad_res);
-            out
-        }
-    }
+    let (dpk, _) = DeterministicPublicKey::new(Some(KeyGenOption::FromSecretKey(sk)));
+    to_bbs_key(&env, dpk, message_count)
 }
 
 #[allow(non_snake_case)]
@@ -255,44 +395,66 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bls_1public_1key_1to_1bbs_1key(
     short_public_key: jbyteArray,
     message_count: jint,
 ) -> jbyteArray {
-    let bad_res = env.new_byte_array(0).unwrap();
-    let dpk = match env.convert_byte_array(short_public_key) {
-        Err(_) => return bad_res,
-        Ok(s) => {
-            if s.len() != DETERMINISTIC_PUBLIC_KEY_COMPRESSED_SIZE {
-                return bad_res;
-            }
-            DeterministicPublicKey::from(*array_ref![
-                s,
-                0,
-                DETERMINISTIC_PUBLIC_KEY_COMPRESSED_SIZE
-            ])
-        }
-    };
-    let pk = match dpk.to_public_key(message_count as usize) {
-        Err(_) => return bad_res,
-        Ok(p) => p,
+    const CONTEXT: &str = "Unable to convert public key";
+    let s = match read_bytes(&env, BbsErrorKind::InvalidKey, CONTEXT, short_public_key) {
+        Err(_) => return ptr::null_mut(),
+        Ok(s) => s,
     };
-    if pk.validate().is_err() {
-        return bad_res;
+    if s.len() != DETERMINISTIC_PUBLIC_KEY_COMPRESSED_SIZE {
+        throw(
+            &env,
+            BbsErrorKind::InvalidKey,
+            &format!(
+                "{}: expected {} bytes, found {}",
+                CONTEXT,
+                DETERMINISTIC_PUBLIC_KEY_COMPRESSED_SIZE,
+                s.len()
+            ),
+        );
+        return ptr::null_mut();
     }
+    let dpk =
+        DeterministicPublicKey::from(*array_ref![s, 0, DETERMINISTIC_PUBLIC_KEY_COMPRESSED_SIZE]);
+    to_bbs_key(&env, dpk, message_count)
+}
 
-    let pk_bytes = pk.to_bytes_compressed_form();
-    match env.new_byte_array(pk_bytes.len() as jint) {
-        Err(_) => bad_res,
-        Ok(out) => {
-            let pp: Vec<jbyte> = pk_bytes.iter().map(|b| *b as jbyte).collect();
-            copy_to_jni!(env, out, pp.as_slice(), bad_res);
-            out
+/// The BBS+ public key for `message_count` messages
+fn to_bbs_key(env: &JNIEnv, dpk: DeterministicPublicKey, message_count: jint) -> jbyteArray {
+    const CONTEXT: &str = "Unable to convert public key";
+    let pk = match dpk.to_public_key(message_count as usize) {
+        Err(e) => {
+            throw(
+                env,
+                BbsErrorKind::InvalidKey,
+                &format!("{}: {:?}", CONTEXT, e),
+            );
+            return ptr::null_mut();
         }
+        Ok(p) => p,
+    };
+    if let Err(e) = pk.validate() {
+        throw(
+            env,
+            BbsErrorKind::InvalidKey,
+            &format!("{}: {:?}", CONTEXT, e),
+        );
+        return ptr::null_mut();
     }
+    new_byte_array(env, CONTEXT, &pk.to_bytes_compressed_form())
 }
 
 #[allow(non_snake_case)]
 #[no_mangle]
-pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1sign_1init(_: JNIEnv, _: JObject) -> jlong {
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1sign_1init(env: JNIEnv, _: JObject) -> jlong {
     let mut error = ExternError::success();
-    bbs_sign_context_init(&mut error) as jlong
+    let handle = bbs_sign_context_init(&mut error);
+    check(
+        &env,
+        BbsErrorKind::General,
+        "Unable to create signing context",
+        error,
+    );
+    handle as jlong
 }
 
 #[allow(non_snake_case)]
@@ -303,18 +465,23 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1sign_1set_1secret_1key(
     handle: jlong,
     secret_key: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(secret_key) {
-        Err(_) => 1,
-        Ok(s) => {
-            if s.len() != FR_COMPRESSED_SIZE {
-                2
-            } else {
-                let mut error = ExternError::success();
-                let byte_array = ByteArray::from(s);
-                bbs_sign_context_set_secret_key(handle as u64, byte_array, &mut error)
-            }
-        }
-    }
+    let secret_key = match read_bytes(
+        &env,
+        BbsErrorKind::InvalidKey,
+        "Unable to set secret key",
+        secret_key,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_sign_context_set_secret_key(handle as u64, ByteArray::from(&secret_key), &mut error);
+    check(
+        &env,
+        BbsErrorKind::InvalidKey,
+        "Unable to set secret key",
+        error,
+    )
 }
 
 #[allow(non_snake_case)]
@@ -325,19 +492,23 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1sign_1set_1public_1key(
     handle: jlong,
     public_key: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(public_key) {
-        Err(_) => 1,
-        Ok(s) => {
-            let mut err = ExternError::success();
-            SIGN_CONTEXT.call_with_result_mut(&mut err, handle as u64, |ctx| -> Result<(), BbsFfiError> {
-                use std::convert::TryFrom;
-                let v = PublicKey::try_from(s)?;
-                ctx.public_key = Some(v);
-                Ok(())
-            });
-            err.get_code().code()
-        }
-    }
+    let public_key = match read_bytes(
+        &env,
+        BbsErrorKind::InvalidKey,
+        "Unable to set public key",
+        public_key,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_sign_context_set_public_key(handle as u64, ByteArray::from(&public_key), &mut error);
+    check(
+        &env,
+        BbsErrorKind::InvalidKey,
+        "Unable to set public key",
+        error,
+    )
 }
 
 #[allow(non_snake_case)]
@@ -348,14 +519,40 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1sign_1add_1message_1bytes(
     handle: jlong,
     message: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(message) {
-        Err(_) => 1,
-        Ok(s) => {
-            let mut error = ExternError::success();
-            let byte_array = ByteArray::from(s);
-            bbs_sign_context_add_message_bytes(handle as u64, byte_array, &mut error)
-        }
-    }
+    let message = match read_bytes(
+        &env,
+        BbsErrorKind::General,
+        "Unable to add message",
+        message,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_sign_context_add_message_bytes(handle as u64, ByteArray::from(&message), &mut error);
+    check(&env, BbsErrorKind::General, "Unable to add message", error)
+}
+
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1sign_1add_1message_1string(
+    env: JNIEnv,
+    _: JObject,
+    handle: jlong,
+    message: JString,
+) -> jint {
+    let message = match read_string(
+        &env,
+        BbsErrorKind::General,
+        "Unable to add message",
+        message,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_sign_context_add_message_string(handle as u64, FfiStr::from_cstr(&message), &mut error);
+    check(&env, BbsErrorKind::General, "Unable to add message", error)
 }
 
 #[allow(non_snake_case)]
@@ -366,14 +563,18 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1sign_1add_1message_1prehashed(
     handle: jlong,
     message: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(message) {
-        Err(_) => 1,
-        Ok(s) => {
-            let mut error = ExternError::success();
-            let byte_array = ByteArray::from(s);
-            bbs_sign_context_add_message_prehashed(handle as u64, byte_array, &mut error)
-        }
-    }
+    let message = match read_bytes(
+        &env,
+        BbsErrorKind::General,
+        "Unable to add message",
+        message,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_sign_context_add_message_prehashed(handle as u64, ByteArray::from(&message), &mut error);
+    check(&env, BbsErrorKind::General, "Unable to add message", error)
 }
 
 #[allow(non_snake_case)]
@@ -385,21 +586,34 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1sign_1finish(
     signature: jbyteArray,
 ) -> jint {
     let mut error = ExternError::success();
-    let mut sig = ByteBuffer::from_vec(vec![]);
-    let result = bbs_sign_context_finish(handle as u64, &mut sig, &mut error);
-    if result != 0 {
-        return result;
+    let mut out = ByteBuffer::default();
+    bbs_sign_context_finish(handle as u64, &mut out, &mut error);
+    let res = check(
+        &env,
+        BbsErrorKind::General,
+        "Unable to create signature",
+        error,
+    );
+    if res != 0 {
+        return res;
     }
-    let sig: Vec<i8> = sig.destroy_into_vec().iter().map(|b| *b as jbyte).collect();
-    copy_to_jni!(env, signature, sig.as_slice());
+    let out = to_jbytes(&out.destroy_into_vec());
+    copy_to_jni!(env, signature, out.as_slice());
     0
 }
 
 #[allow(non_snake_case)]
 #[no_mangle]
-pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1init(_: JNIEnv, _: JObject) -> jlong {
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1init(env: JNIEnv, _: JObject) -> jlong {
     let mut error = ExternError::success();
-    bbs_verify_context_init(&mut error) as jlong
+    let handle = bbs_verify_context_init(&mut error);
+    check(
+        &env,
+        BbsErrorKind::General,
+        "Unable to create verify signature context",
+        error,
+    );
+    handle as jlong
 }
 
 #[allow(non_snake_case)]
@@ -410,14 +624,40 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1add_1message_1bytes(
     handle: jlong,
     message: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(message) {
-        Err(_) => 1,
-        Ok(s) => {
-            let mut error = ExternError::success();
-            let byte_array = ByteArray::from(s);
-            bbs_verify_context_add_message_bytes(handle as u64, byte_array, &mut error)
-        }
-    }
+    let message = match read_bytes(
+        &env,
+        BbsErrorKind::General,
+        "Unable to add message",
+        message,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_verify_context_add_message_bytes(handle as u64, ByteArray::from(&message), &mut error);
+    check(&env, BbsErrorKind::General, "Unable to add message", error)
+}
+
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1add_1message_1string(
+    env: JNIEnv,
+    _: JObject,
+    handle: jlong,
+    message: JString,
+) -> jint {
+    let message = match read_string(
+        &env,
+        BbsErrorKind::General,
+        "Unable to add message",
+        message,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_verify_context_add_message_string(handle as u64, FfiStr::from_cstr(&message), &mut error);
+    check(&env, BbsErrorKind::General, "Unable to add message", error)
 }
 
 #[allow(non_snake_case)]
@@ -428,14 +668,18 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1add_1message_1prehashed(
     handle: jlong,
     message: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(message) {
-        Err(_) => 1,
-        Ok(s) => {
-            let mut error = ExternError::success();
-            let byte_array = ByteArray::from(s);
-            bbs_verify_context_add_message_prehashed(handle as u64, byte_array, &mut error)
-        }
-    }
+    let message = match read_bytes(
+        &env,
+        BbsErrorKind::General,
+        "Unable to add message",
+        message,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_verify_context_add_message_prehashed(handle as u64, ByteArray::from(&message), &mut error);
+    check(&env, BbsErrorKind::General, "Unable to add message", error)
 }
 
 #[allow(non_snake_case)]
@@ -446,14 +690,23 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1set_1public_1key(
     handle: jlong,
     public_key: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(public_key) {
-        Err(_) => 1,
-        Ok(s) => {
-            let mut error = ExternError::success();
-            let byte_array = ByteArray::from(s);
-            bbs_verify_context_set_public_key(handle as u64, byte_array, &mut error)
-        }
-    }
+    let public_key = match read_bytes(
+        &env,
+        BbsErrorKind::InvalidKey,
+        "Unable to set public key",
+        public_key,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_verify_context_set_public_key(handle as u64, ByteArray::from(&public_key), &mut error);
+    check(
+        &env,
+        BbsErrorKind::InvalidKey,
+        "Unable to set public key",
+        error,
+    )
 }
 
 #[allow(non_snake_case)]
@@ -464,39 +717,60 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1set_1signature(
     handle: jlong,
     signature: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(signature) {
-        Err(_) => 1,
-        Ok(s) => {
-            if s.len() < G1_COMPRESSED_SIZE {
-                2
-            } else {
-                let mut error = ExternError::success();
-                let byte_array = ByteArray::from(s);
-                bbs_verify_context_set_signature(handle as u64, byte_array, &mut error)
-            }
-        }
-    }
+    let signature = match read_bytes(
+        &env,
+        BbsErrorKind::InvalidSignature,
+        "Unable to set signature",
+        signature,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_verify_context_set_signature(handle as u64, ByteArray::from(&signature), &mut error);
+    check(
+        &env,
+        BbsErrorKind::InvalidSignature,
+        "Unable to set signature",
+        error,
+    )
 }
 
 #[allow(non_snake_case)]
 #[no_mangle]
 pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1finish(
-    _: JNIEnv,
+    env: JNIEnv,
     _: JObject,
     handle: jlong,
 ) -> jint {
     let mut error = ExternError::success();
-    bbs_verify_context_finish(handle as u64, &mut error)
+    let res = bbs_verify_context_finish(handle as u64, &mut error);
+    match check(
+        &env,
+        BbsErrorKind::General,
+        "Unable to verify signature",
+        error,
+    ) {
+        0 => res,
+        e => e,
+    }
 }
 
 #[allow(non_snake_case)]
 #[no_mangle]
 pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1blind_1commitment_1init(
-    _: JNIEnv,
+    env: JNIEnv,
     _: JObject,
 ) -> jlong {
     let mut error = ExternError::success();
-    bbs_blind_commitment_context_init(&mut error) as jlong
+    let handle = bbs_blind_commitment_context_init(&mut error);
+    check(
+        &env,
+        BbsErrorKind::General,
+        "Unable to create blind commitment context",
+        error,
+    );
+    handle as jlong
 }
 
 #[allow(non_snake_case)]
@@ -508,19 +782,51 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1blind_1commitment_1add_1message_1
     index: jint,
     message: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(message) {
-        Err(_) => 1,
-        Ok(s) => {
-            let mut error = ExternError::success();
-            let byte_array = ByteArray::from(s);
-            bbs_blind_commitment_context_add_message_bytes(
-                handle as u64,
-                index as u32,
-                byte_array,
-                &mut error,
-            )
-        }
-    }
+    let message = match read_bytes(
+        &env,
+        BbsErrorKind::General,
+        "Unable to add message",
+        message,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_blind_commitment_context_add_message_bytes(
+        handle as u64,
+        index as u32,
+        ByteArray::from(&message),
+        &mut error,
+    );
+    check(&env, BbsErrorKind::General, "Unable to add message", error)
+}
+
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1blind_1commitment_1add_1message_1string(
+    env: JNIEnv,
+    _: JObject,
+    handle: jlong,
+    index: jint,
+    message: JString,
+) -> jint {
+    let message = match read_string(
+        &env,
+        BbsErrorKind::General,
+        "Unable to add message",
+        message,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_blind_commitment_context_add_message_string(
+        handle as u64,
+        index as u32,
+        FfiStr::from_cstr(&message),
+        &mut error,
+    );
+    check(&env, BbsErrorKind::General, "Unable to add message", error)
 }
 
 #[allow(non_snake_case)]
@@ -532,19 +838,23 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1blind_1commitment_1add_1prehashed
     index: jint,
     message: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(message) {
-        Err(_) => 1,
-        Ok(s) => {
-            let mut error = ExternError::success();
-            let byte_array = ByteArray::from(s);
-            bbs_blind_commitment_context_add_message_prehashed(
-                handle as u64,
-                index as u32,
-                byte_array,
-                &mut error,
-            )
-        }
-    }
+    let message = match read_bytes(
+        &env,
+        BbsErrorKind::General,
+        "Unable to add message",
+        message,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_blind_commitment_context_add_message_prehashed(
+        handle as u64,
+        index as u32,
+        ByteArray::from(&message),
+        &mut error,
+    );
+    check(&env, BbsErrorKind::General, "Unable to add message", error)
 }
 
 #[allow(non_snake_case)]
@@ -555,14 +865,27 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1blind_1commitment_1set_1public_1k
     handle: jlong,
     public_key: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(public_key) {
-        Err(_) => 1,
-        Ok(s) => {
-            let mut error = ExternError::success();
-            let byte_array = ByteArray::from(s);
-            bbs_blind_commitment_context_set_public_key(handle as u64, byte_array, &mut error)
-        }
-    }
+    let public_key = match read_bytes(
+        &env,
+        BbsErrorKind::InvalidKey,
+        "Unable to set public key",
+        public_key,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_blind_commitment_context_set_public_key(
+        handle as u64,
+        ByteArray::from(&public_key),
+        &mut error,
+    );
+    check(
+        &env,
+        BbsErrorKind::InvalidKey,
+        "Unable to set public key",
+        error,
+    )
 }
 
 #[allow(non_snake_case)]
@@ -573,14 +896,59 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1blind_1commitment_1set_1nonce_1by
     handle: jlong,
     nonce: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(nonce) {
-        Err(_) => 1,
-        Ok(s) => {
-            let mut error = ExternError::success();
-            let byte_array = ByteArray::from(s);
-            bbs_blind_commitment_context_set_nonce_bytes(handle as u64, byte_array, &mut error)
-        }
-    }
+    let nonce = match read_bytes(&env, BbsErrorKind::General, "Unable to set nonce", nonce) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_blind_commitment_context_set_nonce_bytes(
+        handle as u64,
+        ByteArray::from(&nonce),
+        &mut error,
+    );
+    check(&env, BbsErrorKind::General, "Unable to set nonce", error)
+}
+
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1blind_1commitment_1set_1nonce_1string(
+    env: JNIEnv,
+    _: JObject,
+    handle: jlong,
+    nonce: JString,
+) -> jint {
+    let nonce = match read_string(&env, BbsErrorKind::General, "Unable to set nonce", nonce) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_blind_commitment_context_set_nonce_string(
+        handle as u64,
+        FfiStr::from_cstr(&nonce),
+        &mut error,
+    );
+    check(&env, BbsErrorKind::General, "Unable to set nonce", error)
+}
+
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1blind_1commitment_1set_1nonce_1prehashed(
+    env: JNIEnv,
+    _: JObject,
+    handle: jlong,
+    nonce: jbyteArray,
+) -> jint {
+    let nonce = match read_bytes(&env, BbsErrorKind::General, "Unable to set nonce", nonce) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_blind_commitment_context_set_nonce_prehashed(
+        handle as u64,
+        ByteArray::from(&nonce),
+        &mut error,
+    );
+    check(&env, BbsErrorKind::General, "Unable to set nonce", error)
 }
 
 /// commitment: [0u8; 48]
@@ -596,35 +964,50 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1blind_1commitment_1finish(
     blinding_factor: jbyteArray,
 ) -> jbyteArray {
     let mut error = ExternError::success();
-    let mut c = ByteBuffer::from_vec(vec![]);
-    let mut p = ByteBuffer::from_vec(vec![]);
-    let mut r = ByteBuffer::from_vec(vec![]);
-    let res =
-        bbs_blind_commitment_context_finish(handle as u64, &mut c, &mut p, &mut r, &mut error);
-    let bad_res = env.new_byte_array(0).unwrap();
-    if res != 0 {
-        return bad_res;
-    }
-    let cc: Vec<jbyte> = c.destroy_into_vec().iter().map(|b| *b as jbyte).collect();
-    copy_to_jni!(env, commitment, cc.as_slice(), bad_res);
-    let rr: Vec<jbyte> = r.destroy_into_vec().iter().map(|b| *b as jbyte).collect();
-    copy_to_jni!(env, blinding_factor, rr.as_slice(), bad_res);
-    let pp: Vec<jbyte> = p.destroy_into_vec().iter().map(|b| *b as jbyte).collect();
-
-    match env.new_byte_array(pp.len() as jint) {
-        Err(_) => env.new_byte_array(0).unwrap(),
-        Ok(out) => {
-            copy_to_jni!(env, out, pp.as_slice(), bad_res);
-            out
-        }
+    let mut c = ByteBuffer::default();
+    let mut p = ByteBuffer::default();
+    let mut r = ByteBuffer::default();
+    bbs_blind_commitment_context_finish(handle as u64, &mut c, &mut p, &mut r, &mut error);
+    if check(
+        &env,
+        BbsErrorKind::General,
+        "Unable to create blind commitment",
+        error,
+    ) != 0
+    {
+        return ptr::null_mut();
     }
+    copy_to_jni!(
+        env,
+        commitment,
+        to_jbytes(&c.destroy_into_vec()).as_slice(),
+        ptr::null_mut()
+    );
+    copy_to_jni!(
+        env,
+        blinding_factor,
+        to_jbytes(&r.destroy_into_vec()).as_slice(),
+        ptr::null_mut()
+    );
+    new_byte_array(
+        &env,
+        "Unable to create blind commitment",
+        &p.destroy_into_vec(),
+    )
 }
 
 #[allow(non_snake_case)]
 #[no_mangle]
-pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1blind_1sign_1init(_: JNIEnv, _: JObject) -> jlong {
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1blind_1sign_1init(env: JNIEnv, _: JObject) -> jlong {
     let mut error = ExternError::success();
-    bbs_blind_sign_context_init(&mut error) as jlong
+    let handle = bbs_blind_sign_context_init(&mut error);
+    check(
+        &env,
+        BbsErrorKind::General,
+        "Unable to create blind sign context",
+        error,
+    );
+    handle as jlong
 }
 
 #[allow(non_snake_case)]
@@ -635,18 +1018,23 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1blind_1sign_1set_1secret_1key(
     handle: jlong,
     secret_key: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(secret_key) {
-        Err(_) => 1,
-        Ok(s) => {
-            if s.len() != FR_COMPRESSED_SIZE {
-                2
-            } else {
-                let mut error = ExternError::success();
-                let byte_array = ByteArray::from(s);
-                bbs_blind_sign_context_set_secret_key(handle as u64, byte_array, &mut error)
-            }
-        }
-    }
+    let secret_key = match read_bytes(
+        &env,
+        BbsErrorKind::InvalidKey,
+        "Unable to set secret key",
+        secret_key,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_blind_sign_context_set_secret_key(handle as u64, ByteArray::from(&secret_key), &mut error);
+    check(
+        &env,
+        BbsErrorKind::InvalidKey,
+        "Unable to set secret key",
+        error,
+    )
 }
 
 #[allow(non_snake_case)]
@@ -657,14 +1045,23 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1blind_1sign_1set_1public_1key(
     handle: jlong,
     public_key: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(public_key) {
-        Err(_) => 1,
-        Ok(s) => {
-            let mut error = ExternError::success();
-            let byte_array = ByteArray::from(s);
-            bbs_blind_sign_context_set_public_key(handle as u64, byte_array, &mut error)
-        }
-    }
+    let public_key = match read_bytes(
+        &env,
+        BbsErrorKind::InvalidKey,
+        "Unable to set public key",
+        public_key,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_blind_sign_context_set_public_key(handle as u64, ByteArray::from(&public_key), &mut error);
+    check(
+        &env,
+        BbsErrorKind::InvalidKey,
+        "Unable to set public key",
+        error,
+    )
 }
 
 #[allow(non_snake_case)]
@@ -675,14 +1072,23 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1blind_1sign_1set_1commitment(
     handle: jlong,
     commitment: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(commitment) {
-        Err(_) => 1,
-        Ok(s) => {
-            let mut error = ExternError::success();
-            let byte_array = ByteArray::from(s);
-            bbs_blind_sign_context_set_commitment(handle as u64, byte_array, &mut error)
-        }
-    }
+    let commitment = match read_bytes(
+        &env,
+        BbsErrorKind::General,
+        "Unable to set commitment",
+        commitment,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_blind_sign_context_set_commitment(handle as u64, ByteArray::from(&commitment), &mut error);
+    check(
+        &env,
+        BbsErrorKind::General,
+        "Unable to set commitment",
+        error,
+    )
 }
 
 #[allow(non_snake_case)]
@@ -694,19 +1100,51 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1blind_1sign_1add_1message_1bytes(
     index: jint,
     message: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(message) {
-        Err(_) => 1,
-        Ok(s) => {
-            let mut error = ExternError::success();
-            let byte_array = ByteArray::from(s);
-            bbs_blind_sign_context_add_message_bytes(
-                handle as u64,
-                index as u32,
-                byte_array,
-                &mut error,
-            )
-        }
-    }
+    let message = match read_bytes(
+        &env,
+        BbsErrorKind::General,
+        "Unable to add message",
+        message,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_blind_sign_context_add_message_bytes(
+        handle as u64,
+        index as u32,
+        ByteArray::from(&message),
+        &mut error,
+    );
+    check(&env, BbsErrorKind::General, "Unable to add message", error)
+}
+
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1blind_1sign_1add_1message_1string(
+    env: JNIEnv,
+    _: JObject,
+    handle: jlong,
+    index: jint,
+    message: JString,
+) -> jint {
+    let message = match read_string(
+        &env,
+        BbsErrorKind::General,
+        "Unable to add message",
+        message,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_blind_sign_context_add_message_string(
+        handle as u64,
+        index as u32,
+        FfiStr::from_cstr(&message),
+        &mut error,
+    );
+    check(&env, BbsErrorKind::General, "Unable to add message", error)
 }
 
 #[allow(non_snake_case)]
@@ -718,19 +1156,18 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1blind_1sign_1add_1prehashed(
     index: jint,
     hash: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(hash) {
-        Err(_) => 1,
-        Ok(s) => {
-            let mut error = ExternError::success();
-            let byte_array = ByteArray::from(s);
-            bbs_blind_sign_context_add_message_prehashed(
-                handle as u64,
-                index as u32,
-                byte_array,
-                &mut error,
-            )
-        }
-    }
+    let hash = match read_bytes(&env, BbsErrorKind::General, "Unable to add message", hash) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_blind_sign_context_add_message_prehashed(
+        handle as u64,
+        index as u32,
+        ByteArray::from(&hash),
+        &mut error,
+    );
+    check(&env, BbsErrorKind::General, "Unable to add message", error)
 }
 
 #[allow(non_snake_case)]
@@ -741,14 +1178,20 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1blind_1sign_1finish(
     handle: jlong,
     signature: jbyteArray,
 ) -> jint {
-    let mut err = ExternError::success();
-    let mut s = ByteBuffer::from_vec(vec![]);
-    let res = bbs_blind_sign_context_finish(handle as u64, &mut s, &mut err);
+    let mut error = ExternError::success();
+    let mut out = ByteBuffer::default();
+    bbs_blind_sign_context_finish(handle as u64, &mut out, &mut error);
+    let res = check(
+        &env,
+        BbsErrorKind::General,
+        "Unable to create blind signature",
+        error,
+    );
     if res != 0 {
         return res;
     }
-    let ss: Vec<jbyte> = s.destroy_into_vec().iter().map(|b| *b as jbyte).collect();
-    copy_to_jni!(env, signature, ss.as_slice());
+    let out = to_jbytes(&out.destroy_into_vec());
+    copy_to_jni!(env, signature, out.as_slice());
     0
 }
 
@@ -761,43 +1204,61 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1unblind_1signature(
     blinding_factor: jbyteArray,
     unblind_signature: jbyteArray,
 ) -> jint {
-    let mut err = ExternError::success();
-    let bs = match env.convert_byte_array(blind_signature) {
-        Err(_) => return 1,
+    const CONTEXT: &str = "Unable to unblind signature";
+    let bs = match read_bytes(
+        &env,
+        BbsErrorKind::InvalidSignature,
+        CONTEXT,
+        blind_signature,
+    ) {
+        Err(e) => return e,
         Ok(s) => s,
     };
-    let bf = match env.convert_byte_array(blinding_factor) {
-        Err(_) => return 1,
+    let bf = match read_bytes(
+        &env,
+        BbsErrorKind::InvalidSignature,
+        CONTEXT,
+        blinding_factor,
+    ) {
+        Err(e) => return e,
         Ok(s) => s,
     };
 
+    let mut error = ExternError::success();
     let mut signature = ByteBuffer::default();
-    let res = bbs_unblind_signature(
-        ByteArray::from(bs),
-        ByteArray::from(bf),
+    bbs_unblind_signature(
+        ByteArray::from(&bs),
+        ByteArray::from(&bf),
         &mut signature,
-        &mut err,
+        &mut error,
     );
+    let res = check(&env, BbsErrorKind::InvalidSignature, CONTEXT, error);
     if res != 0 {
         return res;
     }
-    let signature: Vec<jbyte> = signature
-        .destroy_into_vec()
-        .iter()
-        .map(|b| *b as jbyte)
-        .collect();
-    copy_to_jni!(env, unblind_signature, signature.as_slice());
+    copy_to_jni!(
+        env,
+        unblind_signature,
+        to_jbytes(&signature.destroy_into_vec()).as_slice()
+    );
     0
 }
 
 #[allow(non_snake_case)]
 #[no_mangle]
 pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1create_1proof_1context_1init(
-    _: JNIEnv,
+    env: JNIEnv,
     _: JObject,
 ) -> jlong {
     let mut error = ExternError::success();
-    bbs_create_proof_context_init(&mut error) as jlong
+    let handle = bbs_create_proof_context_init(&mut error);
+    check(
+        &env,
+        BbsErrorKind::General,
+        "Unable to create proof context",
+        error,
+    );
+    handle as jlong
 }
 
 #[allow(non_snake_case)]
@@ -808,21 +1269,27 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1create_1proof_1context_1set_1publ
     handle: jlong,
     public_key: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(public_key) {
-        Err(_) => 1,
-        Ok(s) => {
-            let mut error = ExternError::success();
-            // let byte_array = ByteArray::from(s.clone());
-            // bbs_create_proof_context_set_public_key(handle as u64, byte_array, &mut error)
-            CREATE_PROOF_CONTEXT.call_with_result_mut(&mut error, handle as u64, |ctx| -> Result<(), BbsFfiError> {
-                use std::convert::TryFrom;
-                let v = PublicKey::try_from(s)?;
-                ctx.public_key = Some(v);
-                Ok(())
-            });
-            error.get_code().code()
-        }
-    }
+    let public_key = match read_bytes(
+        &env,
+        BbsErrorKind::InvalidKey,
+        "Unable to set public key",
+        public_key,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_create_proof_context_set_public_key(
+        handle as u64,
+        ByteArray::from(&public_key),
+        &mut error,
+    );
+    check(
+        &env,
+        BbsErrorKind::InvalidKey,
+        "Unable to set public key",
+        error,
+    )
 }
 
 #[allow(non_snake_case)]
@@ -833,18 +1300,23 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1create_1proof_1context_1set_1sign
     handle: jlong,
     signature: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(signature) {
-        Err(_) => 1,
-        Ok(s) => {
-            let mut error = ExternError::success();
-            let byte_array = ByteArray::from(s);
-            let res = bbs_create_proof_context_set_signature(handle as u64, byte_array, &mut error);
-            if res != 0 {
-                update_last_error(error.get_message().as_str());
-            }
-            res
-        }
-    }
+    let signature = match read_bytes(
+        &env,
+        BbsErrorKind::InvalidSignature,
+        "Unable to set signature",
+        signature,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_create_proof_context_set_signature(handle as u64, ByteArray::from(&signature), &mut error);
+    check(
+        &env,
+        BbsErrorKind::InvalidSignature,
+        "Unable to set signature",
+        error,
+    )
 }
 
 #[allow(non_snake_case)]
@@ -855,14 +1327,51 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1create_1proof_1context_1set_1nonc
     handle: jlong,
     nonce: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(nonce) {
-        Err(_) => 1,
-        Ok(s) => {
-            let mut error = ExternError::success();
-            let byte_array = ByteArray::from(s);
-            bbs_create_proof_context_set_nonce_bytes(handle as u64, byte_array, &mut error)
-        }
-    }
+    let nonce = match read_bytes(&env, BbsErrorKind::General, "Unable to set nonce", nonce) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_create_proof_context_set_nonce_bytes(handle as u64, ByteArray::from(&nonce), &mut error);
+    check(&env, BbsErrorKind::General, "Unable to set nonce", error)
+}
+
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1create_1proof_1context_1set_1nonce_1string(
+    env: JNIEnv,
+    _: JObject,
+    handle: jlong,
+    nonce: JString,
+) -> jint {
+    let nonce = match read_string(&env, BbsErrorKind::General, "Unable to set nonce", nonce) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_create_proof_context_set_nonce_string(handle as u64, FfiStr::from_cstr(&nonce), &mut error);
+    check(&env, BbsErrorKind::General, "Unable to set nonce", error)
+}
+
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1create_1proof_1context_1set_1nonce_1prehashed(
+    env: JNIEnv,
+    _: JObject,
+    handle: jlong,
+    nonce: jbyteArray,
+) -> jint {
+    let nonce = match read_bytes(&env, BbsErrorKind::General, "Unable to set nonce", nonce) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_create_proof_context_set_nonce_prehashed(
+        handle as u64,
+        ByteArray::from(&nonce),
+        &mut error,
+    );
+    check(&env, BbsErrorKind::General, "Unable to set nonce", error)
 }
 
 #[allow(non_snake_case)]
@@ -875,40 +1384,123 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1create_1proof_1context_1add_1proo
     xtype: jint,
     blinding_factor: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(message) {
-        Err(_) => 1,
-        Ok(s) => {
-            let mut error = ExternError::success();
-            let byte_array = ByteArray::from(s);
-            let mut bf_byte_array = ByteArray::default();
-            let proof_msg_type = match xtype {
-                1 => ProofMessageType::Revealed,
-                2 => ProofMessageType::HiddenProofSpecificBlinding,
-                3 => {
-                    match env.convert_byte_array(blinding_factor) {
-                        Err(_) => return 0,
-                        Ok(bf) => {
-                            bf_byte_array = ByteArray::from(bf);
-                        }
-                    };
-                    ProofMessageType::HiddenExternalBlinding
-                }
-                _ => return 2,
-            };
-            bbs_create_proof_context_add_proof_message_bytes(
-                handle as u64,
-                byte_array,
-                proof_msg_type,
-                bf_byte_array,
-                &mut error,
-            )
-        }
-    }
+    let message = match read_bytes(
+        &env,
+        BbsErrorKind::General,
+        "Unable to add proof message",
+        message,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let (proof_msg_type, blinding_factor) =
+        match get_proof_message_type(&env, xtype, blinding_factor) {
+            Err(e) => return e,
+            Ok(t) => t,
+        };
+    let mut error = ExternError::success();
+    bbs_create_proof_context_add_proof_message_bytes(
+        handle as u64,
+        ByteArray::from(&message),
+        proof_msg_type,
+        ByteArray::from(&blinding_factor),
+        &mut error,
+    );
+    check(
+        &env,
+        BbsErrorKind::General,
+        "Unable to add proof message",
+        error,
+    )
+}
+
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1create_1proof_1context_1add_1proof_1message_1string(
+    env: JNIEnv,
+    _: JObject,
+    handle: jlong,
+    message: JString,
+    xtype: jint,
+    blinding_factor: jbyteArray,
+) -> jint {
+    let message = match read_string(
+        &env,
+        BbsErrorKind::General,
+        "Unable to add proof message",
+        message,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let (proof_msg_type, blinding_factor) =
+        match get_proof_message_type(&env, xtype, blinding_factor) {
+            Err(e) => return e,
+            Ok(t) => t,
+        };
+    let mut error = ExternError::success();
+    bbs_create_proof_context_add_proof_message_string(
+        handle as u64,
+        FfiStr::from_cstr(&message),
+        proof_msg_type,
+        ByteArray::from(&blinding_factor),
+        &mut error,
+    );
+    check(
+        &env,
+        BbsErrorKind::General,
+        "Unable to add proof message",
+        error,
+    )
 }
 
 #[allow(non_snake_case)]
 #[no_mangle]
-pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1create_1proof_1size(_: JNIEnv, _: JObject, handle: jlong) -> jint {
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1create_1proof_1context_1add_1proof_1message_1prehashed(
+    env: JNIEnv,
+    _: JObject,
+    handle: jlong,
+    message: jbyteArray,
+    xtype: jint,
+    blinding_factor: jbyteArray,
+) -> jint {
+    let message = match read_bytes(
+        &env,
+        BbsErrorKind::General,
+        "Unable to add proof message",
+        message,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let (proof_msg_type, blinding_factor) =
+        match get_proof_message_type(&env, xtype, blinding_factor) {
+            Err(e) => return e,
+            Ok(t) => t,
+        };
+    let mut error = ExternError::success();
+    bbs_create_proof_context_add_proof_message_prehashed(
+        handle as u64,
+        ByteArray::from(&message),
+        proof_msg_type,
+        ByteArray::from(&blinding_factor),
+        &mut error,
+    );
+    check(
+        &env,
+        BbsErrorKind::General,
+        "Unable to add proof message",
+        error,
+    )
+}
+
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1create_1proof_1size(
+    _: JNIEnv,
+    _: JObject,
+    handle: jlong,
+) -> jint {
     bbs_create_proof_context_size(handle as u64)
 }
 
@@ -921,25 +1513,32 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1create_1proof_1context_1finish(
     proof: jbyteArray,
 ) -> jint {
     let mut error = ExternError::success();
-    let mut p = ByteBuffer::from_vec(vec![]);
-    let res = bbs_create_proof_context_finish(handle as u64, &mut p, &mut error);
+    let mut out = ByteBuffer::default();
+    bbs_create_proof_context_finish(handle as u64, &mut out, &mut error);
+    let res = check(&env, BbsErrorKind::General, "Unable to create proof", error);
     if res != 0 {
         return res;
     }
-    let res = p.destroy_into_vec();
-    let pp: Vec<jbyte> = res.iter().map(|b| *b as jbyte).collect();
-    copy_to_jni!(env, proof, pp.as_slice());
+    let out = to_jbytes(&out.destroy_into_vec());
+    copy_to_jni!(env, proof, out.as_slice());
     0
 }
 
 #[allow(non_snake_case)]
 #[no_mangle]
 pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1proof_1context_1init(
-    _: JNIEnv,
+    env: JNIEnv,
     _: JObject,
 ) -> jlong {
     let mut error = ExternError::success();
-    bbs_verify_proof_context_init(&mut error) as jlong
+    let handle = bbs_verify_proof_context_init(&mut error);
+    check(
+        &env,
+        BbsErrorKind::General,
+        "Unable to create verify proof context",
+        error,
+    );
+    handle as jlong
 }
 
 #[allow(non_snake_case)]
@@ -950,18 +1549,76 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1proof_1context_1add_1mess
     handle: jlong,
     message: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(message) {
-        Err(_) => 1,
-        Ok(s) => {
-            let mut error = ExternError::success();
-            let byte_array = ByteArray::from(s);
-            bbs_verify_proof_context_add_message_bytes(
-                handle as u64,
-                byte_array,
-                &mut error,
-            )
-        }
-    }
+    let message = match read_bytes(
+        &env,
+        BbsErrorKind::General,
+        "Unable to add message",
+        message,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_verify_proof_context_add_message_bytes(
+        handle as u64,
+        ByteArray::from(&message),
+        &mut error,
+    );
+    check(&env, BbsErrorKind::General, "Unable to add message", error)
+}
+
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1proof_1context_1add_1revealed_1message(
+    env: JNIEnv,
+    _: JObject,
+    handle: jlong,
+    index: jint,
+    message: jbyteArray,
+) -> jint {
+    let message = match read_bytes(
+        &env,
+        BbsErrorKind::General,
+        "Unable to add message",
+        message,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_verify_proof_context_add_revealed_message(
+        handle as u64,
+        index as u32,
+        ByteArray::from(&message),
+        &mut error,
+    );
+    check(&env, BbsErrorKind::General, "Unable to add message", error)
+}
+
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1proof_1context_1add_1message_1string(
+    env: JNIEnv,
+    _: JObject,
+    handle: jlong,
+    message: JString,
+) -> jint {
+    let message = match read_string(
+        &env,
+        BbsErrorKind::General,
+        "Unable to add message",
+        message,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_verify_proof_context_add_message_string(
+        handle as u64,
+        FfiStr::from_cstr(&message),
+        &mut error,
+    );
+    check(&env, BbsErrorKind::General, "Unable to add message", error)
 }
 
 #[allow(non_snake_case)]
@@ -972,18 +1629,22 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1proof_1context_1add_1mess
     handle: jlong,
     message: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(message) {
-        Err(_) => 1,
-        Ok(s) => {
-            let mut error = ExternError::success();
-            let byte_array = ByteArray::from(s);
-            bbs_verify_proof_context_add_message_prehashed(
-                handle as u64,
-                byte_array,
-                &mut error,
-            )
-        }
-    }
+    let message = match read_bytes(
+        &env,
+        BbsErrorKind::General,
+        "Unable to add message",
+        message,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_verify_proof_context_add_message_prehashed(
+        handle as u64,
+        ByteArray::from(&message),
+        &mut error,
+    );
+    check(&env, BbsErrorKind::General, "Unable to add message", error)
 }
 
 #[allow(non_snake_case)]
@@ -994,18 +1655,23 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1proof_1context_1set_1proo
     handle: jlong,
     proof: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(proof) {
-        Err(_) => 1,
-        Ok(s) => {
-            let mut error = ExternError::success();
-            let byte_array = ByteArray::from_slice(s.as_slice());
-            let res = bbs_verify_proof_context_set_proof(handle as u64, byte_array, &mut error);
-            if res != 0 {
-                update_last_error(error.get_message().as_str());
-            }
-            res
-        }
-    }
+    let proof = match read_bytes(
+        &env,
+        BbsErrorKind::ProofVerification,
+        "Unable to set proof",
+        proof,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_verify_proof_context_set_proof(handle as u64, ByteArray::from(&proof), &mut error);
+    check(
+        &env,
+        BbsErrorKind::ProofVerification,
+        "Unable to set proof",
+        error,
+    )
 }
 
 #[allow(non_snake_case)]
@@ -1016,21 +1682,27 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1proof_1context_1set_1publ
     handle: jlong,
     public_key: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(public_key) {
-        Err(_) => 1,
-        Ok(s) => {
-            let mut error = ExternError::success();
-            // let byte_array = ByteArray::from(s);
-            // bbs_verify_proof_context_set_public_key(handle as u64, byte_array, &mut error)
-            VERIFY_PROOF_CONTEXT.call_with_result_mut(&mut error, handle as u64, |ctx| -> Result<(), BbsFfiError> {
-                use std::convert::TryFrom;
-                let v = PublicKey::try_from(s)?;
-                ctx.public_key = Some(v);
-                Ok(())
-            });
-            error.get_code().code()
-        }
-    }
+    let public_key = match read_bytes(
+        &env,
+        BbsErrorKind::InvalidKey,
+        "Unable to set public key",
+        public_key,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_verify_proof_context_set_public_key(
+        handle as u64,
+        ByteArray::from(&public_key),
+        &mut error,
+    );
+    check(
+        &env,
+        BbsErrorKind::InvalidKey,
+        "Unable to set public key",
+        error,
+    )
 }
 
 #[allow(non_snake_case)]
@@ -1041,51 +1713,369 @@ pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1proof_1context_1set_1nonc
     handle: jlong,
     nonce: jbyteArray,
 ) -> jint {
-    match env.convert_byte_array(nonce) {
-        Err(_) => 1,
-        Ok(s) => {
-            let mut error = ExternError::success();
-            let byte_array = ByteArray::from(s);
-            bbs_verify_proof_context_set_nonce_bytes(handle as u64, byte_array, &mut error)
-        }
-    }
+    let nonce = match read_bytes(&env, BbsErrorKind::General, "Unable to set nonce", nonce) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_verify_proof_context_set_nonce_bytes(handle as u64, ByteArray::from(&nonce), &mut error);
+    check(&env, BbsErrorKind::General, "Unable to set nonce", error)
+}
+
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1proof_1context_1set_1nonce_1string(
+    env: JNIEnv,
+    _: JObject,
+    handle: jlong,
+    nonce: JString,
+) -> jint {
+    let nonce = match read_string(&env, BbsErrorKind::General, "Unable to set nonce", nonce) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_verify_proof_context_set_nonce_string(handle as u64, FfiStr::from_cstr(&nonce), &mut error);
+    check(&env, BbsErrorKind::General, "Unable to set nonce", error)
+}
+
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1proof_1context_1set_1nonce_1prehashed(
+    env: JNIEnv,
+    _: JObject,
+    handle: jlong,
+    nonce: jbyteArray,
+) -> jint {
+    let nonce = match read_bytes(&env, BbsErrorKind::General, "Unable to set nonce", nonce) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_verify_proof_context_set_nonce_prehashed(
+        handle as u64,
+        ByteArray::from(&nonce),
+        &mut error,
+    );
+    check(&env, BbsErrorKind::General, "Unable to set nonce", error)
 }
 
 #[allow(non_snake_case)]
 #[no_mangle]
 pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1proof_1context_1finish(
-    _: JNIEnv,
+    env: JNIEnv,
+    _: JObject,
+    handle: jlong,
+) -> jint {
+    let mut error = ExternError::success();
+    let res = bbs_verify_proof_context_finish(handle as u64, &mut error);
+    match check(
+        &env,
+        BbsErrorKind::ProofVerification,
+        "Unable to verify proof",
+        error,
+    ) {
+        0 => res,
+        e => e,
+    }
+}
+
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1blind_1commitment_1context_1init(
+    env: JNIEnv,
+    _: JObject,
+) -> jlong {
+    let mut error = ExternError::success();
+    let handle = bbs_verify_blind_commitment_context_init(&mut error);
+    check(
+        &env,
+        BbsErrorKind::General,
+        "Unable to create verify blind commitment context",
+        error,
+    );
+    handle as jlong
+}
+
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1blind_1commitment_1context_1add_1blinded(
+    env: JNIEnv,
+    _: JObject,
+    handle: jlong,
+    index: jint,
+) -> jint {
+    let mut error = ExternError::success();
+    bbs_verify_blind_commitment_context_add_blinded(handle as u64, index as u32, &mut error);
+    check(
+        &env,
+        BbsErrorKind::General,
+        "Unable to add blinded index",
+        error,
+    )
+}
+
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1blind_1commitment_1context_1set_1public_1key(
+    env: JNIEnv,
     _: JObject,
     handle: jlong,
+    public_key: jbyteArray,
 ) -> jint {
+    let public_key = match read_bytes(
+        &env,
+        BbsErrorKind::InvalidKey,
+        "Unable to set public key",
+        public_key,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
     let mut error = ExternError::success();
-    bbs_verify_proof_context_finish(handle as u64, &mut error)
+    bbs_verify_blind_commitment_context_set_public_key(
+        handle as u64,
+        ByteArray::from(&public_key),
+        &mut error,
+    );
+    check(
+        &env,
+        BbsErrorKind::InvalidKey,
+        "Unable to set public key",
+        error,
+    )
+}
+
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1blind_1commitment_1context_1set_1nonce_1bytes(
+    env: JNIEnv,
+    _: JObject,
+    handle: jlong,
+    nonce: jbyteArray,
+) -> jint {
+    let nonce = match read_bytes(&env, BbsErrorKind::General, "Unable to set nonce", nonce) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_verify_blind_commitment_context_set_nonce_bytes(
+        handle as u64,
+        ByteArray::from(&nonce),
+        &mut error,
+    );
+    check(&env, BbsErrorKind::General, "Unable to set nonce", error)
+}
+
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1blind_1commitment_1context_1set_1nonce_1string(
+    env: JNIEnv,
+    _: JObject,
+    handle: jlong,
+    nonce: JString,
+) -> jint {
+    let nonce = match read_string(&env, BbsErrorKind::General, "Unable to set nonce", nonce) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_verify_blind_commitment_context_set_nonce_string(
+        handle as u64,
+        FfiStr::from_cstr(&nonce),
+        &mut error,
+    );
+    check(&env, BbsErrorKind::General, "Unable to set nonce", error)
+}
+
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1blind_1commitment_1context_1set_1nonce_1prehashed(
+    env: JNIEnv,
+    _: JObject,
+    handle: jlong,
+    nonce: jbyteArray,
+) -> jint {
+    let nonce = match read_bytes(&env, BbsErrorKind::General, "Unable to set nonce", nonce) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_verify_blind_commitment_context_set_nonce_prehashed(
+        handle as u64,
+        ByteArray::from(&nonce),
+        &mut error,
+    );
+    check(&env, BbsErrorKind::General, "Unable to set nonce", error)
+}
+
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1blind_1commitment_1context_1set_1proof(
+    env: JNIEnv,
+    _: JObject,
+    handle: jlong,
+    proof: jbyteArray,
+) -> jint {
+    let proof = match read_bytes(
+        &env,
+        BbsErrorKind::ProofVerification,
+        "Unable to set proof",
+        proof,
+    ) {
+        Err(e) => return e,
+        Ok(v) => v,
+    };
+    let mut error = ExternError::success();
+    bbs_verify_blind_commitment_context_set_proof(
+        handle as u64,
+        ByteArray::from(&proof),
+        &mut error,
+    );
+    check(
+        &env,
+        BbsErrorKind::ProofVerification,
+        "Unable to set proof",
+        error,
+    )
+}
+
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1verify_1blind_1commitment_1context_1finish(
+    env: JNIEnv,
+    _: JObject,
+    handle: jlong,
+) -> jint {
+    let mut error = ExternError::success();
+    let res = bbs_verify_blind_commitment_context_finish(handle as u64, &mut error);
+    match check(
+        &env,
+        BbsErrorKind::ProofVerification,
+        "Unable to verify blind commitment",
+        error,
+    ) {
+        0 => res,
+        e => e,
+    }
 }
 
 fn get_secret_key(env: &JNIEnv, secret_key: jbyteArray) -> Result<SecretKey, jint> {
-    match env.convert_byte_array(secret_key) {
-        Err(_) => Err(0),
-        Ok(s) => {
-            if s.len() != FR_COMPRESSED_SIZE {
-                Err(0)
-            } else {
-                Ok(SecretKey::from(array_ref![s, 0, FR_COMPRESSED_SIZE]))
-            }
+    const CONTEXT: &str = "Unable to convert secret key";
+    let s = read_bytes(env, BbsErrorKind::InvalidKey, CONTEXT, secret_key)?;
+    if s.len() != FR_COMPRESSED_SIZE {
+        throw(
+            env,
+            BbsErrorKind::InvalidKey,
+            &format!(
+                "{}: expected {} bytes, found {}",
+                CONTEXT,
+                FR_COMPRESSED_SIZE,
+                s.len()
+            ),
+        );
+        return Err(1);
+    }
+    Ok(SecretKey::from(array_ref![s, 0, FR_COMPRESSED_SIZE]))
+}
+
+/// Maps the `ProofMessage` type of the Java wrapper, the blinding factor is only read for
+/// hidden messages with an external blinding
+fn get_proof_message_type(
+    env: &JNIEnv,
+    xtype: jint,
+    blinding_factor: jbyteArray,
+) -> Result<(ProofMessageType, Vec<u8>), jint> {
+    const CONTEXT: &str = "Unable to add proof message";
+    match xtype {
+        1 => Ok((ProofMessageType::Revealed, Vec::new())),
+        2 => Ok((ProofMessageType::HiddenProofSpecificBlinding, Vec::new())),
+        3 => Ok((
+            ProofMessageType::HiddenExternalBlinding,
+            read_bytes(env, BbsErrorKind::General, CONTEXT, blinding_factor)?,
+        )),
+        _ => {
+            throw(
+                env,
+                BbsErrorKind::General,
+                &format!("{}: unknown proof message type {}", CONTEXT, xtype),
+            );
+            Err(2)
         }
     }
 }
 
 #[allow(non_snake_case)]
 #[no_mangle]
-pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1get_1total_1messages_1count_1for_1proof(env: JNIEnv, _: JObject, proof: jbyteArray) -> jint {
-    match env.convert_byte_array(proof) {
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1get_1total_1messages_1count_1for_1proof(
+    env: JNIEnv,
+    _: JObject,
+    proof: jbyteArray,
+) -> jint {
+    const CONTEXT: &str = "Unable to get messages count";
+    match read_bytes(&env, BbsErrorKind::ProofVerification, CONTEXT, proof) {
         Err(_) => -1,
-        Ok(s) => {
-            if s.len() < 2 {
+        Ok(s) => match envelope::open(ArtifactType::ProofArtifact, &s) {
+            Err(e) => {
+                throw(
+                    &env,
+                    BbsErrorKind::ProofVerification,
+                    &format!("{}: {}", CONTEXT, e.message()),
+                );
+                -1
+            }
+            Ok(s) if s.len() < 2 => {
+                throw(
+                    &env,
+                    BbsErrorKind::ProofVerification,
+                    &format!("{}: proof is too short", CONTEXT),
+                );
                 -1
-            } else {
-                u16::from_be_bytes(*array_ref![s, 0, 2]) as jint
             }
+            Ok(s) => u16::from_be_bytes(*array_ref![s, 0, 2]) as jint,
+        },
+    }
+}
+
+/// The version, message count, hidden count then the revealed indices of `proof`
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Java_bbs_signatures_Bbs_bbs_1proof_1inspect(
+    env: JNIEnv,
+    _: JObject,
+    proof: jbyteArray,
+) -> jintArray {
+    const CONTEXT: &str = "Unable to inspect proof";
+    let proof = match read_bytes(&env, BbsErrorKind::ProofVerification, CONTEXT, proof) {
+        Err(_) => return ptr::null_mut(),
+        Ok(p) => p,
+    };
+    let mut error = ExternError::success();
+    let (mut message_count, mut hidden_count, mut version) = (0, 0, 0);
+    let mut revealed = ByteBuffer::default();
+    bbs_proof_inspect(
+        ByteArray::from(&proof),
+        &mut message_count,
+        &mut revealed,
+        &mut hidden_count,
+        &mut version,
+        &mut error,
+    );
+    if check(&env, BbsErrorKind::ProofVerification, CONTEXT, error) != 0 {
+        return ptr::null_mut();
+    }
+    let mut info = vec![version as jint, message_count as jint, hidden_count as jint];
+    info.extend(
+        revealed
+            .destroy_into_vec()
+            .chunks(4)
+            .map(|c| u32::from_be_bytes(*array_ref![c, 0, 4]) as jint),
+    );
+    match env.new_int_array(info.len() as jint) {
+        Ok(array) if env.set_int_array_region(array, 0, &info).is_ok() => array,
+        Ok(_) | Err(_) => {
+            throw(&env, BbsErrorKind::General, CONTEXT);
+            ptr::null_mut()
         }
     }
 }
@@ -0,0 +1,457 @@
+use crate::bbs_blind_commitment::*;
+use crate::bbs_blind_sign::*;
+use crate::bbs_create_proof::*;
+use crate::bbs_sign::*;
+use crate::bbs_verify_proof::*;
+use crate::bls::{bls_generate_g2_key, bls_public_key_to_bbs_key};
+use crate::{ByteArray, ProofMessageType};
+use bbs::prelude::PoKOfSignatureProofStatus;
+use ffi_support::{
+    call_with_output, rust_string_to_c, ByteBuffer, ConcurrentHandleMap, ExternError, FfiStr,
+};
+use serde::{de::Error as DError, Deserialize, Deserializer};
+use serde_json::{json, Value};
+use std::os::raw::c_char;
+use zeroize::Zeroize;
+
+/// Bytes encoded as unpadded base64url in requests and responses
+struct Bytes(Vec<u8>);
+
+impl<'de> Deserialize<'de> for Bytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        base64::decode_config(s.trim_end_matches('='), base64::URL_SAFE_NO_PAD)
+            .map(Bytes)
+            .map_err(|e| D::Error::custom(format!("invalid base64url: {}", e)))
+    }
+}
+
+impl Drop for Bytes {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Bytes {
+    fn as_array(&self) -> ByteArray {
+        ByteArray::from_slice(&self.0)
+    }
+}
+
+fn encode(buffer: ByteBuffer) -> Value {
+    Value::String(base64::encode_config(
+        buffer.destroy_into_vec(),
+        base64::URL_SAFE_NO_PAD,
+    ))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IndexedMessage {
+    index: u32,
+    message: Bytes,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProofMessage {
+    message: Bytes,
+    #[serde(default)]
+    revealed: bool,
+    blinding_factor: Option<Bytes>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum Request {
+    #[serde(rename_all = "camelCase")]
+    GenerateKey { seed: Option<Bytes> },
+    #[serde(rename_all = "camelCase")]
+    PublicKeyToBbsKey {
+        public_key: Bytes,
+        message_count: u32,
+    },
+    #[serde(rename_all = "camelCase")]
+    Sign {
+        secret_key: Bytes,
+        public_key: Bytes,
+        messages: Vec<Bytes>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Verify {
+        public_key: Bytes,
+        messages: Vec<Bytes>,
+        signature: Bytes,
+    },
+    #[serde(rename_all = "camelCase")]
+    BlindCommitment {
+        public_key: Bytes,
+        nonce: Bytes,
+        messages: Vec<IndexedMessage>,
+    },
+    #[serde(rename_all = "camelCase")]
+    BlindSign {
+        secret_key: Bytes,
+        public_key: Bytes,
+        commitment: Bytes,
+        messages: Vec<IndexedMessage>,
+    },
+    #[serde(rename_all = "camelCase")]
+    UnblindSignature {
+        blind_signature: Bytes,
+        blinding_factor: Bytes,
+    },
+    #[serde(rename_all = "camelCase")]
+    CreateProof {
+        signature: Bytes,
+        public_key: Bytes,
+        nonce: Bytes,
+        messages: Vec<ProofMessage>,
+    },
+    #[serde(rename_all = "camelCase")]
+    VerifyProof {
+        public_key: Bytes,
+        proof: Bytes,
+        nonce: Bytes,
+        messages: Vec<Bytes>,
+    },
+}
+
+struct JsonError {
+    code: i32,
+    message: String,
+}
+
+impl From<ExternError> for JsonError {
+    fn from(err: ExternError) -> Self {
+        let code = err.get_code().code();
+        let message = unsafe { err.get_and_consume_message() }.unwrap_or_default();
+        JsonError { code, message }
+    }
+}
+
+fn check(err: ExternError) -> Result<(), JsonError> {
+    if err.get_code().is_success() {
+        return Ok(());
+    }
+    Err(err.into())
+}
+
+/// Call an exported function with a local `ExternError` and fail on error
+macro_rules! ffi {
+    ($f:ident($($arg:expr),* $(,)?)) => {{
+        let mut err = ExternError::success();
+        let res = $f($($arg,)* &mut err);
+        check(err)?;
+        res
+    }};
+}
+
+/// Run `f` with the context `handle` in `contexts` and delete it afterwards.
+/// Finish functions that succeed have already deleted it, so this removes it
+/// directly rather than through the deleter, which would record the missing
+/// handle as the last error
+fn in_context<C, T, F: FnOnce(u64) -> Result<T, JsonError>>(
+    contexts: &ConcurrentHandleMap<C>,
+    handle: u64,
+    f: F,
+) -> Result<T, JsonError> {
+    let res = f(handle);
+    let _ = contexts.remove_u64(handle);
+    res
+}
+
+fn generate_key(seed: Option<Bytes>) -> Result<Value, JsonError> {
+    let seed = seed.as_ref().map(Bytes::as_array).unwrap_or_default();
+    let mut public_key = ByteBuffer::default();
+    let mut secret_key = ByteBuffer::default();
+    ffi!(bls_generate_g2_key(seed, &mut public_key, &mut secret_key));
+    Ok(json!({
+        "publicKey": encode(public_key),
+        "secretKey": encode(secret_key),
+    }))
+}
+
+fn public_key_to_bbs_key(public_key: Bytes, message_count: u32) -> Result<Value, JsonError> {
+    let mut bbs_key = ByteBuffer::default();
+    ffi!(bls_public_key_to_bbs_key(
+        public_key.as_array(),
+        message_count,
+        &mut bbs_key
+    ));
+    Ok(json!({ "publicKey": encode(bbs_key) }))
+}
+
+fn sign(secret_key: Bytes, public_key: Bytes, messages: Vec<Bytes>) -> Result<Value, JsonError> {
+    let handle = ffi!(bbs_sign_context_init());
+    in_context(&SIGN_CONTEXT, handle, |handle| {
+        ffi!(bbs_sign_context_set_secret_key(
+            handle,
+            secret_key.as_array()
+        ));
+        ffi!(bbs_sign_context_set_public_key(
+            handle,
+            public_key.as_array()
+        ));
+        for m in &messages {
+            ffi!(bbs_sign_context_add_message_bytes(handle, m.as_array()));
+        }
+        let mut signature = ByteBuffer::default();
+        ffi!(bbs_sign_context_finish(handle, &mut signature));
+        Ok(json!({ "signature": encode(signature) }))
+    })
+}
+
+fn verify(public_key: Bytes, messages: Vec<Bytes>, signature: Bytes) -> Result<Value, JsonError> {
+    let handle = ffi!(bbs_verify_context_init());
+    in_context(&SIGN_CONTEXT, handle, |handle| {
+        ffi!(bbs_verify_context_set_public_key(
+            handle,
+            public_key.as_array()
+        ));
+        ffi!(bbs_verify_context_set_signature(
+            handle,
+            signature.as_array()
+        ));
+        for m in &messages {
+            ffi!(bbs_verify_context_add_message_bytes(handle, m.as_array()));
+        }
+        let verified = ffi!(bbs_verify_context_finish(handle)) == 0;
+        Ok(json!({ "verified": verified }))
+    })
+}
+
+fn blind_commitment(
+    public_key: Bytes,
+    nonce: Bytes,
+    messages: Vec<IndexedMessage>,
+) -> Result<Value, JsonError> {
+    let handle = ffi!(bbs_blind_commitment_context_init());
+    in_context(&BLIND_COMMITMENT_CONTEXT, handle, |handle| {
+        ffi!(bbs_blind_commitment_context_set_public_key(
+            handle,
+            public_key.as_array()
+        ));
+        ffi!(bbs_blind_commitment_context_set_nonce_bytes(
+            handle,
+            nonce.as_array()
+        ));
+        for m in &messages {
+            ffi!(bbs_blind_commitment_context_add_message_bytes(
+                handle,
+                m.index,
+                m.message.as_array()
+            ));
+        }
+        let mut commitment = ByteBuffer::default();
+        let mut context = ByteBuffer::default();
+        let mut blinding_factor = ByteBuffer::default();
+        ffi!(bbs_blind_commitment_context_finish(
+            handle,
+            &mut commitment,
+            &mut context,
+            &mut blinding_factor
+        ));
+        Ok(json!({
+            "commitment": encode(commitment),
+            "context": encode(context),
+            "blindingFactor": encode(blinding_factor),
+        }))
+    })
+}
+
+fn blind_sign(
+    secret_key: Bytes,
+    public_key: Bytes,
+    commitment: Bytes,
+    messages: Vec<IndexedMessage>,
+) -> Result<Value, JsonError> {
+    let handle = ffi!(bbs_blind_sign_context_init());
+    in_context(&BLIND_SIGN_CONTEXT, handle, |handle| {
+        ffi!(bbs_blind_sign_context_set_secret_key(
+            handle,
+            secret_key.as_array()
+        ));
+        ffi!(bbs_blind_sign_context_set_public_key(
+            handle,
+            public_key.as_array()
+        ));
+        ffi!(bbs_blind_sign_context_set_commitment(
+            handle,
+            commitment.as_array()
+        ));
+        for m in &messages {
+            ffi!(bbs_blind_sign_context_add_message_bytes(
+                handle,
+                m.index,
+                m.message.as_array()
+            ));
+        }
+        let mut blind_signature = ByteBuffer::default();
+        ffi!(bbs_blind_sign_context_finish(handle, &mut blind_signature));
+        Ok(json!({ "blindSignature": encode(blind_signature) }))
+    })
+}
+
+fn unblind_signature(blind_signature: Bytes, blinding_factor: Bytes) -> Result<Value, JsonError> {
+    let mut signature = ByteBuffer::default();
+    ffi!(bbs_unblind_signature(
+        blind_signature.as_array(),
+        blinding_factor.as_array(),
+        &mut signature
+    ));
+    Ok(json!({ "signature": encode(signature) }))
+}
+
+fn create_proof(
+    signature: Bytes,
+    public_key: Bytes,
+    nonce: Bytes,
+    messages: Vec<ProofMessage>,
+) -> Result<Value, JsonError> {
+    let handle = ffi!(bbs_create_proof_context_init());
+    in_context(&CREATE_PROOF_CONTEXT, handle, |handle| {
+        ffi!(bbs_create_proof_context_set_signature(
+            handle,
+            signature.as_array()
+        ));
+        ffi!(bbs_create_proof_context_set_public_key(
+            handle,
+            public_key.as_array()
+        ));
+        ffi!(bbs_create_proof_context_set_nonce_bytes(
+            handle,
+            nonce.as_array()
+        ));
+        for m in &messages {
+            let (xtype, blinding_factor) = match (&m.blinding_factor, m.revealed) {
+                (_, true) => (ProofMessageType::Revealed, ByteArray::default()),
+                (None, false) => (
+                    ProofMessageType::HiddenProofSpecificBlinding,
+                    ByteArray::default(),
+                ),
+                (Some(b), false) => (ProofMessageType::HiddenExternalBlinding, b.as_array()),
+            };
+            ffi!(bbs_create_proof_context_add_proof_message_bytes(
+                handle,
+                m.message.as_array(),
+                xtype,
+                blinding_factor
+            ));
+        }
+        let mut proof = ByteBuffer::default();
+        ffi!(bbs_create_proof_context_finish(handle, &mut proof));
+        Ok(json!({ "proof": encode(proof) }))
+    })
+}
+
+fn verify_proof(
+    public_key: Bytes,
+    proof: Bytes,
+    nonce: Bytes,
+    messages: Vec<Bytes>,
+) -> Result<Value, JsonError> {
+    let handle = ffi!(bbs_verify_proof_context_init());
+    in_context(&VERIFY_PROOF_CONTEXT, handle, |handle| {
+        ffi!(bbs_verify_proof_context_set_public_key(
+            handle,
+            public_key.as_array()
+        ));
+        ffi!(bbs_verify_proof_context_set_proof(handle, proof.as_array()));
+        ffi!(bbs_verify_proof_context_set_nonce_bytes(
+            handle,
+            nonce.as_array()
+        ));
+        for m in &messages {
+            ffi!(bbs_verify_proof_context_add_message_bytes(
+                handle,
+                m.as_array()
+            ));
+        }
+        let status = VERIFY_PROOF_CONTEXT
+            .get_u64(handle, |ctx| verify_context(ctx).map_err(ExternError::from))?;
+        Ok(json!({ "verified": matches!(status, PoKOfSignatureProofStatus::Success) }))
+    })
+}
+
+fn call(request: &str) -> Result<Value, JsonError> {
+    let request: Request = serde_json::from_str(request).map_err(|e| JsonError {
+        code: 1,
+        message: format!("Invalid request: {}", e),
+    })?;
+    match request {
+        Request::GenerateKey { seed } => generate_key(seed),
+        Request::PublicKeyToBbsKey {
+            public_key,
+            message_count,
+        } => public_key_to_bbs_key(public_key, message_count),
+        Request::Sign {
+            secret_key,
+            public_key,
+            messages,
+        } => sign(secret_key, public_key, messages),
+        Request::Verify {
+            public_key,
+            messages,
+            signature,
+        } => verify(public_key, messages, signature),
+        Request::BlindCommitment {
+            public_key,
+            nonce,
+            messages,
+        } => blind_commitment(public_key, nonce, messages),
+        Request::BlindSign {
+            secret_key,
+            public_key,
+            commitment,
+            messages,
+        } => blind_sign(secret_key, public_key, commitment, messages),
+        Request::UnblindSignature {
+            blind_signature,
+            blinding_factor,
+        } => unblind_signature(blind_signature, blinding_factor),
+        Request::CreateProof {
+            signature,
+            public_key,
+            nonce,
+            messages,
+        } => create_proof(signature, public_key, nonce, messages),
+        Request::VerifyProof {
+            public_key,
+            proof,
+            nonce,
+            messages,
+        } => verify_proof(public_key, proof, nonce, messages),
+    }
+}
+
+fn respond(response: Result<Value, JsonError>) -> String {
+    match response {
+        Ok(result) => json!({ "result": result }),
+        Err(e) => json!({ "error": { "code": e.code, "message": e.message } }),
+    }
+    .to_string()
+}
+
+/// Run the operation named by `op` in the JSON object `request`. Keys, messages,
+/// signatures, commitments, nonces and proofs are unpadded base64url strings.
+/// Returns `{"result": {...}}` on success or `{"error": {"code": ..., "message": ...}}`.
+/// The response must be freed with `bbs_string_free`
+#[no_mangle]
+pub extern "C" fn bbs_call_json(request: FfiStr<'_>) -> *mut c_char {
+    let mut err = ExternError::success();
+    let response = call_with_output(&mut err, || {
+        respond(match request.as_opt_str() {
+            None => Err(JsonError {
+                code: 1,
+                message: "Request must be a valid UTF-8 string".to_string(),
+            }),
+            Some(request) => call(request),
+        })
+    });
+    if err.get_code().is_success() {
+        return response;
+    }
+    // A panic is caught by `call_with_output` and reported like any other error
+    rust_string_to_c(respond(Err(err.into())))
+}
@@ -0,0 +1,283 @@
+//! JSON Web Proof compact serialization of signatures and proofs.
+//!
+//! An issued JWP is `issuer header . payloads . signature` and a presented JWP is
+//! `presentation header . issuer header . payloads . proof`, each part base64url without padding
+//! and the payloads separated by `~`. The issuer header and payloads are signed as messages
+//! 0, 1, 2, ... so the header is always revealed, and payloads that are not disclosed are empty
+//! in the presented form. BBS+ has no presentation header input, so the proof nonce is the hash
+//! of the presentation header, which must carry the verifier's `nonce`. The `alg` is the
+//! ciphersuite id, the JOSE registered BBS algorithms use a different ciphersuite.
+
+use crate::api::{CreateProofBuilder, Parse, Proof, Result, Signature, VerifyProofBuilder};
+use crate::version::BBS_CIPHERSUITE;
+use crate::BbsFfiError;
+use bbs::prelude::{HashElem, PoKOfSignatureProofStatus, ProofNonce, PublicKey, SignatureMessage};
+use std::collections::{BTreeMap, BTreeSet};
+use zeroize::Zeroize;
+
+fn encode(data: &[u8]) -> String {
+    base64::encode_config(data, base64::URL_SAFE_NO_PAD)
+}
+
+fn decode(part: &str, what: &str) -> Result<Vec<u8>> {
+    base64::decode_config(part, base64::URL_SAFE_NO_PAD)
+        .map_err(|e| BbsFfiError(format!("Invalid JWP {}: {}", what, e)).into())
+}
+
+fn split<'a>(jwp: &'a [u8], parts: usize, what: &str) -> Result<Vec<&'a str>> {
+    let jwp =
+        std::str::from_utf8(jwp).map_err(|_| BbsFfiError(format!("{} JWP must be ASCII", what)))?;
+    let split: Vec<&str> = jwp.split('.').collect();
+    if split.len() != parts {
+        return Err(BbsFfiError(format!(
+            "{} JWP must have {} parts, found {}",
+            what,
+            parts,
+            split.len()
+        ))
+        .into());
+    }
+    Ok(split)
+}
+
+/// Check `header` is a JSON object, with `alg` set to the ciphersuite id for an issuer header
+fn check_header(header: &[u8], what: &str, issuer: bool) -> Result<()> {
+    let value: serde_json::Value = serde_json::from_slice(header)
+        .map_err(|e| BbsFfiError(format!("JWP {} must be JSON: {}", what, e)))?;
+    let object = value
+        .as_object()
+        .ok_or_else(|| BbsFfiError(format!("JWP {} must be a JSON object", what)))?;
+    if issuer && object.get("alg").and_then(|a| a.as_str()) != Some(BBS_CIPHERSUITE) {
+        return Err(BbsFfiError(format!("JWP {} alg must be {}", what, BBS_CIPHERSUITE)).into());
+    }
+    Ok(())
+}
+
+/// The messages to sign for `header` and `payloads`
+pub fn messages<P: AsRef<[u8]>>(header: &[u8], payloads: &[P]) -> Result<Vec<SignatureMessage>> {
+    check_header(header, "issuer header", true)?;
+    if payloads.iter().any(|p| p.as_ref().is_empty()) {
+        return Err(BbsFfiError::new("JWP payloads cannot be empty").into());
+    }
+    Ok(std::iter::once(header)
+        .chain(payloads.iter().map(AsRef::as_ref))
+        .map(SignatureMessage::hash)
+        .collect())
+}
+
+/// The proof nonce for `presentation_header`
+pub fn nonce(presentation_header: &[u8]) -> ProofNonce {
+    ProofNonce::hash(presentation_header)
+}
+
+/// A JWP in the issued form, as received by the holder
+pub struct IssuedJwp {
+    pub header: Vec<u8>,
+    pub payloads: Vec<Vec<u8>>,
+    pub signature: Signature,
+}
+
+impl Drop for IssuedJwp {
+    fn drop(&mut self) {
+        for p in self.payloads.iter_mut() {
+            p.zeroize();
+        }
+    }
+}
+
+impl IssuedJwp {
+    /// Parse the compact serialization
+    pub fn parse(jwp: &[u8]) -> Result<Self> {
+        let parts = split(jwp, 3, "Issued")?;
+        let header = decode(parts[0], "issuer header")?;
+        check_header(&header, "issuer header", true)?;
+        let mut payloads = Vec::new();
+        if !parts[1].is_empty() {
+            for (i, p) in parts[1].split('~').enumerate() {
+                if p.is_empty() {
+                    return Err(BbsFfiError(format!("Issued JWP payload {} is empty", i)).into());
+                }
+                payloads.push(decode(p, "payload")?);
+            }
+        }
+        let signature = Signature::parse(&decode(parts[2], "signature")?)?;
+        Ok(Self {
+            header,
+            payloads,
+            signature,
+        })
+    }
+
+    /// The compact serialization
+    pub fn to_compact(&self) -> String {
+        let payloads: Vec<String> = self.payloads.iter().map(|p| encode(p)).collect();
+        format!(
+            "{}.{}.{}",
+            encode(&self.header),
+            payloads.join("~"),
+            encode(&self.signature.to_bytes_compressed_form())
+        )
+    }
+
+    /// Prove knowledge of the signature for `public_key`, disclosing the payloads at the `disclosed` indices
+    pub fn present(
+        &self,
+        public_key: &PublicKey,
+        presentation_header: &[u8],
+        disclosed: &BTreeSet<usize>,
+    ) -> Result<PresentedJwp> {
+        check_header(presentation_header, "presentation header", false)?;
+        if let Some(i) = disclosed.range(self.payloads.len()..).next() {
+            return Err(BbsFfiError(format!(
+                "Cannot disclose payload {}, the JWP has {} payloads",
+                i,
+                self.payloads.len()
+            ))
+            .into());
+        }
+        let nonce = nonce(presentation_header);
+        let mut builder =
+            CreateProofBuilder::new(public_key, &self.signature, &nonce).revealed(&self.header);
+        for (i, p) in self.payloads.iter().enumerate() {
+            builder = if disclosed.contains(&i) {
+                builder.revealed(p)
+            } else {
+                builder.hidden(p)
+            };
+        }
+        Ok(PresentedJwp {
+            presentation_header: presentation_header.to_vec(),
+            header: self.header.clone(),
+            payloads: self
+                .payloads
+                .iter()
+                .enumerate()
+                .map(|(i, p)| disclosed.get(&i).map(|_| p.clone()))
+                .collect(),
+            proof: builder.create()?,
+        })
+    }
+}
+
+/// A JWP in the presented form, as received by the verifier.
+/// Payloads that are not disclosed are `None`
+#[derive(Debug)]
+pub struct PresentedJwp {
+    pub presentation_header: Vec<u8>,
+    pub header: Vec<u8>,
+    pub payloads: Vec<Option<Vec<u8>>>,
+    pub proof: Proof,
+}
+
+impl PresentedJwp {
+    /// Parse the compact serialization, checking the disclosed payloads match the proof
+    pub fn parse(jwp: &[u8]) -> Result<Self> {
+        let parts = split(jwp, 4, "Presented")?;
+        let presentation_header = decode(parts[0], "presentation header")?;
+        check_header(&presentation_header, "presentation header", false)?;
+        let header = decode(parts[1], "issuer header")?;
+        check_header(&header, "issuer header", true)?;
+        let proof = Proof::parse(&decode(parts[3], "proof")?)?;
+
+        let count = proof
+            .message_count()
+            .checked_sub(1)
+            .ok_or_else(|| BbsFfiError::new("JWP proof must include the issuer header"))?;
+        // A single undisclosed payload and no payloads are both empty
+        let split: Vec<&str> = match (parts[2], count) {
+            ("", 0) => Vec::new(),
+            (p, _) => p.split('~').collect(),
+        };
+        if split.len() != count {
+            return Err(BbsFfiError(format!(
+                "Presented JWP has {} payloads but the proof is for {}",
+                split.len(),
+                count
+            ))
+            .into());
+        }
+        let mut payloads = Vec::new();
+        for p in split {
+            payloads.push(match p {
+                "" => None,
+                p => Some(decode(p, "payload")?),
+            });
+        }
+
+        let jwp = Self {
+            presentation_header,
+            header,
+            payloads,
+            proof,
+        };
+        let disclosed: BTreeSet<usize> = jwp.revealed_messages().keys().copied().collect();
+        let revealed = jwp.proof.revealed();
+        if disclosed != revealed {
+            return Err(BbsFfiError(format!(
+                "Presented JWP discloses messages {:?} but the proof reveals {:?}",
+                disclosed, revealed
+            ))
+            .into());
+        }
+        Ok(jwp)
+    }
+
+    /// The compact serialization
+    pub fn to_compact(&self) -> String {
+        let payloads: Vec<String> = self
+            .payloads
+            .iter()
+            .map(|p| p.as_ref().map(|p| encode(p)).unwrap_or_default())
+            .collect();
+        format!(
+            "{}.{}.{}.{}",
+            encode(&self.presentation_header),
+            encode(&self.header),
+            payloads.join("~"),
+            encode(&self.proof.to_bytes())
+        )
+    }
+
+    /// The proof nonce, the hash of the presentation header
+    pub fn nonce(&self) -> ProofNonce {
+        nonce(&self.presentation_header)
+    }
+
+    /// Check the presentation header `nonce` is the one the verifier sent, so a presentation
+    /// made for another verifier or session cannot be replayed
+    pub fn check_nonce(&self, expected: &str) -> Result<()> {
+        let header: serde_json::Value = serde_json::from_slice(&self.presentation_header)
+            .map_err(|e| BbsFfiError(format!("JWP presentation header must be JSON: {}", e)))?;
+        match header.get("nonce").and_then(|n| n.as_str()) {
+            Some(nonce) if nonce == expected => Ok(()),
+            Some(_) => Err(BbsFfiError::new(
+                "JWP presentation header nonce does not match the expected nonce",
+            )
+            .into()),
+            None => {
+                Err(BbsFfiError::new("JWP presentation header must have a string nonce").into())
+            }
+        }
+    }
+
+    /// The issuer header and disclosed payloads hashed and keyed by message index
+    pub fn revealed_messages(&self) -> BTreeMap<usize, SignatureMessage> {
+        std::iter::once((0, SignatureMessage::hash(&self.header)))
+            .chain(
+                self.payloads
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, p)| p.as_ref().map(|p| (i + 1, SignatureMessage::hash(p)))),
+            )
+            .collect()
+    }
+
+    /// Verify the proof for `public_key` and the `nonce` the verifier sent
+    pub fn verify(&self, public_key: &PublicKey, nonce: &str) -> Result<PoKOfSignatureProofStatus> {
+        self.check_nonce(nonce)?;
+        let nonce = self.nonce();
+        VerifyProofBuilder::new(public_key, &self.proof, &nonce)
+            .prehashed_revealed_messages(self.revealed_messages())
+            .verify()
+    }
+}
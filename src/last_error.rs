@@ -0,0 +1,71 @@
+use ffi_support::{ErrorCode, ExternError};
+use std::{
+    cell::RefCell,
+    mem,
+    ops::{Deref, DerefMut},
+};
+
+thread_local! {
+    static LAST_ERROR: RefCell<(i32, Option<String>)> = const { RefCell::new((0, None)) };
+}
+
+/// The error of an exported function. When the function returns it is recorded
+/// as the last error on this thread and written to the caller's `err`, or released
+/// if the caller passed null
+pub(crate) struct ErrorSlot {
+    out: *mut ExternError,
+    err: ExternError,
+}
+
+impl ErrorSlot {
+    pub(crate) fn new(out: *mut ExternError) -> Self {
+        Self {
+            out,
+            err: ExternError::success(),
+        }
+    }
+}
+
+impl Deref for ErrorSlot {
+    type Target = ExternError;
+
+    fn deref(&self) -> &ExternError {
+        &self.err
+    }
+}
+
+impl DerefMut for ErrorSlot {
+    fn deref_mut(&mut self) -> &mut ExternError {
+        &mut self.err
+    }
+}
+
+impl Drop for ErrorSlot {
+    fn drop(&mut self) {
+        let err = mem::replace(&mut self.err, ExternError::success());
+        let message = err.get_message().as_opt_str().map(String::from);
+        LAST_ERROR.with(|last| *last.borrow_mut() = (err.get_code().code(), message));
+        // Safety: `out` is either null or the caller's `err` pointer
+        match unsafe { self.out.as_mut() } {
+            Some(out) => *out = err,
+            None => unsafe { err.manually_release() },
+        }
+    }
+}
+
+/// Copy the error of the last function called on this thread that takes an `err` parameter
+/// into `err`. Those functions accept a null `err` so callers can check this instead.
+/// The message must be freed with `bbs_string_free`. Returns the error code, 0 on success
+#[no_mangle]
+pub extern "C" fn bbs_get_last_error(err: &mut ExternError) -> i32 {
+    LAST_ERROR.with(|last| {
+        let last = last.borrow();
+        *err = match last.0 {
+            0 => ExternError::success(),
+            code => {
+                ExternError::new_error(ErrorCode::new(code), last.1.clone().unwrap_or_default())
+            }
+        };
+        last.0
+    })
+}
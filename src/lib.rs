@@ -4,6 +4,7 @@ extern crate arrayref;
 extern crate ffi_support;
 #[macro_use]
 extern crate lazy_static;
+extern crate ursa_bbs as bbs;
 
 use bbs::errors::BBSError;
 use bbs::pok_vc::PoKVCError;
@@ -15,8 +16,13 @@ use pairing_plus::{
     serdes::SerDes,
     CurveProjective,
 };
-use rand::prelude::*;
-use std::{ptr, slice};
+use rand::RngCore;
+use rng::BbsRng;
+use std::{
+    ptr, slice,
+    sync::atomic::{compiler_fence, Ordering},
+};
+use zeroize::Zeroize;
 
 /// Used for receiving a ByteBuffer from C that was allocated by either C or Rust.
 /// If Rust allocated, then the outgoing struct is `ffi_support::ByteBuffer`
@@ -83,10 +89,10 @@ impl From<&Vec<u8>> for ByteArray {
     }
 }
 
+/// Leaks `b` so the pointer stays valid, prefer borrowing with `From<&Vec<u8>>`
 impl From<Vec<u8>> for ByteArray {
-    #[allow(clippy::needless_borrow)]
     fn from(b: Vec<u8>) -> Self {
-        Self::from_slice(b)
+        Self::from_slice(Box::leak(b.into_boxed_slice()))
     }
 }
 
@@ -96,10 +102,10 @@ impl From<&[u8]> for ByteArray {
     }
 }
 
+/// Leaks the bytes of `b` so the pointer stays valid, prefer `ByteArray::from_slice`
 impl From<ByteBuffer> for ByteArray {
-    #[allow(clippy::needless_borrow)]
     fn from(b: ByteBuffer) -> Self {
-        Self::from_slice(b.destroy_into_vec())
+        Self::from(b.destroy_into_vec())
     }
 }
 
@@ -114,7 +120,22 @@ pub enum ProofMessageType {
 define_string_destructor!(bbs_string_free);
 define_bytebuffer_destructor!(bbs_byte_buffer_free);
 
+/// Overwrite a buffer holding secret material such as a secret key,
+/// blinding factor or commitment context with zeros before freeing it
+#[no_mangle]
+pub extern "C" fn bbs_byte_buffer_free_secure(buffer: ByteBuffer) {
+    buffer.destroy_into_vec().zeroize();
+}
+
+/// Overwrite `value` with `zero` in a way the compiler will not optimize away.
+/// For types from `bbs` whose fields cannot be zeroized directly
+pub(crate) fn zeroize_with<T>(value: &mut T, zero: T) {
+    unsafe { ptr::write_volatile(value, zero) };
+    compiler_fence(Ordering::SeqCst);
+}
+
 /// Wrapper to convert a string to ExternError and BBSError
+#[derive(Debug)]
 pub(crate) struct BbsFfiError(pub String);
 
 impl BbsFfiError {
@@ -196,9 +217,8 @@ fn bls_generate_keypair<G: CurveProjective<Engine = Bls12, Scalar = Fr> + SerDes
 ) -> (Option<Vec<u8>>, Vec<u8>, Vec<u8>) {
     let passed_seed = ikm.is_some();
     let seed = ikm.unwrap_or_else(|| {
-        let mut rng = thread_rng();
-        let mut seed_data = vec![0u8, 32];
-        rng.fill_bytes(seed_data.as_mut_slice());
+        let mut seed_data = vec![0u8; 32];
+        BbsRng.fill_bytes(seed_data.as_mut_slice());
         seed_data
     });
 
@@ -213,9 +233,8 @@ fn bls_generate_keypair<G: CurveProjective<Engine = Bls12, Scalar = Fr> + SerDes
             if passed_seed {
                 data.extend_from_slice(seed.as_slice());
             } else {
-                let mut rng = thread_rng();
-                let mut blinding_factor = vec![0u8, 32];
-                rng.fill_bytes(blinding_factor.as_mut_slice());
+                let mut blinding_factor = vec![0u8; 32];
+                BbsRng.fill_bytes(blinding_factor.as_mut_slice());
                 data.extend_from_slice(blinding_factor.as_slice());
             }
             let mut blinding_g = G::deserialize(&mut gg, true).unwrap();
@@ -254,13 +273,58 @@ fn gen_sk(msg: &[u8]) -> Fr {
 
 #[macro_use]
 mod macros;
+pub mod api;
 pub mod bbs_blind_commitment;
 pub mod bbs_blind_sign;
 pub mod bbs_create_proof;
 pub mod bbs_sign;
 pub mod bbs_verify_proof;
 pub mod bbs_verify_sign_proof;
+mod blinded_key;
+mod bls_ciphersuite;
 pub mod bls;
+pub mod bls_sign;
+#[cfg(feature = "cose")]
+pub mod cose;
+pub mod envelope;
+pub mod json;
+#[cfg(feature = "jwp")]
+pub mod jwp;
+pub mod last_error;
+pub mod parse;
+mod proof;
+pub mod rng;
+pub mod signer;
+mod threshold;
+pub mod validate;
+pub mod version;
 
 #[cfg(any(target_os = "android", feature = "java"))]
 pub mod java;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "napi")]
+pub mod napi;
+
+#[cfg(feature = "python")]
+pub mod python;
+
+#[cfg(feature = "uniffi")]
+pub mod uniffi;
+// The UniFFI scaffolding expects its type tag at the crate root
+#[cfg(feature = "uniffi")]
+use self::uniffi::UniFfiTag;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_array_from_owned_bytes() {
+        assert_eq!(ByteArray::from(vec![1u8, 2, 3]).to_vec(), vec![1, 2, 3]);
+        let buffer = ByteBuffer::from_vec(vec![4u8, 5]);
+        assert_eq!(ByteArray::from(buffer).to_vec(), vec![4, 5]);
+    }
+}
@@ -4,21 +4,34 @@ macro_rules! add_message_impl {
      $name_bytes:ident,
      $name_prehash:ident,
      $static:expr
+    ) => {
+        add_message_impl!($name_string, $name_bytes, $name_prehash, $static, check = |_| Ok(()));
+    };
+    (
+     $name_string:ident,
+     $name_bytes:ident,
+     $name_prehash:ident,
+     $static:expr,
+     check = $check:expr
     ) => {
         #[no_mangle]
         pub extern "C" fn $name_string(
             handle: u64,
             message: FfiStr<'_>,
-            err: &mut ExternError,
+            err: *mut ExternError,
         ) -> i32 {
+            let err: &mut ExternError = &mut $crate::last_error::ErrorSlot::new(err);
             let message = message.into_string();
             if message.is_empty() {
                 *err = ExternError::new_error(ErrorCode::new(1), "Message cannot be empty");
                 return 1;
             }
-            $static.call_with_output_mut(err, handle, |ctx| {
+            $static.call_with_result_mut(err, handle, |ctx| -> Result<(), BbsFfiError> {
+                let checked: Result<(), BbsFfiError> = ($check)(&*ctx);
+                checked?;
                 ctx.messages
                     .push(SignatureMessage::hash(message.as_bytes()));
+                Ok(())
             });
             err.get_code().code()
         }
@@ -27,15 +40,19 @@ macro_rules! add_message_impl {
         pub extern "C" fn $name_bytes(
             handle: u64,
             message: ByteArray,
-            err: &mut ExternError,
+            err: *mut ExternError,
         ) -> i32 {
+            let err: &mut ExternError = &mut $crate::last_error::ErrorSlot::new(err);
             let message = message.to_vec();
             if message.is_empty() {
                 *err = ExternError::new_error(ErrorCode::new(1), "Message cannot be empty");
                 return 1;
             }
-            $static.call_with_output_mut(err, handle, |ctx| {
+            $static.call_with_result_mut(err, handle, |ctx| -> Result<(), BbsFfiError> {
+                let checked: Result<(), BbsFfiError> = ($check)(&*ctx);
+                checked?;
                 ctx.messages.push(SignatureMessage::hash(&message));
+                Ok(())
             });
             err.get_code().code()
         }
@@ -44,15 +61,18 @@ macro_rules! add_message_impl {
         pub extern "C" fn $name_prehash(
             handle: u64,
             message: ByteArray,
-            err: &mut ExternError,
+            err: *mut ExternError,
         ) -> i32 {
+            let err: &mut ExternError = &mut $crate::last_error::ErrorSlot::new(err);
             let message = message.to_vec();
             if message.is_empty() {
                 *err = ExternError::new_error(ErrorCode::new(1), "Message cannot be empty");
                 return 1;
             }
             $static.call_with_result_mut(err, handle, |ctx| -> Result<(), BbsFfiError> {
-                let msg = SignatureMessage::try_from(message)?;
+                let checked: Result<(), BbsFfiError> = ($check)(&*ctx);
+                checked?;
+                let msg = <SignatureMessage as $crate::api::Parse>::parse(&message)?;
                 ctx.messages.push(msg);
                 Ok(())
             });
@@ -71,8 +91,9 @@ macro_rules! add_message_impl {
             handle: u64,
             index: $index,
             message: FfiStr<'_>,
-            err: &mut ExternError,
+            err: *mut ExternError,
         ) -> i32 {
+            let err: &mut ExternError = &mut $crate::last_error::ErrorSlot::new(err);
             let message = message.into_string();
             if message.is_empty() {
                 *err = ExternError::new_error(ErrorCode::new(1), "Message cannot be empty");
@@ -90,8 +111,9 @@ macro_rules! add_message_impl {
             handle: u64,
             index: $index,
             message: ByteArray,
-            err: &mut ExternError,
+            err: *mut ExternError,
         ) -> i32 {
+            let err: &mut ExternError = &mut $crate::last_error::ErrorSlot::new(err);
             let message = message.to_vec();
             if message.is_empty() {
                 *err = ExternError::new_error(ErrorCode::new(1), "Message cannot be empty");
@@ -109,15 +131,16 @@ macro_rules! add_message_impl {
             handle: u64,
             index: $index,
             message: ByteArray,
-            err: &mut ExternError,
+            err: *mut ExternError,
         ) -> i32 {
+            let err: &mut ExternError = &mut $crate::last_error::ErrorSlot::new(err);
             let message = message.to_vec();
             if message.is_empty() {
                 *err = ExternError::new_error(ErrorCode::new(1), "Message cannot be empty");
                 return 1;
             }
             $static.call_with_result_mut(err, handle, |ctx| -> Result<(), BbsFfiError> {
-                let msg = SignatureMessage::try_from(message)?;
+                let msg = <SignatureMessage as $crate::api::Parse>::parse(&message)?;
                 ctx.messages.insert(index as usize, msg);
                 Ok(())
             });
@@ -129,7 +152,8 @@ macro_rules! add_message_impl {
 macro_rules! add_bytes_impl {
     ($name:ident,$static:expr,$property:ident,$type:ident) => {
         #[no_mangle]
-        pub extern "C" fn $name(handle: u64, value: ByteArray, err: &mut ExternError) -> i32 {
+        pub extern "C" fn $name(handle: u64, value: ByteArray, err: *mut ExternError) -> i32 {
+            let err: &mut ExternError = &mut $crate::last_error::ErrorSlot::new(err);
             let value = value.to_vec();
             if value.is_empty() {
                 *err = ExternError::new_error(
@@ -139,7 +163,7 @@ macro_rules! add_bytes_impl {
                 return 1;
             }
             $static.call_with_result_mut(err, handle, |ctx| -> Result<(), BbsFfiError> {
-                let v = $type::try_from(value)?;
+                let v = <$type as $crate::api::Parse>::parse(&value)?;
                 ctx.$property = Some(v);
                 Ok(())
             });
@@ -157,8 +181,9 @@ macro_rules! add_bytes_impl {
         pub extern "C" fn $name_string(
             handle: u64,
             message: FfiStr<'_>,
-            err: &mut ExternError,
+            err: *mut ExternError,
         ) -> i32 {
+            let err: &mut ExternError = &mut $crate::last_error::ErrorSlot::new(err);
             let message = message.into_string();
             if message.is_empty() {
                 *err = ExternError::new_error(ErrorCode::new(1), "Message cannot be empty");
@@ -171,7 +196,8 @@ macro_rules! add_bytes_impl {
         }
 
         #[no_mangle]
-        pub extern "C" fn $name_bytes(handle: u64, value: ByteArray, err: &mut ExternError) -> i32 {
+        pub extern "C" fn $name_bytes(handle: u64, value: ByteArray, err: *mut ExternError) -> i32 {
+            let err: &mut ExternError = &mut $crate::last_error::ErrorSlot::new(err);
             let value = value.to_vec();
             if value.is_empty() {
                 *err = ExternError::new_error(
@@ -190,8 +216,9 @@ macro_rules! add_bytes_impl {
         pub extern "C" fn $name_prehash(
             handle: u64,
             value: ByteArray,
-            err: &mut ExternError,
+            err: *mut ExternError,
         ) -> i32 {
+            let err: &mut ExternError = &mut $crate::last_error::ErrorSlot::new(err);
             let value = value.to_vec();
             if value.is_empty() {
                 *err = ExternError::new_error(
@@ -201,7 +228,7 @@ macro_rules! add_bytes_impl {
                 return 1;
             }
             $static.call_with_result_mut(err, handle, |ctx| -> Result<(), BbsFfiError> {
-                let v = $type::try_from(value)?;
+                let v = <$type as $crate::api::Parse>::parse(&value)?;
                 ctx.$property = Some(v);
                 Ok(())
             });
@@ -223,8 +250,9 @@ macro_rules! add_proof_message_impl {
             message: FfiStr<'_>,
             xtype: ProofMessageType,
             blinding_factor: ByteArray,
-            err: &mut ExternError,
+            err: *mut ExternError,
         ) -> i32 {
+            let err: &mut ExternError = &mut $crate::last_error::ErrorSlot::new(err);
             let message = message.into_string();
             if message.is_empty() {
                 *err = ExternError::new_error(ErrorCode::new(1), "Message cannot be empty");
@@ -263,8 +291,9 @@ macro_rules! add_proof_message_impl {
             message: ByteArray,
             xtype: ProofMessageType,
             blinding_factor: ByteArray,
-            err: &mut ExternError,
+            err: *mut ExternError,
         ) -> i32 {
+            let err: &mut ExternError = &mut $crate::last_error::ErrorSlot::new(err);
             let message = message.to_vec();
             if message.is_empty() {
                 *err = ExternError::new_error(ErrorCode::new(1), "Message cannot be empty");
@@ -303,8 +332,9 @@ macro_rules! add_proof_message_impl {
             message: ByteArray,
             xtype: ProofMessageType,
             blinding_factor: ByteArray,
-            err: &mut ExternError,
+            err: *mut ExternError,
         ) -> i32 {
+            let err: &mut ExternError = &mut $crate::last_error::ErrorSlot::new(err);
             let message = message.to_vec();
             if message.is_empty() {
                 *err = ExternError::new_error(ErrorCode::new(1), "Message cannot be empty");
@@ -318,16 +348,16 @@ macro_rules! add_proof_message_impl {
             $static.call_with_result_mut(err, handle, |ctx| -> Result<(), BbsFfiError> {
                 let m = match xtype {
                     ProofMessageType::Revealed => {
-                        ProofMessage::Revealed(SignatureMessage::try_from(message.as_slice())?)
+                        ProofMessage::Revealed(<SignatureMessage as $crate::api::Parse>::parse(&message)?)
                     }
                     ProofMessageType::HiddenProofSpecificBlinding => {
                         ProofMessage::Hidden(HiddenMessage::ProofSpecificBlinding(
-                            SignatureMessage::try_from(message.as_slice())?,
+                            <SignatureMessage as $crate::api::Parse>::parse(&message)?,
                         ))
                     }
                     ProofMessageType::HiddenExternalBlinding => {
                         ProofMessage::Hidden(HiddenMessage::ExternalBlinding(
-                            SignatureMessage::try_from(message.as_slice())?,
+                            <SignatureMessage as $crate::api::Parse>::parse(&message)?,
                             ProofNonce::hash(bf.as_slice()),
                         ))
                     }
@@ -340,15 +370,30 @@ macro_rules! add_proof_message_impl {
     };
 }
 
+/// Like `ffi_support::define_handle_map_deleter` but records the last error
+/// and accepts a null `err`
+macro_rules! define_handle_deleter {
+    ($static:ident, $name:ident) => {
+        #[no_mangle]
+        pub extern "C" fn $name(v: u64, err: *mut ExternError) {
+            let err: &mut ExternError = &mut $crate::last_error::ErrorSlot::new(err);
+            ffi_support::call_with_result(err, || $static.delete_u64(v))
+        }
+    };
+}
+
 #[cfg(any(target_os = "android", feature = "java"))]
 macro_rules! copy_to_jni {
     ($env:expr, $var:expr, $from:expr) => {
-        if $env.set_byte_array_region($var, 0, $from).is_err() {
-            return 0;
-        }
+        copy_to_jni!($env, $var, $from, 1)
     };
     ($env:expr, $var:expr, $from:expr, $val:expr) => {
-        if $env.set_byte_array_region($var, 0, $from).is_err() {
+        if let Err(e) = $env.set_byte_array_region($var, 0, $from) {
+            throw(
+                &$env,
+                BbsErrorKind::General,
+                &format!("Unable to copy the result: {}", e),
+            );
             return $val;
         }
     };
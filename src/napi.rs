@@ -0,0 +1,328 @@
+//! Node.js exports over the `api` builders, built as a native addon with N-API.
+//!
+//! Binary inputs and outputs are `Buffer`s in the same byte formats as the C ABI and errors
+//! are thrown as JS `Error`s. `createProofAsync` and `verifyProofAsync` run on the libuv
+//! thread pool and return a `Promise`.
+
+use crate::api::{
+    self, BlindCommitmentBuilder, BlindSignBuilder, BlindSignature, BlindSignatureContext,
+    Commitment, CreateProofBuilder, DeterministicPublicKey, HashElem, HiddenMessage, Parse, Proof,
+    ProofMessage, ProofNonce, PublicKey, SecretKey, SignBuilder, Signature, SignatureBlinding,
+    SignatureMessage, ToVariableLengthBytes, VerifyBlindCommitmentBuilder, VerifyBuilder,
+    VerifyProofBuilder,
+};
+use napi::{bindgen_prelude::*, Env, Task};
+use napi_derive::napi;
+use std::collections::BTreeSet;
+
+impl From<api::Error> for napi::Error {
+    fn from(e: api::Error) -> Self {
+        napi::Error::from_reason(e.message())
+    }
+}
+
+fn parse<T: Parse>(bytes: &[u8]) -> Result<T> {
+    Ok(T::parse(bytes)?)
+}
+
+fn hash_messages<M: AsRef<[u8]>>(messages: &[M]) -> Vec<SignatureMessage> {
+    messages.iter().map(SignatureMessage::hash).collect()
+}
+
+fn indexed_messages(
+    indices: &[u32],
+    messages: &[Buffer],
+) -> Result<Vec<(usize, SignatureMessage)>> {
+    if indices.len() != messages.len() {
+        return Err(napi::Error::from_reason(
+            "Indices and messages are not equal",
+        ));
+    }
+    Ok(indices
+        .iter()
+        .map(|i| *i as usize)
+        .zip(hash_messages(messages))
+        .collect())
+}
+
+/// A BLS key pair with the public key in G2
+#[napi(object)]
+pub struct KeyPair {
+    pub public_key: Buffer,
+    pub secret_key: Buffer,
+}
+
+/// The holder's output from `blindCommitment`
+#[napi(object)]
+pub struct BlindCommitment {
+    pub commitment: Buffer,
+    pub context: Buffer,
+    pub blinding_factor: Buffer,
+}
+
+/// Generate a BLS key pair in G2, deterministically from `seed` if set
+#[napi]
+pub fn generate_key(seed: Option<Buffer>) -> Result<KeyPair> {
+    let (pk, sk) = api::generate_key(seed.as_deref())?;
+    Ok(KeyPair {
+        public_key: pk.to_bytes_compressed_form().to_vec().into(),
+        secret_key: sk.to_bytes_compressed_form().to_vec().into(),
+    })
+}
+
+/// Convert a BLS public key to a BBS public key for `message_count` messages
+#[napi]
+pub fn bls_public_key_to_bbs_key(public_key: Buffer, message_count: u32) -> Result<Buffer> {
+    let dpk = parse::<DeterministicPublicKey>(&public_key)?;
+    let pk = dpk
+        .to_public_key(message_count as usize)
+        .map_err(|e| napi::Error::from_reason(format!("{:?}", e)))?;
+    Ok(pk.to_bytes_compressed_form().into())
+}
+
+/// Sign `messages`
+#[napi]
+pub fn sign(secret_key: Buffer, public_key: Buffer, messages: Vec<Buffer>) -> Result<Buffer> {
+    let sk = parse::<SecretKey>(&secret_key)?;
+    let pk = parse::<PublicKey>(&public_key)?;
+    let signature = SignBuilder::new(&pk)
+        .secret_key(&sk)
+        .prehashed_messages(hash_messages(&messages))
+        .sign()?;
+    Ok(signature.to_bytes_compressed_form().to_vec().into())
+}
+
+/// `true` if `signature` is valid for `messages`
+#[napi]
+pub fn verify(public_key: Buffer, signature: Buffer, messages: Vec<Buffer>) -> Result<bool> {
+    let pk = parse::<PublicKey>(&public_key)?;
+    let signature = parse::<Signature>(&signature)?;
+    Ok(VerifyBuilder::new(&pk, &signature)
+        .prehashed_messages(hash_messages(&messages))
+        .verify()?)
+}
+
+/// Commit to the hidden `messages` at `indices`
+#[napi]
+pub fn blind_commitment(
+    public_key: Buffer,
+    nonce: Buffer,
+    indices: Vec<u32>,
+    messages: Vec<Buffer>,
+) -> Result<BlindCommitment> {
+    let pk = parse::<PublicKey>(&public_key)?;
+    let nonce = ProofNonce::hash(&nonce);
+    let res = BlindCommitmentBuilder::new(&pk, &nonce)
+        .prehashed_messages(indexed_messages(&indices, &messages)?)
+        .commit()?;
+    Ok(BlindCommitment {
+        commitment: res.commitment.to_bytes_compressed_form().to_vec().into(),
+        context: res.context.to_bytes_compressed_form().into(),
+        blinding_factor: res
+            .blinding_factor
+            .to_bytes_compressed_form()
+            .to_vec()
+            .into(),
+    })
+}
+
+/// `true` if the proof in the commitment `context` is valid for the `blinded` indices
+#[napi]
+pub fn verify_blind_commitment(
+    public_key: Buffer,
+    context: Buffer,
+    nonce: Buffer,
+    blinded: Vec<u32>,
+) -> Result<bool> {
+    let pk = parse::<PublicKey>(&public_key)?;
+    let context = parse::<BlindSignatureContext>(&context)?;
+    let nonce = ProofNonce::hash(&nonce);
+    Ok(blinded
+        .iter()
+        .fold(
+            VerifyBlindCommitmentBuilder::new(&pk, &context, &nonce),
+            |builder, i| builder.blinded(*i as usize),
+        )
+        .verify()?)
+}
+
+/// Sign the holder's `commitment` and the known `messages` at `indices`
+#[napi]
+pub fn blind_sign(
+    secret_key: Buffer,
+    public_key: Buffer,
+    commitment: Buffer,
+    indices: Vec<u32>,
+    messages: Vec<Buffer>,
+) -> Result<Buffer> {
+    let sk = parse::<SecretKey>(&secret_key)?;
+    let pk = parse::<PublicKey>(&public_key)?;
+    let commitment = parse::<Commitment>(&commitment)?;
+    let signature = BlindSignBuilder::new(&pk, &commitment)
+        .secret_key(&sk)
+        .prehashed_messages(indexed_messages(&indices, &messages)?)
+        .sign()?;
+    Ok(signature.to_bytes_compressed_form().to_vec().into())
+}
+
+/// Unblind `blind_signature` with the holder's `blinding_factor`
+#[napi]
+pub fn unblind_signature(blind_signature: Buffer, blinding_factor: Buffer) -> Result<Buffer> {
+    let blind_signature = parse::<BlindSignature>(&blind_signature)?;
+    let blinding_factor = parse::<SignatureBlinding>(&blinding_factor)?;
+    let signature = api::unblind_signature(&blind_signature, &blinding_factor);
+    Ok(signature.to_bytes_compressed_form().to_vec().into())
+}
+
+/// The inputs to `createProof` copied out of their `Buffer`s so they can leave the JS thread
+pub struct CreateProofTask {
+    signature: Vec<u8>,
+    public_key: Vec<u8>,
+    nonce: Vec<u8>,
+    messages: Vec<Vec<u8>>,
+    revealed: Vec<u32>,
+}
+
+impl CreateProofTask {
+    fn new(
+        signature: Buffer,
+        public_key: Buffer,
+        nonce: Buffer,
+        messages: Vec<Buffer>,
+        revealed: Vec<u32>,
+    ) -> Self {
+        Self {
+            signature: signature.to_vec(),
+            public_key: public_key.to_vec(),
+            nonce: nonce.to_vec(),
+            messages: messages.iter().map(|m| m.to_vec()).collect(),
+            revealed,
+        }
+    }
+
+    fn create(&self) -> Result<Vec<u8>> {
+        let signature = parse::<Signature>(&self.signature)?;
+        let pk = parse::<PublicKey>(&self.public_key)?;
+        let nonce = ProofNonce::hash(&self.nonce);
+        let revealed: BTreeSet<usize> = self.revealed.iter().map(|i| *i as usize).collect();
+        let messages = hash_messages(&self.messages)
+            .into_iter()
+            .enumerate()
+            .map(|(i, m)| {
+                if revealed.contains(&i) {
+                    ProofMessage::Revealed(m)
+                } else {
+                    ProofMessage::Hidden(HiddenMessage::ProofSpecificBlinding(m))
+                }
+            });
+        let proof = CreateProofBuilder::new(&pk, &signature, &nonce)
+            .proof_messages(messages)
+            .create()?;
+        Ok(proof.to_bytes())
+    }
+}
+
+impl Task for CreateProofTask {
+    type Output = Vec<u8>;
+    type JsValue = Buffer;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        self.create()
+    }
+
+    fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output.into())
+    }
+}
+
+/// Prove knowledge of `signature` over all signed `messages`, revealing the messages at `revealed`
+#[napi]
+pub fn create_proof(
+    signature: Buffer,
+    public_key: Buffer,
+    nonce: Buffer,
+    messages: Vec<Buffer>,
+    revealed: Vec<u32>,
+) -> Result<Buffer> {
+    CreateProofTask::new(signature, public_key, nonce, messages, revealed)
+        .create()
+        .map(Buffer::from)
+}
+
+/// `createProof` off the event loop
+#[napi(ts_return_type = "Promise<Buffer>")]
+pub fn create_proof_async(
+    signature: Buffer,
+    public_key: Buffer,
+    nonce: Buffer,
+    messages: Vec<Buffer>,
+    revealed: Vec<u32>,
+) -> AsyncTask<CreateProofTask> {
+    AsyncTask::new(CreateProofTask::new(
+        signature, public_key, nonce, messages, revealed,
+    ))
+}
+
+/// The inputs to `verifyProof` copied out of their `Buffer`s so they can leave the JS thread
+pub struct VerifyProofTask {
+    public_key: Vec<u8>,
+    proof: Vec<u8>,
+    nonce: Vec<u8>,
+    messages: Vec<Vec<u8>>,
+}
+
+impl VerifyProofTask {
+    fn new(public_key: Buffer, proof: Buffer, nonce: Buffer, messages: Vec<Buffer>) -> Self {
+        Self {
+            public_key: public_key.to_vec(),
+            proof: proof.to_vec(),
+            nonce: nonce.to_vec(),
+            messages: messages.iter().map(|m| m.to_vec()).collect(),
+        }
+    }
+
+    fn verify(&self) -> Result<bool> {
+        let pk = parse::<PublicKey>(&self.public_key)?;
+        let proof = parse::<Proof>(&self.proof)?;
+        let nonce = ProofNonce::hash(&self.nonce);
+        let status = VerifyProofBuilder::new(&pk, &proof, &nonce)
+            .prehashed_messages(hash_messages(&self.messages))
+            .verify()?;
+        Ok(status.is_valid())
+    }
+}
+
+impl Task for VerifyProofTask {
+    type Output = bool;
+    type JsValue = bool;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        self.verify()
+    }
+
+    fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+/// `true` if `proof` is valid for the revealed `messages` in index order
+#[napi]
+pub fn verify_proof(
+    public_key: Buffer,
+    proof: Buffer,
+    nonce: Buffer,
+    messages: Vec<Buffer>,
+) -> Result<bool> {
+    VerifyProofTask::new(public_key, proof, nonce, messages).verify()
+}
+
+/// `verifyProof` off the event loop
+#[napi(ts_return_type = "Promise<boolean>")]
+pub fn verify_proof_async(
+    public_key: Buffer,
+    proof: Buffer,
+    nonce: Buffer,
+    messages: Vec<Buffer>,
+) -> AsyncTask<VerifyProofTask> {
+    AsyncTask::new(VerifyProofTask::new(public_key, proof, nonce, messages))
+}
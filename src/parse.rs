@@ -0,0 +1,166 @@
+//! Length-checked parsing of untrusted bytes.
+//!
+//! The `TryFrom` impls in `bbs` trust the counts and lengths embedded in public keys,
+//! proofs and commitment contexts, so a short or forged value can panic or request a
+//! huge allocation. `Parse` checks the layout first and only then hands the bytes to `bbs`.
+
+use crate::api::Result;
+use crate::envelope::{open, ArtifactType};
+use crate::BbsFfiError;
+use bbs::prelude::*;
+use std::convert::TryFrom;
+
+/// Parse a value from bytes received over the FFI or from another party
+pub trait Parse: Sized {
+    /// Parse `data`, returning an error for malformed input instead of panicking
+    fn parse(data: &[u8]) -> Result<Self>;
+}
+
+fn invalid_length(name: &str, expected: String, found: usize) -> BbsFfiError {
+    BbsFfiError(format!(
+        "{} must be {} bytes, found {}",
+        name, expected, found
+    ))
+}
+
+fn check_length(name: &str, data: &[u8], sizes: &[usize]) -> Result<()> {
+    if sizes.contains(&data.len()) {
+        return Ok(());
+    }
+    let expected = sizes
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>()
+        .join(" or ");
+    Err(invalid_length(name, expected, data.len()).into())
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<usize> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_be_bytes(*array_ref![b, 0, 4]) as usize)
+}
+
+/// Check a compressed `ProofG1`: commitment (G1) || response count (u32) || responses (Fr)
+fn check_proof_g1(name: &str, data: &[u8]) -> Result<()> {
+    let count = read_u32(data, G1_COMPRESSED_SIZE).ok_or_else(|| {
+        invalid_length(
+            name,
+            format!("at least {}", G1_COMPRESSED_SIZE + 4),
+            data.len(),
+        )
+    })?;
+    let expected = count
+        .checked_mul(FR_COMPRESSED_SIZE)
+        .and_then(|n| n.checked_add(G1_COMPRESSED_SIZE + 4));
+    if expected != Some(data.len()) {
+        return Err(BbsFfiError(format!(
+            "{} declares {} responses but is {} bytes",
+            name,
+            count,
+            data.len()
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+macro_rules! parse_fixed_impl {
+    ($($type:ident, $name:expr, [$($size:expr),+];)+) => {
+        $(
+            impl Parse for $type {
+                fn parse(data: &[u8]) -> Result<Self> {
+                    check_length($name, data, &[$($size),+])?;
+                    Ok($type::try_from(data)?)
+                }
+            }
+        )+
+    };
+    ($($type:ident, $name:expr, [$($size:expr),+], $kind:expr;)+) => {
+        $(
+            impl Parse for $type {
+                fn parse(data: &[u8]) -> Result<Self> {
+                    let data = open($kind, data)?;
+                    check_length($name, data, &[$($size),+])?;
+                    Ok($type::try_from(data)?)
+                }
+            }
+        )+
+    };
+}
+
+parse_fixed_impl!(
+    SecretKey, "Secret key", [FR_COMPRESSED_SIZE];
+    DeterministicPublicKey, "Public key", [G2_COMPRESSED_SIZE];
+    SignatureBlinding, "Blinding factor", [FR_COMPRESSED_SIZE];
+    SignatureMessage, "Message", [FR_COMPRESSED_SIZE];
+    ProofNonce, "Nonce", [FR_COMPRESSED_SIZE];
+);
+
+parse_fixed_impl!(
+    Signature, "Signature", [SIGNATURE_COMPRESSED_SIZE], ArtifactType::SignatureArtifact;
+    BlindSignature, "Blind signature", [SIGNATURE_COMPRESSED_SIZE], ArtifactType::BlindSignatureArtifact;
+    Commitment, "Commitment", [G1_COMPRESSED_SIZE], ArtifactType::CommitmentArtifact;
+);
+
+/// w (G2) || h0 (G1) || generator count (u32) || generators (G1)
+impl Parse for PublicKey {
+    fn parse(data: &[u8]) -> Result<Self> {
+        let data = open(ArtifactType::PublicKeyArtifact, data)?;
+        const HEADER: usize = G2_COMPRESSED_SIZE + G1_COMPRESSED_SIZE + 4;
+        let count = read_u32(data, HEADER - 4).ok_or_else(|| {
+            invalid_length("Public key", format!("at least {}", HEADER), data.len())
+        })?;
+        let expected = count
+            .checked_mul(G1_COMPRESSED_SIZE)
+            .and_then(|n| n.checked_add(HEADER));
+        if expected != Some(data.len()) {
+            return Err(BbsFfiError(format!(
+                "Public key declares {} message generators but is {} bytes",
+                count,
+                data.len()
+            ))
+            .into());
+        }
+        Ok(PublicKey::try_from(data)?)
+    }
+}
+
+/// commitment (G1) || challenge (Fr) || proof of hidden messages (`ProofG1`)
+impl Parse for BlindSignatureContext {
+    fn parse(data: &[u8]) -> Result<Self> {
+        let data = open(ArtifactType::BlindSignatureContextArtifact, data)?;
+        const HEADER: usize = G1_COMPRESSED_SIZE + FR_COMPRESSED_SIZE;
+        if data.len() < HEADER {
+            return Err(invalid_length(
+                "Commitment context",
+                format!("at least {}", HEADER),
+                data.len(),
+            )
+            .into());
+        }
+        check_proof_g1("Commitment context proof", &data[HEADER..])?;
+        Ok(BlindSignatureContext::try_from(data)?)
+    }
+}
+
+/// A' (G1) || A-bar (G1) || d (G1) || first proof length (u32) || first proof || second proof
+impl Parse for PoKOfSignatureProof {
+    fn parse(data: &[u8]) -> Result<Self> {
+        const HEADER: usize = 3 * G1_COMPRESSED_SIZE + 4;
+        let length = read_u32(data, HEADER - 4)
+            .ok_or_else(|| invalid_length("Proof", format!("at least {}", HEADER), data.len()))?;
+        let end = HEADER
+            .checked_add(length)
+            .filter(|end| *end <= data.len())
+            .ok_or_else(|| {
+                BbsFfiError(format!(
+                    "Proof declares a {} byte first proof but is {} bytes",
+                    length,
+                    data.len()
+                ))
+            })?;
+        check_proof_g1("First sub-proof", &data[HEADER..end])?;
+        check_proof_g1("Second sub-proof", &data[end..])?;
+        Ok(PoKOfSignatureProof::try_from(data)?)
+    }
+}
@@ -0,0 +1,284 @@
+use crate::rng::BbsRng;
+use crate::signer::signature_base;
+use crate::BbsFfiError;
+use bbs::prelude::*;
+use ff_zeroize::Field;
+use pairing_plus::{
+    bls12_381::{Fr, G1},
+    serdes::SerDes,
+    CurveProjective,
+};
+use std::collections::BTreeMap;
+
+// These mirror `Prover::new_blind_signature_context` and `PoKOfSignature::init`
+// and produce byte identical output, but draw every blinding from `BbsRng`
+// so a caller supplied or seeded generator controls them.
+
+fn random_blinding() -> SignatureMessage {
+    SignatureMessage::from(Fr::random(&mut BbsRng))
+}
+
+/// Commit to the hidden `messages` and prove knowledge of them for the issuer
+pub(crate) fn blind_signature_context(
+    pk: &PublicKey,
+    messages: &BTreeMap<usize, SignatureMessage>,
+    nonce: &ProofNonce,
+) -> Result<(BlindSignatureContext, SignatureBlinding), BbsFfiError> {
+    let blinding_factor = SignatureBlinding::from(Fr::random(&mut BbsRng));
+    let mut builder = CommitmentBuilder::new();
+    builder.add(pk.h0, blinding_factor);
+
+    let mut committing = ProverCommittingG1::new();
+    committing.commit_with(pk.h0, random_blinding());
+    let mut secrets = vec![SignatureMessage::from(*blinding_factor.as_ref())];
+    for (i, m) in messages {
+        let h = pk.h.get(*i).ok_or_else(|| {
+            BbsFfiError(format!(
+                "Message index {} is out of range for {} messages",
+                i,
+                pk.message_count()
+            ))
+        })?;
+        secrets.push(*m);
+        builder.add(h, m);
+        committing.commit_with(h, random_blinding());
+    }
+
+    let commitment = builder.finalize();
+    let committed = committing.finish();
+
+    let mut extra = commitment.to_bytes_uncompressed_form().to_vec();
+    extra.extend_from_slice(&nonce.to_bytes_uncompressed_form()[..]);
+    let challenge_hash = committed.gen_challenge(extra);
+    let proof_of_hidden_messages = committed.gen_proof(&challenge_hash, secrets.as_slice())?;
+
+    Ok((
+        BlindSignatureContext {
+            commitment,
+            challenge_hash,
+            proof_of_hidden_messages,
+        },
+        blinding_factor,
+    ))
+}
+
+/// A, e and s from a compressed signature
+fn signature_parts(signature: &Signature) -> Result<(G1, Fr, Fr), BbsFfiError> {
+    let bytes = signature.to_bytes_compressed_form();
+    let mut cursor = &bytes[..];
+    let a = G1::deserialize(&mut cursor, true).map_err(|e| BbsFfiError(format!("{:?}", e)))?;
+    let e = Fr::deserialize(&mut cursor, true).map_err(|e| BbsFfiError(format!("{:?}", e)))?;
+    let s = Fr::deserialize(&mut cursor, true).map_err(|e| BbsFfiError(format!("{:?}", e)))?;
+    Ok((a, e, s))
+}
+
+fn negate(f: Fr) -> Fr {
+    let mut f = f;
+    f.negate();
+    f
+}
+
+/// Prove knowledge of `signature` over `messages` revealing only the revealed messages
+pub(crate) fn create_proof(
+    signature: &Signature,
+    pk: &PublicKey,
+    messages: &[ProofMessage],
+    nonce: &ProofNonce,
+) -> Result<PoKOfSignatureProof, BbsFfiError> {
+    if messages.len() != pk.message_count() {
+        return Err(BbsFfiError(format!(
+            "Public key expects {} messages, found {}",
+            pk.message_count(),
+            messages.len()
+        )));
+    }
+    let sig_messages: Vec<SignatureMessage> = messages.iter().map(|m| m.get_message()).collect();
+    if !signature.verify(sig_messages.as_slice(), pk)? {
+        return Err(BbsFfiError::new("The messages and signature do not match."));
+    }
+
+    let (a, e, s) = signature_parts(signature)?;
+    let r1 = Fr::random(&mut BbsRng);
+    let r2 = Fr::random(&mut BbsRng);
+    let b = signature_base(pk, sig_messages.iter().enumerate(), s, None)?;
+
+    // A' = A ^ r1, A_bar = B ^ r1 * A' ^ -e, d = B ^ r1 * h0 ^ -r2
+    let mut a_prime = a;
+    a_prime.mul_assign(r1);
+    let mut a_bar_denom = a_prime;
+    a_bar_denom.mul_assign(e);
+    let mut b_r1 = b;
+    b_r1.mul_assign(r1);
+    let mut a_bar = b_r1;
+    a_bar.sub_assign(&a_bar_denom);
+    let mut h0_r2 = *pk.h0.as_ref();
+    h0_r2.mul_assign(r2);
+    let mut d = b_r1;
+    d.sub_assign(&h0_r2);
+
+    let r3 = r1
+        .inverse()
+        .ok_or_else(|| BbsFfiError::new("r1 cannot be zero"))?;
+    // s' = s - r2 * r3
+    let mut s_prime = r2;
+    s_prime.mul_assign(&r3);
+    s_prime.negate();
+    s_prime.add_assign(&s);
+
+    // a_bar / d == a_prime ^ -e * h0 ^ r2
+    let mut committing_1 = ProverCommittingG1::new();
+    committing_1.commit_with(GeneratorG1::from(a_prime), random_blinding());
+    committing_1.commit_with(pk.h0, random_blinding());
+    let secrets_1 = [
+        SignatureMessage::from(negate(e)),
+        SignatureMessage::from(r2),
+    ];
+    let pok_vc_1 = committing_1.finish();
+
+    // d ^ -r3 * h0 ^ s' * h_j ^ m_j for hidden m_j == g1 * h_i ^ -m_i for revealed m_i
+    let mut committing_2 = ProverCommittingG1::new();
+    committing_2.commit_with(GeneratorG1::from(d), random_blinding());
+    committing_2.commit_with(pk.h0, random_blinding());
+    let mut secrets_2 = vec![
+        SignatureMessage::from(negate(r3)),
+        SignatureMessage::from(s_prime),
+    ];
+    for (h, m) in pk.h.iter().zip(messages) {
+        match m {
+            ProofMessage::Revealed(_) => {}
+            ProofMessage::Hidden(HiddenMessage::ProofSpecificBlinding(m)) => {
+                committing_2.commit_with(h, random_blinding());
+                secrets_2.push(*m);
+            }
+            ProofMessage::Hidden(HiddenMessage::ExternalBlinding(m, blinding)) => {
+                committing_2.commit_with(h, blinding);
+                secrets_2.push(*m);
+            }
+        }
+    }
+    let pok_vc_2 = committing_2.finish();
+
+    let mut challenge_bytes = Vec::new();
+    a_bar.serialize(&mut challenge_bytes, false).unwrap();
+    challenge_bytes.append(&mut pok_vc_1.to_bytes());
+    challenge_bytes.append(&mut pok_vc_2.to_bytes());
+    challenge_bytes.extend_from_slice(&nonce.to_bytes_compressed_form()[..]);
+    let challenge_hash = ProofChallenge::hash(&challenge_bytes);
+
+    let proof_vc_1 = pok_vc_1.gen_proof(&challenge_hash, &secrets_1)?;
+    let proof_vc_2 = pok_vc_2.gen_proof(&challenge_hash, secrets_2.as_slice())?;
+
+    let mut output = Vec::new();
+    for p in &[a_prime, a_bar, d] {
+        p.serialize(&mut output, true).unwrap();
+    }
+    let mut proof1_bytes = proof_vc_1.to_bytes_compressed_form();
+    output.extend_from_slice(&(proof1_bytes.len() as u32).to_be_bytes());
+    output.append(&mut proof1_bytes);
+    output.append(&mut proof_vc_2.to_bytes_compressed_form());
+    Ok(PoKOfSignatureProof::from_bytes_compressed_form(output)?)
+}
+
+#[cfg(all(test, feature = "deterministic-rng"))]
+mod tests {
+    use super::*;
+    use crate::rng::{seeded_rng, with_seed};
+    use std::collections::BTreeSet;
+
+    const SEED: &[u8] = b"proof test seed";
+
+    fn keys() -> (PublicKey, SecretKey) {
+        let (dpk, sk) = crate::api::generate_key(Some(b"proof test key")).unwrap();
+        (dpk.to_public_key(3).unwrap(), sk)
+    }
+
+    fn messages() -> Vec<SignatureMessage> {
+        [&b"name"[..], b"age", b"city"]
+            .iter()
+            .map(SignatureMessage::hash)
+            .collect()
+    }
+
+    #[test]
+    fn blind_commitment_matches_upstream() {
+        let (pk, _) = keys();
+        let messages = messages();
+        let nonce = ProofNonce::hash(b"issuer nonce");
+        let hidden = BTreeMap::from([(0, messages[0])]);
+        let (context, blinding) =
+            with_seed(SEED, || blind_signature_context(&pk, &hidden, &nonce)).unwrap();
+
+        let expected = SignatureBlinding::from(Fr::random(&mut seeded_rng(SEED)));
+        assert_eq!(
+            blinding.to_bytes_compressed_form(),
+            expected.to_bytes_compressed_form()
+        );
+        let mut builder = CommitmentBuilder::new();
+        builder.add(pk.h0, expected);
+        builder.add(pk.h[0], messages[0]);
+        assert_eq!(context.commitment, builder.finalize());
+
+        let known: BTreeSet<usize> = [1, 2].iter().copied().collect();
+        assert!(context.verify(&known, &pk, &nonce).unwrap());
+        assert!(!context
+            .verify(&known, &pk, &ProofNonce::hash(b"other"))
+            .unwrap());
+
+        let (again, _) = with_seed(SEED, || blind_signature_context(&pk, &hidden, &nonce)).unwrap();
+        assert_eq!(
+            again.to_bytes_compressed_form(),
+            context.to_bytes_compressed_form()
+        );
+    }
+
+    #[test]
+    fn proof_verifies_with_upstream() {
+        let (pk, sk) = keys();
+        let messages = messages();
+        let signature = Signature::new(&messages, &sk, &pk).unwrap();
+        let nonce = ProofNonce::hash(b"verifier nonce");
+        let proof_messages = [
+            ProofMessage::Revealed(messages[0]),
+            ProofMessage::Hidden(HiddenMessage::ProofSpecificBlinding(messages[1])),
+            ProofMessage::Revealed(messages[2]),
+        ];
+        let proof = with_seed(SEED, || {
+            create_proof(&signature, &pk, &proof_messages, &nonce)
+        })
+        .unwrap();
+
+        // A' = A ^ r1 with r1 the first draw
+        let (mut a_prime, _, _) = signature_parts(&signature).unwrap();
+        a_prime.mul_assign(Fr::random(&mut seeded_rng(SEED)));
+        let mut expected = Vec::new();
+        a_prime.serialize(&mut expected, true).unwrap();
+        assert_eq!(
+            proof.to_bytes_compressed_form()[..G1_COMPRESSED_SIZE],
+            expected[..]
+        );
+
+        // The verifier rebuilds the challenge from the layout of `PoKOfSignature::init`
+        let request = Verifier::new_proof_request(&[0, 2], &pk).unwrap();
+        let revealed = BTreeMap::from([(0, messages[0]), (2, messages[2])]);
+        let signature_proof = SignatureProof {
+            revealed_messages: revealed,
+            proof: proof.clone(),
+        };
+        assert!(Verifier::verify_signature_pok(&request, &signature_proof, &nonce).is_ok());
+        assert!(Verifier::verify_signature_pok(
+            &request,
+            &signature_proof,
+            &ProofNonce::hash(b"replayed")
+        )
+        .is_err());
+
+        let again = with_seed(SEED, || {
+            create_proof(&signature, &pk, &proof_messages, &nonce)
+        })
+        .unwrap();
+        assert_eq!(
+            again.to_bytes_compressed_form(),
+            proof.to_bytes_compressed_form()
+        );
+    }
+}
@@ -0,0 +1,306 @@
+//! Python extension module over the `api` builders, built with PyO3.
+//!
+//! Installed as `ursa_bbs_signatures.native`. Binary inputs and outputs are `bytes` in the
+//! same formats as the C ABI. The GIL is released while signing, committing and proving.
+
+use crate::api::{
+    self, BlindCommitmentBuilder, BlindSignBuilder, BlindSignature, BlindSignatureContext,
+    Commitment, CreateProofBuilder, DeterministicPublicKey, HashElem, HiddenMessage, Parse, Proof,
+    ProofMessage, ProofNonce, PublicKey, SecretKey, SignBuilder, Signature, SignatureBlinding,
+    SignatureMessage, ToVariableLengthBytes, VerifyBlindCommitmentBuilder, VerifyBuilder,
+    VerifyProofBuilder,
+};
+use pyo3::{create_exception, exceptions::PyException, prelude::*, types::PyBytes};
+use std::collections::{BTreeMap, BTreeSet};
+
+create_exception!(
+    native,
+    BbsException,
+    PyException,
+    "An error from a BBS operation"
+);
+create_exception!(
+    native,
+    BbsInvalidKeyException,
+    BbsException,
+    "A public or secret key is malformed"
+);
+create_exception!(
+    native,
+    BbsInvalidSignatureException,
+    BbsException,
+    "A signature is malformed"
+);
+
+impl From<api::Error> for PyErr {
+    fn from(e: api::Error) -> Self {
+        BbsException::new_err(e.message().to_string())
+    }
+}
+
+fn parse<T: Parse>(bytes: &[u8], what: &str) -> PyResult<T> {
+    T::parse(bytes).map_err(|e| {
+        let msg = e.message().to_string();
+        match what {
+            "public key" | "secret key" => BbsInvalidKeyException::new_err(msg),
+            "signature" | "blind signature" => BbsInvalidSignatureException::new_err(msg),
+            _ => BbsException::new_err(msg),
+        }
+    })
+}
+
+fn hash_messages(messages: &[&[u8]]) -> Vec<SignatureMessage> {
+    messages.iter().map(SignatureMessage::hash).collect()
+}
+
+fn as_slices<'a>(messages: &'a [Bound<'_, PyBytes>]) -> Vec<&'a [u8]> {
+    messages.iter().map(|m| m.as_bytes()).collect()
+}
+
+fn indexed_slices<'a>(messages: &'a BTreeMap<usize, Bound<'_, PyBytes>>) -> Vec<(usize, &'a [u8])> {
+    messages.iter().map(|(i, m)| (*i, m.as_bytes())).collect()
+}
+
+fn to_bytes<'py>(py: Python<'py>, bytes: &[u8]) -> Bound<'py, PyBytes> {
+    PyBytes::new(py, bytes)
+}
+
+/// Generate a BLS key pair in G2, deterministically from `seed` if set.
+/// Returns `(public_key, secret_key)`
+#[pyfunction]
+#[pyo3(signature = (seed = None))]
+fn generate_key<'py>(
+    py: Python<'py>,
+    seed: Option<&[u8]>,
+) -> PyResult<(Bound<'py, PyBytes>, Bound<'py, PyBytes>)> {
+    let (pk, sk) = api::generate_key(seed)?;
+    Ok((
+        to_bytes(py, &pk.to_bytes_compressed_form()),
+        to_bytes(py, &sk.to_bytes_compressed_form()),
+    ))
+}
+
+/// Convert a BLS public key to a BBS public key for `message_count` messages
+#[pyfunction]
+fn bls_public_key_to_bbs_key<'py>(
+    py: Python<'py>,
+    public_key: &[u8],
+    message_count: usize,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let dpk = parse::<DeterministicPublicKey>(public_key, "public key")?;
+    let pk = dpk
+        .to_public_key(message_count)
+        .map_err(|e| BbsInvalidKeyException::new_err(format!("{:?}", e)))?;
+    Ok(to_bytes(py, &pk.to_bytes_compressed_form()))
+}
+
+/// Sign `messages`
+#[pyfunction]
+fn sign<'py>(
+    py: Python<'py>,
+    secret_key: &[u8],
+    public_key: &[u8],
+    messages: Vec<Bound<'_, PyBytes>>,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let sk = parse::<SecretKey>(secret_key, "secret key")?;
+    let pk = parse::<PublicKey>(public_key, "public key")?;
+    let messages = as_slices(&messages);
+    let signature = py.allow_threads(|| {
+        SignBuilder::new(&pk)
+            .secret_key(&sk)
+            .prehashed_messages(hash_messages(&messages))
+            .sign()
+    })?;
+    Ok(to_bytes(py, &signature.to_bytes_compressed_form()))
+}
+
+/// `True` if `signature` is valid for `messages`
+#[pyfunction]
+fn verify(
+    py: Python<'_>,
+    public_key: &[u8],
+    signature: &[u8],
+    messages: Vec<Bound<'_, PyBytes>>,
+) -> PyResult<bool> {
+    let pk = parse::<PublicKey>(public_key, "public key")?;
+    let signature = parse::<Signature>(signature, "signature")?;
+    let messages = as_slices(&messages);
+    Ok(py.allow_threads(|| {
+        VerifyBuilder::new(&pk, &signature)
+            .prehashed_messages(hash_messages(&messages))
+            .verify()
+    })?)
+}
+
+/// Commit to the hidden `messages` by index.
+/// Returns `(commitment, context, blinding_factor)`
+#[pyfunction]
+fn blind_commitment<'py>(
+    py: Python<'py>,
+    public_key: &[u8],
+    nonce: &[u8],
+    messages: BTreeMap<usize, Bound<'_, PyBytes>>,
+) -> PyResult<(
+    Bound<'py, PyBytes>,
+    Bound<'py, PyBytes>,
+    Bound<'py, PyBytes>,
+)> {
+    let pk = parse::<PublicKey>(public_key, "public key")?;
+    let messages = indexed_slices(&messages);
+    let res = py.allow_threads(|| {
+        let nonce = ProofNonce::hash(nonce);
+        BlindCommitmentBuilder::new(&pk, &nonce)
+            .prehashed_messages(
+                messages
+                    .iter()
+                    .map(|(i, m)| (*i, SignatureMessage::hash(m))),
+            )
+            .commit()
+    })?;
+    Ok((
+        to_bytes(py, &res.commitment.to_bytes_compressed_form()),
+        to_bytes(py, &res.context.to_bytes_compressed_form()),
+        to_bytes(py, &res.blinding_factor.to_bytes_compressed_form()),
+    ))
+}
+
+/// `True` if the proof in the commitment `context` is valid for the `blinded` indices
+#[pyfunction]
+fn verify_blind_commitment(
+    py: Python<'_>,
+    public_key: &[u8],
+    context: &[u8],
+    nonce: &[u8],
+    blinded: Vec<usize>,
+) -> PyResult<bool> {
+    let pk = parse::<PublicKey>(public_key, "public key")?;
+    let context = parse::<BlindSignatureContext>(context, "commitment context")?;
+    Ok(py.allow_threads(|| {
+        let nonce = ProofNonce::hash(nonce);
+        blinded
+            .iter()
+            .fold(
+                VerifyBlindCommitmentBuilder::new(&pk, &context, &nonce),
+                |builder, i| builder.blinded(*i),
+            )
+            .verify()
+    })?)
+}
+
+/// Sign the holder's `commitment` and the known `messages` by index
+#[pyfunction]
+fn blind_sign<'py>(
+    py: Python<'py>,
+    secret_key: &[u8],
+    public_key: &[u8],
+    commitment: &[u8],
+    messages: BTreeMap<usize, Bound<'_, PyBytes>>,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let sk = parse::<SecretKey>(secret_key, "secret key")?;
+    let pk = parse::<PublicKey>(public_key, "public key")?;
+    let commitment = parse::<Commitment>(commitment, "commitment")?;
+    let messages = indexed_slices(&messages);
+    let signature = py.allow_threads(|| {
+        BlindSignBuilder::new(&pk, &commitment)
+            .secret_key(&sk)
+            .prehashed_messages(
+                messages
+                    .iter()
+                    .map(|(i, m)| (*i, SignatureMessage::hash(m))),
+            )
+            .sign()
+    })?;
+    Ok(to_bytes(py, &signature.to_bytes_compressed_form()))
+}
+
+/// Unblind `blind_signature` with the holder's `blinding_factor`
+#[pyfunction]
+fn unblind_signature<'py>(
+    py: Python<'py>,
+    blind_signature: &[u8],
+    blinding_factor: &[u8],
+) -> PyResult<Bound<'py, PyBytes>> {
+    let blind_signature = parse::<BlindSignature>(blind_signature, "blind signature")?;
+    let blinding_factor = parse::<SignatureBlinding>(blinding_factor, "blinding factor")?;
+    let signature = api::unblind_signature(&blind_signature, &blinding_factor);
+    Ok(to_bytes(py, &signature.to_bytes_compressed_form()))
+}
+
+/// Prove knowledge of `signature` over all signed `messages`, revealing the messages at `revealed`
+#[pyfunction]
+fn create_proof<'py>(
+    py: Python<'py>,
+    signature: &[u8],
+    public_key: &[u8],
+    nonce: &[u8],
+    messages: Vec<Bound<'_, PyBytes>>,
+    revealed: Vec<usize>,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let signature = parse::<Signature>(signature, "signature")?;
+    let pk = parse::<PublicKey>(public_key, "public key")?;
+    let messages = as_slices(&messages);
+    let revealed: BTreeSet<usize> = revealed.into_iter().collect();
+    let proof = py.allow_threads(|| {
+        let nonce = ProofNonce::hash(nonce);
+        let messages = hash_messages(&messages)
+            .into_iter()
+            .enumerate()
+            .map(|(i, m)| {
+                if revealed.contains(&i) {
+                    ProofMessage::Revealed(m)
+                } else {
+                    ProofMessage::Hidden(HiddenMessage::ProofSpecificBlinding(m))
+                }
+            });
+        CreateProofBuilder::new(&pk, &signature, &nonce)
+            .proof_messages(messages)
+            .create()
+    })?;
+    Ok(to_bytes(py, &proof.to_bytes()))
+}
+
+/// `True` if `proof` is valid for the revealed `messages` in index order
+#[pyfunction]
+fn verify_proof(
+    py: Python<'_>,
+    public_key: &[u8],
+    proof: &[u8],
+    nonce: &[u8],
+    messages: Vec<Bound<'_, PyBytes>>,
+) -> PyResult<bool> {
+    let pk = parse::<PublicKey>(public_key, "public key")?;
+    let proof = parse::<Proof>(proof, "proof")?;
+    let messages = as_slices(&messages);
+    let status = py.allow_threads(|| {
+        let nonce = ProofNonce::hash(nonce);
+        VerifyProofBuilder::new(&pk, &proof, &nonce)
+            .prehashed_messages(hash_messages(&messages))
+            .verify()
+    })?;
+    Ok(status.is_valid())
+}
+
+#[pymodule]
+fn native(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add("BbsException", py.get_type::<BbsException>())?;
+    m.add(
+        "BbsInvalidKeyException",
+        py.get_type::<BbsInvalidKeyException>(),
+    )?;
+    m.add(
+        "BbsInvalidSignatureException",
+        py.get_type::<BbsInvalidSignatureException>(),
+    )?;
+    m.add_function(wrap_pyfunction!(generate_key, m)?)?;
+    m.add_function(wrap_pyfunction!(bls_public_key_to_bbs_key, m)?)?;
+    m.add_function(wrap_pyfunction!(sign, m)?)?;
+    m.add_function(wrap_pyfunction!(verify, m)?)?;
+    m.add_function(wrap_pyfunction!(blind_commitment, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_blind_commitment, m)?)?;
+    m.add_function(wrap_pyfunction!(blind_sign, m)?)?;
+    m.add_function(wrap_pyfunction!(unblind_signature, m)?)?;
+    m.add_function(wrap_pyfunction!(create_proof, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_proof, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use crate::last_error::ErrorSlot;
+use crate::BbsFfiError;
+#[cfg(feature = "deterministic-rng")]
+use crate::ByteArray;
+use ffi_support::ExternError;
+use rand::{CryptoRng, Error, RngCore};
+use std::{
+    os::raw::c_void,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+/// Fills `buffer` with `length` random bytes. Returns 0 on success
+pub type BbsRngCallback =
+    extern "C" fn(user_data: *mut c_void, buffer: *mut u8, length: usize) -> i32;
+
+enum RngSource {
+    Thread,
+    Callback {
+        callback: BbsRngCallback,
+        user_data: *mut c_void,
+    },
+    #[cfg(feature = "deterministic-rng")]
+    Seeded(Box<rand_chacha::ChaCha20Rng>),
+}
+
+unsafe impl Send for RngSource {}
+
+lazy_static! {
+    static ref RNG_SOURCE: Mutex<RngSource> = Mutex::new(RngSource::Thread);
+}
+
+/// Whether `RNG_SOURCE` is not `Thread`, so the default source never takes the lock
+static CUSTOM_SOURCE: AtomicBool = AtomicBool::new(false);
+
+/// Replace the source while holding its lock
+fn set_source(source: &mut RngSource, new: RngSource) {
+    CUSTOM_SOURCE.store(!matches!(new, RngSource::Thread), Ordering::Release);
+    *source = new;
+}
+
+/// The random number generator used for all keys, nonces, blinding factors and proofs.
+/// Defaults to the operating system backed thread rng
+pub(crate) struct BbsRng;
+
+impl RngCore for BbsRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut b = [0u8; 4];
+        self.fill_bytes(&mut b);
+        u32::from_le_bytes(b)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut b = [0u8; 8];
+        self.fill_bytes(&mut b);
+        u64::from_le_bytes(b)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.try_fill_bytes(dest)
+            .expect("Random number generator failed")
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        if !CUSTOM_SOURCE.load(Ordering::Acquire) {
+            return rand::thread_rng().try_fill_bytes(dest);
+        }
+        // The callback runs without the lock so it can call back into the library
+        let callback = {
+            let mut source = RNG_SOURCE.lock().unwrap_or_else(|e| e.into_inner());
+            match &mut *source {
+                RngSource::Thread => None,
+                RngSource::Callback {
+                    callback,
+                    user_data,
+                } => Some((*callback, *user_data)),
+                #[cfg(feature = "deterministic-rng")]
+                RngSource::Seeded(rng) => return rng.try_fill_bytes(dest),
+            }
+        };
+        let (callback, user_data) = match callback {
+            Some(c) => c,
+            None => return rand::thread_rng().try_fill_bytes(dest),
+        };
+        match callback(user_data, dest.as_mut_ptr(), dest.len()) {
+            0 => Ok(()),
+            code => Err(Error::new(format!(
+                "Random number callback failed with code {}",
+                code
+            ))),
+        }
+    }
+}
+
+impl CryptoRng for BbsRng {}
+
+/// Run `f` turning a failure of the random number generator into an error
+/// for functions that do not already catch panics with `call_with_result`
+pub(crate) fn catch_rng_failure<T, F: FnOnce() -> T>(f: F) -> Result<T, BbsFfiError> {
+    panic::catch_unwind(AssertUnwindSafe(f))
+        .map_err(|_| BbsFfiError::new("Random number generator failed"))
+}
+
+/// Use `callback` as the source of all randomness in this library, for example
+/// to supply a hardware entropy source. `callback` may be called from any thread.
+/// Pass a null `callback` to return to the operating system random number generator
+#[no_mangle]
+pub extern "C" fn bbs_set_rng_callback(
+    callback: Option<BbsRngCallback>,
+    user_data: *mut c_void,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let mut source = RNG_SOURCE.lock().unwrap_or_else(|e| e.into_inner());
+    set_source(
+        &mut source,
+        match callback {
+            Some(callback) => RngSource::Callback {
+                callback,
+                user_data,
+            },
+            None => RngSource::Thread,
+        },
+    );
+    *err = ExternError::success();
+    0
+}
+
+/// Replace all randomness in this library with a ChaCha20 stream seeded from `seed`
+/// so keys, commitments, signatures and proofs are reproducible.
+/// Only for tests, results depend on the order of calls across all threads.
+/// Pass an empty `seed` to return to the operating system random number generator
+#[cfg(feature = "deterministic-rng")]
+#[no_mangle]
+pub extern "C" fn bbs_set_rng_seed(seed: ByteArray, err: *mut ExternError) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let seed = seed.to_vec();
+    let mut source = RNG_SOURCE.lock().unwrap_or_else(|e| e.into_inner());
+    if seed.is_empty() {
+        set_source(&mut source, RngSource::Thread);
+    } else {
+        set_source(&mut source, RngSource::Seeded(Box::new(seeded_rng(&seed))));
+    }
+    *err = ExternError::success();
+    0
+}
+
+/// The ChaCha20 stream `bbs_set_rng_seed` uses for `seed`
+#[cfg(feature = "deterministic-rng")]
+pub(crate) fn seeded_rng(seed: &[u8]) -> rand_chacha::ChaCha20Rng {
+    use rand::SeedableRng;
+    use sha2::Digest;
+
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&sha2::Sha256::digest(seed));
+    rand_chacha::ChaCha20Rng::from_seed(key)
+}
+
+#[cfg(test)]
+lazy_static! {
+    static ref TEST_RNG_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// Held by tests that use `BbsRng` so a seeded test only sees its own draws
+#[cfg(test)]
+pub(crate) fn lock_rng() -> std::sync::MutexGuard<'static, ()> {
+    TEST_RNG_LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Run `f` with `BbsRng` seeded from `seed`
+#[cfg(all(test, feature = "deterministic-rng"))]
+pub(crate) fn with_seed<T, F: FnOnce() -> T>(seed: &[u8], f: F) -> T {
+    let _rng = lock_rng();
+    bbs_set_rng_seed(ByteArray::from_slice(seed), std::ptr::null_mut());
+    let res = f();
+    bbs_set_rng_seed(ByteArray::default(), std::ptr::null_mut());
+    res
+}
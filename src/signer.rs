@@ -0,0 +1,395 @@
+use crate::bls_ciphersuite::{point_from_bytes, point_to_bytes, secret_key_from_bytes};
+use crate::last_error::ErrorSlot;
+use crate::rng::BbsRng;
+use crate::{BbsFfiError, ByteArray};
+use bbs::prelude::*;
+use ff_zeroize::Field;
+use ffi_support::{call_with_result, ByteBuffer, ExternError};
+use pairing_plus::{
+    bls12_381::{Bls12, Fq12, Fr, G1, G2},
+    serdes::SerDes,
+    CurveProjective, Engine,
+};
+use std::{convert::TryFrom, os::raw::c_void};
+use zeroize::Zeroize;
+
+/// Computes `a` = `b` ^ (1 / (`x` + `e`)) where `x` is the secret key held by the signer.
+/// `b` and `a` are compressed G1 points and `e` is a field element, all big endian.
+/// `a` points to `a_len` writable bytes. Returns 0 on success
+pub type BbsSignerSignCallback = extern "C" fn(
+    user_data: *mut c_void,
+    b: ByteArray,
+    e: ByteArray,
+    a: *mut u8,
+    a_len: usize,
+) -> i32;
+
+/// An external signer that performs the secret key operation of signing and blind signing
+/// so the secret key never has to be passed to this library.
+/// `user_data` is passed unchanged to `sign`, which may be called from any thread
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct BbsSigner {
+    pub user_data: *mut c_void,
+    pub sign: Option<BbsSignerSignCallback>,
+}
+
+unsafe impl Send for BbsSigner {}
+
+impl BbsSigner {
+    pub(crate) fn check(&self) -> Result<(), BbsFfiError> {
+        if self.sign.is_none() {
+            return Err(BbsFfiError::new("Signer sign callback cannot be null"));
+        }
+        Ok(())
+    }
+
+    fn sign_base(&self, b: &G1, e: Fr) -> Result<G1, BbsFfiError> {
+        let sign = self
+            .sign
+            .ok_or_else(|| BbsFfiError::new("Signer sign callback cannot be null"))?;
+        let b = point_to_bytes(b);
+        let mut e_bytes = Vec::new();
+        e.serialize(&mut e_bytes, true).unwrap();
+        let mut a = [0u8; G1_COMPRESSED_SIZE];
+        let rc = sign(
+            self.user_data,
+            ByteArray::from_slice(&b),
+            ByteArray::from_slice(&e_bytes),
+            a.as_mut_ptr(),
+            a.len(),
+        );
+        if rc != 0 {
+            return Err(BbsFfiError(format!("Signer failed with code {}", rc)));
+        }
+        point_from_bytes(&a, G1_COMPRESSED_SIZE, "Signer output")
+    }
+}
+
+/// Performs the secret key operation `A` = `B` ^ (1 / (`x` + `e`)) when signing
+#[derive(Clone, Copy)]
+pub(crate) enum SigningKey<'a> {
+    Secret(&'a SecretKey),
+    External(&'a BbsSigner),
+}
+
+impl SigningKey<'_> {
+    fn sign_base(&self, b: &G1, e: Fr) -> Result<G1, BbsFfiError> {
+        match self {
+            SigningKey::Secret(sk) => {
+                let mut x = secret_key_from_bytes(&sk.to_bytes_compressed_form())?;
+                let a = compute_a(x, *b, e);
+                x.zeroize();
+                a
+            }
+            SigningKey::External(signer) => signer.sign_base(b, e),
+        }
+    }
+}
+
+/// `b` ^ (1 / (`x` + `e`))
+fn compute_a(x: Fr, mut b: G1, e: Fr) -> Result<G1, BbsFfiError> {
+    let mut exp = x;
+    exp.add_assign(&e);
+    let mut inv = exp
+        .inverse()
+        .ok_or_else(|| BbsFfiError::new("x + e cannot be zero"))?;
+    exp.zeroize();
+    b.mul_assign(inv);
+    inv.zeroize();
+    Ok(b)
+}
+
+fn g1_from_generator(g: &GeneratorG1) -> Result<G1, BbsFfiError> {
+    let b = g.to_bytes_compressed_form();
+    let mut b = &b[..];
+    G1::deserialize(&mut b, true).map_err(|e| BbsFfiError(format!("{:?}", e)))
+}
+
+fn fr_from_message(m: &SignatureMessage) -> Result<Fr, BbsFfiError> {
+    let b = m.to_bytes_compressed_form();
+    let mut b = &b[..];
+    Fr::deserialize(&mut b, true).map_err(|e| BbsFfiError(format!("{:?}", e)))
+}
+
+/// `B` = `commitment` * `g1` * `h0` ^ `s` * `h_i` ^ `m_i` for each indexed message
+pub(crate) fn signature_base<'a, I: IntoIterator<Item = (usize, &'a SignatureMessage)>>(
+    pk: &PublicKey,
+    messages: I,
+    s: Fr,
+    commitment: Option<&Commitment>,
+) -> Result<G1, BbsFfiError> {
+    let mut base = G1::one();
+    if let Some(c) = commitment {
+        let b = c.to_bytes_compressed_form();
+        let mut b = &b[..];
+        let c = G1::deserialize(&mut b, true).map_err(|e| BbsFfiError(format!("{:?}", e)))?;
+        base.add_assign(&c);
+    }
+    let mut h0 = g1_from_generator(&pk.h0)?;
+    h0.mul_assign(s);
+    base.add_assign(&h0);
+    for (i, m) in messages {
+        let h = pk.h.get(i).ok_or_else(|| {
+            BbsFfiError(format!(
+                "Message index {} is out of range for {} messages",
+                i,
+                pk.message_count()
+            ))
+        })?;
+        let mut h = g1_from_generator(h)?;
+        h.mul_assign(fr_from_message(m)?);
+        base.add_assign(&h);
+    }
+    Ok(base)
+}
+
+/// Have `key` compute `A` for `B`. The output of an external signer is checked with
+/// e(`A`, `w` * `g2` ^ `e`) == e(`B`, `g2`) so it cannot produce an invalid signature.
+/// Returns `A` || `e` || `s`
+fn sign_with(
+    key: &SigningKey<'_>,
+    pk: &PublicKey,
+    b: G1,
+    e: Fr,
+    s: Fr,
+) -> Result<Vec<u8>, BbsFfiError> {
+    let a = key.sign_base(&b, e)?;
+
+    if let SigningKey::External(_) = key {
+        let w = pk.w.to_bytes_compressed_form();
+        let mut w = point_from_bytes::<G2>(&w, G2_COMPRESSED_SIZE, "Public key w")?;
+        let mut g2_e = G2::one();
+        g2_e.mul_assign(e);
+        w.add_assign(&g2_e);
+        let mut neg_b = b;
+        neg_b.negate();
+        let valid = Bls12::pairing_multi_product(
+            &[a.into_affine(), neg_b.into_affine()],
+            &[w.into_affine(), G2::one().into_affine()],
+        ) == Fq12::one();
+        if !valid {
+            return Err(BbsFfiError::new(
+                "Signer output does not match the public key",
+            ));
+        }
+    }
+
+    let mut out = point_to_bytes(&a);
+    e.serialize(&mut out, true).unwrap();
+    s.serialize(&mut out, true).unwrap();
+    Ok(out)
+}
+
+/// Sign `messages`
+pub(crate) fn sign(
+    key: SigningKey<'_>,
+    messages: &[SignatureMessage],
+    pk: &PublicKey,
+) -> Result<Signature, BbsFfiError> {
+    if messages.len() != pk.message_count() {
+        return Err(BbsFfiError(format!(
+            "Public key expects {} messages, found {}",
+            pk.message_count(),
+            messages.len()
+        )));
+    }
+    let e = Fr::random(&mut BbsRng);
+    let s = Fr::random(&mut BbsRng);
+    let b = signature_base(pk, messages.iter().enumerate(), s, None)?;
+    Ok(Signature::try_from(sign_with(&key, pk, b, e, s)?)?)
+}
+
+/// Blind sign the known `messages` and `commitment`
+pub(crate) fn blind_sign(
+    key: SigningKey<'_>,
+    commitment: &Commitment,
+    messages: &std::collections::BTreeMap<usize, SignatureMessage>,
+    pk: &PublicKey,
+) -> Result<BlindSignature, BbsFfiError> {
+    if messages.len() > pk.message_count() {
+        return Err(BbsFfiError(format!(
+            "Public key expects at most {} messages, found {}",
+            pk.message_count(),
+            messages.len()
+        )));
+    }
+    let e = Fr::random(&mut BbsRng);
+    let s = Fr::random(&mut BbsRng);
+    let b = signature_base(
+        pk,
+        messages.iter().map(|(i, m)| (*i, m)),
+        s,
+        Some(commitment),
+    )?;
+    Ok(BlindSignature::try_from(sign_with(&key, pk, b, e, s)?)?)
+}
+
+/// The secret key operation performed by a `BbsSigner`: `a` = `b` ^ (1 / (`x` + `e`)).
+/// For signers that run this library in another process or key store
+#[no_mangle]
+pub extern "C" fn bbs_signer_compute(
+    secret_key: ByteArray,
+    b: ByteArray,
+    e: ByteArray,
+    a: &mut ByteBuffer,
+    err: *mut ExternError,
+) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    let res = call_with_result(err, || -> Result<ByteBuffer, BbsFfiError> {
+        let mut x = secret_key_from_bytes(&secret_key.to_vec())?;
+        let b = point_from_bytes::<G1>(&b.to_vec(), G1_COMPRESSED_SIZE, "b")?;
+        let e = e.to_vec();
+        if e.len() != FR_COMPRESSED_SIZE {
+            return Err(BbsFfiError(format!(
+                "e must be {} bytes, found {}",
+                FR_COMPRESSED_SIZE,
+                e.len()
+            )));
+        }
+        let mut e = &e[..];
+        let e = Fr::deserialize(&mut e, true).map_err(|e| BbsFfiError(format!("{:?}", e)))?;
+        let b = compute_a(x, b, e);
+        x.zeroize();
+        let b = b?;
+        Ok(ByteBuffer::from_vec(point_to_bytes(&b)))
+    });
+    if err.get_code().is_success() {
+        *a = res;
+    }
+    err.get_code().code()
+}
+
+#[cfg(all(test, feature = "deterministic-rng"))]
+mod tests {
+    use super::*;
+    use crate::rng::{seeded_rng, with_seed};
+    use std::{collections::BTreeMap, ptr, slice};
+
+    const SEED: &[u8] = b"signer test seed";
+
+    fn keys() -> (PublicKey, SecretKey) {
+        let (dpk, sk) = crate::api::generate_key(Some(b"signer test key")).unwrap();
+        (dpk.to_public_key(3).unwrap(), sk)
+    }
+
+    fn messages() -> Vec<SignatureMessage> {
+        [&b"name"[..], b"age", b"city"]
+            .iter()
+            .map(SignatureMessage::hash)
+            .collect()
+    }
+
+    extern "C" fn sign_callback(
+        user_data: *mut c_void,
+        b: ByteArray,
+        e: ByteArray,
+        a: *mut u8,
+        a_len: usize,
+    ) -> i32 {
+        let sk = unsafe { &*(user_data as *const SecretKey) };
+        let sk = sk.to_bytes_compressed_form();
+        let mut out = ByteBuffer::default();
+        let rc = bbs_signer_compute(ByteArray::from_slice(sk), b, e, &mut out, ptr::null_mut());
+        if rc == 0 {
+            unsafe { slice::from_raw_parts_mut(a, a_len) }.copy_from_slice(&out.destroy_into_vec());
+        }
+        rc
+    }
+
+    fn signer(sk: &SecretKey) -> BbsSigner {
+        BbsSigner {
+            user_data: sk as *const SecretKey as *mut c_void,
+            sign: Some(sign_callback),
+        }
+    }
+
+    fn fr_bytes(f: Fr) -> Vec<u8> {
+        let mut out = Vec::new();
+        f.serialize(&mut out, true).unwrap();
+        out
+    }
+
+    #[test]
+    fn sign_verifies_with_upstream() {
+        let (pk, sk) = keys();
+        let messages = messages();
+        let signature = with_seed(SEED, || sign(SigningKey::Secret(&sk), &messages, &pk)).unwrap();
+        // A is fixed by B, x and e, so a signature upstream verifies is the one it would make
+        assert!(signature.verify(&messages, &pk).unwrap());
+
+        let mut rng = seeded_rng(SEED);
+        let e = Fr::random(&mut rng);
+        let s = Fr::random(&mut rng);
+        let bytes = signature.to_bytes_compressed_form();
+        assert_eq!(
+            bytes[G1_COMPRESSED_SIZE..],
+            [fr_bytes(e), fr_bytes(s)].concat()[..]
+        );
+
+        let signer = signer(&sk);
+        let external =
+            with_seed(SEED, || sign(SigningKey::External(&signer), &messages, &pk)).unwrap();
+        assert_eq!(external, signature);
+    }
+
+    #[test]
+    fn blind_sign_verifies_with_upstream() {
+        let (pk, sk) = keys();
+        let messages = messages();
+        let nonce = ProofNonce::hash(b"issuer nonce");
+        let hidden = BTreeMap::from([(0, messages[0])]);
+        let (context, blinding) =
+            Prover::new_blind_signature_context(&pk, &hidden, &nonce).unwrap();
+        let known: BTreeMap<usize, SignatureMessage> =
+            messages.iter().copied().enumerate().skip(1).collect();
+
+        let blind_signature = with_seed(SEED, || {
+            blind_sign(SigningKey::Secret(&sk), &context.commitment, &known, &pk)
+        })
+        .unwrap();
+        let signature = blind_signature.to_unblinded(&blinding);
+        assert!(signature.verify(&messages, &pk).unwrap());
+
+        let signer = signer(&sk);
+        let external = with_seed(SEED, || {
+            blind_sign(
+                SigningKey::External(&signer),
+                &context.commitment,
+                &known,
+                &pk,
+            )
+        })
+        .unwrap();
+        assert_eq!(
+            external.to_bytes_compressed_form()[..],
+            blind_signature.to_bytes_compressed_form()[..]
+        );
+    }
+
+    #[test]
+    fn external_signer_output_is_checked() {
+        extern "C" fn wrong_key(
+            _: *mut c_void,
+            b: ByteArray,
+            e: ByteArray,
+            a: *mut u8,
+            a_len: usize,
+        ) -> i32 {
+            let (_, sk) = crate::api::generate_key(Some(b"another key")).unwrap();
+            sign_callback(&sk as *const SecretKey as *mut c_void, b, e, a, a_len)
+        }
+
+        let (pk, _) = keys();
+        let signer = BbsSigner {
+            user_data: ptr::null_mut(),
+            sign: Some(wrong_key),
+        };
+        assert!(with_seed(SEED, || sign(
+            SigningKey::External(&signer),
+            &messages(),
+            &pk
+        ))
+        .is_err());
+    }
+}
@@ -0,0 +1,378 @@
+use crate::rng::BbsRng;
+use crate::{gen_sk, BbsFfiError};
+use bbs::prelude::*;
+use ff_zeroize::{Field, PrimeField};
+use pairing_plus::{
+    bls12_381::{Fr, FrRepr, G1},
+    serdes::SerDes,
+    CurveProjective,
+};
+use std::convert::TryFrom;
+use zeroize::Zeroize;
+
+/// Size of a share record: index (u32 big endian) || value (Fr)
+pub(crate) const SHARE_SIZE: usize = 4 + FR_COMPRESSED_SIZE;
+/// Size of a nonce share record: index (u32 big endian) || nonce (Fr) || zero (Fr)
+pub(crate) const NONCE_SHARE_SIZE: usize = 4 + 2 * FR_COMPRESSED_SIZE;
+/// Size of a signing session: e (Fr) || s (Fr)
+pub(crate) const SESSION_SIZE: usize = 2 * FR_COMPRESSED_SIZE;
+/// Size of a partial signature: index (u32 big endian) || u (Fr) || R (G1)
+pub(crate) const PARTIAL_SIGNATURE_SIZE: usize = 4 + FR_COMPRESSED_SIZE + G1_COMPRESSED_SIZE;
+
+/// A point on a Shamir polynomial held by the party at `index`
+#[derive(Clone, Debug)]
+pub(crate) struct Share {
+    pub index: u32,
+    pub value: Fr,
+}
+
+impl Drop for Share {
+    fn drop(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+impl Share {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(SHARE_SIZE);
+        out.extend_from_slice(&self.index.to_be_bytes());
+        self.value.serialize(&mut out, true).unwrap();
+        out
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, BbsFfiError> {
+        if data.len() != SHARE_SIZE {
+            return Err(BbsFfiError(format!(
+                "Share must be {} bytes, found {}",
+                SHARE_SIZE,
+                data.len()
+            )));
+        }
+        let index = u32::from_be_bytes(*array_ref![data, 0, 4]);
+        if index == 0 {
+            return Err(BbsFfiError::new("Share index cannot be zero"));
+        }
+        Ok(Self {
+            index,
+            value: fr_from_bytes(&data[4..])?,
+        })
+    }
+}
+
+/// A party's share of one signer's nonce contribution, a point on a degree `t - 1`
+/// polynomial, and of its mask, a point on a degree `2t - 2` polynomial that is zero at zero
+#[derive(Clone, Debug)]
+pub(crate) struct NonceShare {
+    pub index: u32,
+    pub r: Fr,
+    pub zero: Fr,
+}
+
+impl Drop for NonceShare {
+    fn drop(&mut self) {
+        self.r.zeroize();
+        self.zero.zeroize();
+    }
+}
+
+impl NonceShare {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(NONCE_SHARE_SIZE);
+        out.extend_from_slice(&self.index.to_be_bytes());
+        self.r.serialize(&mut out, true).unwrap();
+        self.zero.serialize(&mut out, true).unwrap();
+        out
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, BbsFfiError> {
+        if data.len() != NONCE_SHARE_SIZE {
+            return Err(BbsFfiError(format!(
+                "Nonce share must be {} bytes, found {}",
+                NONCE_SHARE_SIZE,
+                data.len()
+            )));
+        }
+        let index = u32::from_be_bytes(*array_ref![data, 0, 4]);
+        if index == 0 {
+            return Err(BbsFfiError::new("Nonce share index cannot be zero"));
+        }
+        Ok(Self {
+            index,
+            r: fr_from_bytes(&data[4..4 + FR_COMPRESSED_SIZE])?,
+            zero: fr_from_bytes(&data[4 + FR_COMPRESSED_SIZE..])?,
+        })
+    }
+}
+
+/// The public values every party uses for one threshold signature
+#[derive(Clone, Debug)]
+pub(crate) struct Session {
+    pub e: Fr,
+    pub s: Fr,
+}
+
+impl Session {
+    pub fn random() -> Self {
+        Self {
+            e: Fr::random(&mut BbsRng),
+            s: Fr::random(&mut BbsRng),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(SESSION_SIZE);
+        self.e.serialize(&mut out, true).unwrap();
+        self.s.serialize(&mut out, true).unwrap();
+        out
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, BbsFfiError> {
+        if data.len() != SESSION_SIZE {
+            return Err(BbsFfiError(format!(
+                "Session must be {} bytes, found {}",
+                SESSION_SIZE,
+                data.len()
+            )));
+        }
+        Ok(Self {
+            e: fr_from_bytes(&data[..FR_COMPRESSED_SIZE])?,
+            s: fr_from_bytes(&data[FR_COMPRESSED_SIZE..])?,
+        })
+    }
+}
+
+/// A signer's contribution `u` = `r` * (`x` + `e`) + `z` and `R` = `B` ^ `r`
+/// computed from its shares of `x`, `r` and the zero mask `z`
+#[derive(Clone, Debug)]
+pub(crate) struct PartialSignature {
+    pub index: u32,
+    pub u: Fr,
+    pub r: G1,
+}
+
+impl PartialSignature {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(PARTIAL_SIGNATURE_SIZE);
+        out.extend_from_slice(&self.index.to_be_bytes());
+        self.u.serialize(&mut out, true).unwrap();
+        self.r.serialize(&mut out, true).unwrap();
+        out
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, BbsFfiError> {
+        if data.len() != PARTIAL_SIGNATURE_SIZE {
+            return Err(BbsFfiError(format!(
+                "Partial signature must be {} bytes, found {}",
+                PARTIAL_SIGNATURE_SIZE,
+                data.len()
+            )));
+        }
+        let index = u32::from_be_bytes(*array_ref![data, 0, 4]);
+        if index == 0 {
+            return Err(BbsFfiError::new("Partial signature index cannot be zero"));
+        }
+        let u = fr_from_bytes(&data[4..4 + FR_COMPRESSED_SIZE])?;
+        let mut g = &data[4 + FR_COMPRESSED_SIZE..];
+        let r = G1::deserialize(&mut g, true).map_err(|e| BbsFfiError(format!("{:?}", e)))?;
+        Ok(Self { index, u, r })
+    }
+}
+
+/// Threshold signing state kept in a signing context
+#[derive(Default)]
+pub(crate) struct ThresholdState {
+    pub key_share: Option<Share>,
+    pub nonce_shares: Vec<NonceShare>,
+    pub session: Option<Session>,
+    pub partial_signatures: Vec<PartialSignature>,
+}
+
+fn fr_from_bytes(data: &[u8]) -> Result<Fr, BbsFfiError> {
+    let mut data = data;
+    Fr::deserialize(&mut data, true).map_err(|e| BbsFfiError(format!("{:?}", e)))
+}
+
+fn fr_from_u32(i: u32) -> Fr {
+    Fr::from_repr(FrRepr::from(i as u64)).unwrap()
+}
+
+/// Signing needs `2t - 1` parties, so `threshold` can be at most (`total` + 1) / 2
+// `div_ceil` needs Rust 1.73
+#[allow(clippy::manual_div_ceil)]
+fn check_threshold(threshold: u32, total: u32) -> Result<(), BbsFfiError> {
+    let max = (total as u64 + 1) / 2;
+    if threshold == 0 || threshold as u64 > max {
+        return Err(BbsFfiError(format!(
+            "Threshold must be between 1 and {} so that 2 * threshold - 1 of {} parties can sign, found {}",
+            max, total, threshold
+        )));
+    }
+    Ok(())
+}
+
+/// Evaluate a polynomial with `secret` as the constant term and
+/// the given higher order coefficients at 1..=`total`
+fn split(secret: Fr, coefficients: &[Fr], total: u32) -> Vec<Share> {
+    (1..=total)
+        .map(|index| {
+            let x = fr_from_u32(index);
+            // Horner's method from the highest coefficient down
+            let mut value = Fr::zero();
+            for c in coefficients.iter().rev() {
+                value.add_assign(c);
+                value.mul_assign(&x);
+            }
+            value.add_assign(&secret);
+            Share { index, value }
+        })
+        .collect()
+}
+
+/// Deal `total` shares of a secret key derived from `seed` on a degree `threshold - 1`
+/// polynomial. Any `2 * threshold - 1` of them can sign together
+pub(crate) fn split_secret_key(
+    seed: &[u8],
+    threshold: u32,
+    total: u32,
+) -> Result<(Fr, Vec<Share>), BbsFfiError> {
+    check_threshold(threshold, total)?;
+    let sk = gen_sk(seed);
+    let coefficients: Vec<Fr> = (1..threshold)
+        .map(|k| {
+            let mut data = seed.to_vec();
+            data.extend_from_slice(b"SHARE-COEFFICIENT-");
+            data.extend_from_slice(&k.to_be_bytes());
+            gen_sk(&data)
+        })
+        .collect();
+    Ok((sk, split(sk, &coefficients, total)))
+}
+
+/// Shares of a fresh random value for the joint nonce, each with a share of a fresh
+/// degree `2t - 2` sharing of zero that masks the partial signatures
+pub(crate) fn random_shares(threshold: u32, total: u32) -> Result<Vec<NonceShare>, BbsFfiError> {
+    check_threshold(threshold, total)?;
+    let secret = Fr::random(&mut BbsRng);
+    let coefficients: Vec<Fr> = (1..threshold).map(|_| Fr::random(&mut BbsRng)).collect();
+    let zero_coefficients: Vec<Fr> = (1..2 * threshold - 1)
+        .map(|_| Fr::random(&mut BbsRng))
+        .collect();
+    Ok(split(secret, &coefficients, total)
+        .iter()
+        .zip(split(Fr::zero(), &zero_coefficients, total).iter())
+        .map(|(r, z)| NonceShare {
+            index: r.index,
+            r: r.value,
+            zero: z.value,
+        })
+        .collect())
+}
+
+/// The Lagrange coefficient at zero for `index` in the set `indices`
+fn lagrange_at_zero(index: u32, indices: &[u32]) -> Fr {
+    let xi = fr_from_u32(index);
+    let mut num = Fr::one();
+    let mut den = Fr::one();
+    for &j in indices.iter().filter(|&&j| j != index) {
+        let xj = fr_from_u32(j);
+        num.mul_assign(&xj);
+        let mut d = xj;
+        d.sub_assign(&xi);
+        den.mul_assign(&d);
+    }
+    num.mul_assign(&den.inverse().unwrap());
+    num
+}
+
+/// `B` = `g1` * `h0` ^ `s` * `h_1` ^ `m_1` * ... * `h_n` ^ `m_n`
+fn signature_base(messages: &[SignatureMessage], pk: &PublicKey, s: Fr) -> Result<G1, BbsFfiError> {
+    if messages.len() != pk.message_count() {
+        return Err(BbsFfiError(format!(
+            "Public key expects {} messages, found {}",
+            pk.message_count(),
+            messages.len()
+        )));
+    }
+    crate::signer::signature_base(pk, messages.iter().enumerate(), s, None)
+}
+
+/// Compute this party's partial signature. The nonce and mask shares are the sums of
+/// the shares received from every party taking part in the signature. Without the mask
+/// `u` would be a point on `r(z) * (x(z) + e)`, which the combiner could factor to learn `x`
+pub(crate) fn partial_sign(
+    messages: &[SignatureMessage],
+    pk: &PublicKey,
+    key_share: &Share,
+    nonce_shares: &[NonceShare],
+    session: &Session,
+) -> Result<PartialSignature, BbsFfiError> {
+    if nonce_shares.is_empty() {
+        return Err(BbsFfiError::new("Nonce shares cannot be empty"));
+    }
+    let mut r = Fr::zero();
+    let mut zero = Fr::zero();
+    for n in nonce_shares {
+        if n.index != key_share.index {
+            return Err(BbsFfiError(format!(
+                "Nonce share index {} does not match secret key share index {}",
+                n.index, key_share.index
+            )));
+        }
+        r.add_assign(&n.r);
+        zero.add_assign(&n.zero);
+    }
+    let mut u = key_share.value;
+    u.add_assign(&session.e);
+    u.mul_assign(&r);
+    u.add_assign(&zero);
+    zero.zeroize();
+    let mut big_r = signature_base(messages, pk, session.s)?;
+    big_r.mul_assign(r);
+    Ok(PartialSignature {
+        index: key_share.index,
+        u,
+        r: big_r,
+    })
+}
+
+/// Interpolate `u` = `r` * (`x` + `e`) + `z` and `R` = `B` ^ `r` at zero and output
+/// the signature (`A` = `R` ^ (1 / `u`), `e`, `s`). `u` has degree `2t - 2`
+/// so at least `2t - 1` partial signatures are needed
+pub(crate) fn combine(
+    partials: &[PartialSignature],
+    session: &Session,
+) -> Result<Signature, BbsFfiError> {
+    if partials.is_empty() {
+        return Err(BbsFfiError::new("Partial signatures cannot be empty"));
+    }
+    let indices: Vec<u32> = partials.iter().map(|p| p.index).collect();
+    for (i, index) in indices.iter().enumerate() {
+        if indices[..i].contains(index) {
+            return Err(BbsFfiError(format!(
+                "Duplicate partial signature index {}",
+                index
+            )));
+        }
+    }
+    let mut u = Fr::zero();
+    let mut big_r = G1::zero();
+    for p in partials {
+        let l = lagrange_at_zero(p.index, &indices);
+        let mut u_i = p.u;
+        u_i.mul_assign(&l);
+        u.add_assign(&u_i);
+        let mut r_i = p.r;
+        r_i.mul_assign(l);
+        big_r.add_assign(&r_i);
+    }
+    let u_inv = u
+        .inverse()
+        .ok_or_else(|| BbsFfiError::new("Partial signatures combine to zero"))?;
+    big_r.mul_assign(u_inv);
+
+    let mut out = Vec::with_capacity(SIGNATURE_COMPRESSED_SIZE);
+    big_r.serialize(&mut out, true).unwrap();
+    session.e.serialize(&mut out, true).unwrap();
+    session.s.serialize(&mut out, true).unwrap();
+    Ok(Signature::try_from(out)?)
+}
@@ -0,0 +1,233 @@
+//! UniFFI exports over the `api` builders, declared in `src/bbs.udl`.
+//!
+//! The Kotlin and Swift bindings are generated from the built library with
+//! `cargo run --features uniffi-bindgen --bin uniffi-bindgen -- generate --library <lib>`.
+//! Binary inputs and outputs are byte arrays in the same formats as the C ABI.
+
+// The generated scaffolding has a doc comment followed by blank lines
+#![allow(clippy::empty_line_after_doc_comments)]
+
+use crate::api::{
+    self, BlindCommitmentBuilder, BlindSignBuilder, BlindSignature, BlindSignatureContext,
+    Commitment, CreateProofBuilder, DeterministicPublicKey, HashElem, HiddenMessage, Parse, Proof,
+    ProofMessage, ProofNonce, PublicKey, SecretKey, SignBuilder, Signature, SignatureBlinding,
+    SignatureMessage, ToVariableLengthBytes, VerifyBlindCommitmentBuilder, VerifyBuilder,
+    VerifyProofBuilder,
+};
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt,
+};
+
+::uniffi::include_scaffolding!("bbs");
+
+/// The errors thrown across the UniFFI boundary
+#[derive(Debug)]
+pub enum BbsError {
+    /// A public or secret key is malformed
+    InvalidKey(String),
+    /// A signature is malformed
+    InvalidSignature(String),
+    /// Any other failure
+    Failed(String),
+}
+
+impl fmt::Display for BbsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BbsError::InvalidKey(message)
+            | BbsError::InvalidSignature(message)
+            | BbsError::Failed(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for BbsError {}
+
+impl From<api::Error> for BbsError {
+    fn from(e: api::Error) -> Self {
+        BbsError::Failed(e.message().to_string())
+    }
+}
+
+type Result<T> = std::result::Result<T, BbsError>;
+
+fn parse<T: Parse>(bytes: Vec<u8>, what: &str) -> Result<T> {
+    T::parse(&bytes).map_err(|e| {
+        let message = e.message().to_string();
+        match what {
+            "public key" | "secret key" => BbsError::InvalidKey(message),
+            "signature" | "blind signature" => BbsError::InvalidSignature(message),
+            _ => BbsError::Failed(message),
+        }
+    })
+}
+
+fn hash_messages(messages: &[Vec<u8>]) -> Vec<SignatureMessage> {
+    messages.iter().map(SignatureMessage::hash).collect()
+}
+
+fn hash_indexed_messages(
+    messages: &HashMap<u32, Vec<u8>>,
+) -> impl Iterator<Item = (usize, SignatureMessage)> + '_ {
+    messages
+        .iter()
+        .map(|(i, m)| (*i as usize, SignatureMessage::hash(m)))
+}
+
+/// A BLS key pair with the public key in G2
+pub struct KeyPair {
+    pub public_key: Vec<u8>,
+    pub secret_key: Vec<u8>,
+}
+
+/// The holder's output from `blind_commitment`
+pub struct BlindCommitment {
+    pub commitment: Vec<u8>,
+    pub context: Vec<u8>,
+    pub blinding_factor: Vec<u8>,
+}
+
+/// Generate a BLS key pair in G2, deterministically from `seed` if set
+pub fn generate_key(seed: Option<Vec<u8>>) -> Result<KeyPair> {
+    let (pk, sk) = api::generate_key(seed.as_deref())?;
+    Ok(KeyPair {
+        public_key: pk.to_bytes_compressed_form().to_vec(),
+        secret_key: sk.to_bytes_compressed_form().to_vec(),
+    })
+}
+
+/// Convert a BLS public key to a BBS public key for `message_count` messages
+pub fn bls_public_key_to_bbs_key(public_key: Vec<u8>, message_count: u32) -> Result<Vec<u8>> {
+    let dpk = parse::<DeterministicPublicKey>(public_key, "public key")?;
+    let pk = dpk
+        .to_public_key(message_count as usize)
+        .map_err(|e| BbsError::InvalidKey(format!("{:?}", e)))?;
+    Ok(pk.to_bytes_compressed_form())
+}
+
+/// Sign `messages`
+pub fn sign(secret_key: Vec<u8>, public_key: Vec<u8>, messages: Vec<Vec<u8>>) -> Result<Vec<u8>> {
+    let sk = parse::<SecretKey>(secret_key, "secret key")?;
+    let pk = parse::<PublicKey>(public_key, "public key")?;
+    let signature = SignBuilder::new(&pk)
+        .secret_key(&sk)
+        .prehashed_messages(hash_messages(&messages))
+        .sign()?;
+    Ok(signature.to_bytes_compressed_form().to_vec())
+}
+
+/// `true` if `signature` is valid for `messages`
+pub fn verify(public_key: Vec<u8>, signature: Vec<u8>, messages: Vec<Vec<u8>>) -> Result<bool> {
+    let pk = parse::<PublicKey>(public_key, "public key")?;
+    let signature = parse::<Signature>(signature, "signature")?;
+    Ok(VerifyBuilder::new(&pk, &signature)
+        .prehashed_messages(hash_messages(&messages))
+        .verify()?)
+}
+
+/// Commit to the hidden `messages` by index
+pub fn blind_commitment(
+    public_key: Vec<u8>,
+    nonce: Vec<u8>,
+    messages: HashMap<u32, Vec<u8>>,
+) -> Result<BlindCommitment> {
+    let pk = parse::<PublicKey>(public_key, "public key")?;
+    let nonce = ProofNonce::hash(&nonce);
+    let res = BlindCommitmentBuilder::new(&pk, &nonce)
+        .prehashed_messages(hash_indexed_messages(&messages))
+        .commit()?;
+    Ok(BlindCommitment {
+        commitment: res.commitment.to_bytes_compressed_form().to_vec(),
+        context: res.context.to_bytes_compressed_form(),
+        blinding_factor: res.blinding_factor.to_bytes_compressed_form().to_vec(),
+    })
+}
+
+/// `true` if the proof in the commitment `context` is valid for the `blinded` indices
+pub fn verify_blind_commitment(
+    public_key: Vec<u8>,
+    context: Vec<u8>,
+    nonce: Vec<u8>,
+    blinded: Vec<u32>,
+) -> Result<bool> {
+    let pk = parse::<PublicKey>(public_key, "public key")?;
+    let context = parse::<BlindSignatureContext>(context, "commitment context")?;
+    let nonce = ProofNonce::hash(&nonce);
+    Ok(blinded
+        .iter()
+        .fold(
+            VerifyBlindCommitmentBuilder::new(&pk, &context, &nonce),
+            |builder, i| builder.blinded(*i as usize),
+        )
+        .verify()?)
+}
+
+/// Sign the holder's `commitment` and the known `messages` by index
+pub fn blind_sign(
+    secret_key: Vec<u8>,
+    public_key: Vec<u8>,
+    commitment: Vec<u8>,
+    messages: HashMap<u32, Vec<u8>>,
+) -> Result<Vec<u8>> {
+    let sk = parse::<SecretKey>(secret_key, "secret key")?;
+    let pk = parse::<PublicKey>(public_key, "public key")?;
+    let commitment = parse::<Commitment>(commitment, "commitment")?;
+    let signature = BlindSignBuilder::new(&pk, &commitment)
+        .secret_key(&sk)
+        .prehashed_messages(hash_indexed_messages(&messages))
+        .sign()?;
+    Ok(signature.to_bytes_compressed_form().to_vec())
+}
+
+/// Unblind `blind_signature` with the holder's `blinding_factor`
+pub fn unblind_signature(blind_signature: Vec<u8>, blinding_factor: Vec<u8>) -> Result<Vec<u8>> {
+    let blind_signature = parse::<BlindSignature>(blind_signature, "blind signature")?;
+    let blinding_factor = parse::<SignatureBlinding>(blinding_factor, "blinding factor")?;
+    let signature = api::unblind_signature(&blind_signature, &blinding_factor);
+    Ok(signature.to_bytes_compressed_form().to_vec())
+}
+
+/// Prove knowledge of `signature` over all signed `messages`, revealing the messages at `revealed`
+pub fn create_proof(
+    signature: Vec<u8>,
+    public_key: Vec<u8>,
+    nonce: Vec<u8>,
+    messages: Vec<Vec<u8>>,
+    revealed: Vec<u32>,
+) -> Result<Vec<u8>> {
+    let signature = parse::<Signature>(signature, "signature")?;
+    let pk = parse::<PublicKey>(public_key, "public key")?;
+    let nonce = ProofNonce::hash(&nonce);
+    let revealed: BTreeSet<usize> = revealed.into_iter().map(|i| i as usize).collect();
+    let messages = hash_messages(&messages)
+        .into_iter()
+        .enumerate()
+        .map(|(i, m)| {
+            if revealed.contains(&i) {
+                ProofMessage::Revealed(m)
+            } else {
+                ProofMessage::Hidden(HiddenMessage::ProofSpecificBlinding(m))
+            }
+        });
+    let proof = CreateProofBuilder::new(&pk, &signature, &nonce)
+        .proof_messages(messages)
+        .create()?;
+    Ok(proof.to_bytes())
+}
+
+/// `true` if `proof` is valid for the revealed `messages` in index order
+pub fn verify_proof(
+    public_key: Vec<u8>,
+    proof: Vec<u8>,
+    nonce: Vec<u8>,
+    messages: Vec<Vec<u8>>,
+) -> Result<bool> {
+    let pk = parse::<PublicKey>(public_key, "public key")?;
+    let proof = parse::<Proof>(proof, "proof")?;
+    let nonce = ProofNonce::hash(&nonce);
+    let status = VerifyProofBuilder::new(&pk, &proof, &nonce)
+        .prehashed_messages(hash_messages(&messages))
+        .verify()?;
+    Ok(status.is_valid())
+}
@@ -0,0 +1,186 @@
+use crate::ByteArray;
+use crate::last_error::ErrorSlot;
+use bbs::prelude::*;
+use ffi_support::{ErrorCode, ExternError};
+use pairing_plus::{
+    bls12_381::{Fr, G1Compressed, G1Uncompressed, G2Compressed, G2Uncompressed},
+    serdes::SerDes,
+    CurveAffine, EncodedPoint, GroupDecodingError,
+};
+
+/// The reason a key or signature failed validation.
+/// The value is also the code returned by the validation functions.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ValidationResult {
+    Valid = 0,
+    InvalidLength = 1,
+    InvalidEncoding = 2,
+    NotOnCurve = 3,
+    NotInSubgroup = 4,
+    IdentityElement = 5,
+    InvalidScalar = 6,
+}
+
+/// Describes which element of the input failed and why
+struct ValidationError {
+    reason: ValidationResult,
+    message: String,
+}
+
+impl ValidationError {
+    fn new(reason: ValidationResult, message: String) -> Self {
+        Self { reason, message }
+    }
+}
+
+fn decoding_reason(e: &GroupDecodingError) -> ValidationResult {
+    match e {
+        GroupDecodingError::NotOnCurve => ValidationResult::NotOnCurve,
+        GroupDecodingError::NotInSubgroup => ValidationResult::NotInSubgroup,
+        _ => ValidationResult::InvalidEncoding,
+    }
+}
+
+/// Decode a point and check it is on the curve, in the prime order subgroup and not the identity
+fn check_point<E: EncodedPoint>(data: &[u8], name: &str) -> Result<(), ValidationError> {
+    let mut encoded = E::empty();
+    encoded.as_mut().copy_from_slice(data);
+    match encoded.into_affine() {
+        Err(e) => Err(ValidationError::new(
+            decoding_reason(&e),
+            format!("{} is invalid: {:?}", name, e),
+        )),
+        Ok(p) => {
+            if p.is_zero() {
+                Err(ValidationError::new(
+                    ValidationResult::IdentityElement,
+                    format!("{} is the identity element", name),
+                ))
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
+fn check_scalar(data: &[u8], name: &str) -> Result<(), ValidationError> {
+    let mut data = data;
+    Fr::deserialize(&mut data, true).map(|_| ()).map_err(|e| {
+        ValidationError::new(
+            ValidationResult::InvalidScalar,
+            format!("{} is not a valid field element: {}", name, e),
+        )
+    })
+}
+
+fn check_g2_public_key(data: &[u8]) -> Result<(), ValidationError> {
+    match data.len() {
+        G2_COMPRESSED_SIZE => check_point::<G2Compressed>(data, "Public key"),
+        G2_UNCOMPRESSED_SIZE => check_point::<G2Uncompressed>(data, "Public key"),
+        l => Err(ValidationError::new(
+            ValidationResult::InvalidLength,
+            format!(
+                "Public key must be {} or {} bytes, found {}",
+                G2_COMPRESSED_SIZE, G2_UNCOMPRESSED_SIZE, l
+            ),
+        )),
+    }
+}
+
+// `is_multiple_of` needs Rust 1.87
+#[allow(clippy::manual_is_multiple_of)]
+fn check_bbs_public_key(data: &[u8]) -> Result<(), ValidationError> {
+    const HEADER: usize = G2_COMPRESSED_SIZE + G1_COMPRESSED_SIZE + 4;
+    if data.len() < HEADER || (data.len() - HEADER) % G1_COMPRESSED_SIZE != 0 {
+        return Err(ValidationError::new(
+            ValidationResult::InvalidLength,
+            format!("Public key has an invalid length {}", data.len()),
+        ));
+    }
+    let message_count = u32::from_be_bytes(*array_ref![data, HEADER - 4, 4]) as usize;
+    let found = (data.len() - HEADER) / G1_COMPRESSED_SIZE;
+    if message_count == 0 || message_count != found {
+        return Err(ValidationError::new(
+            ValidationResult::InvalidLength,
+            format!(
+                "Public key declares {} message generators, found {}",
+                message_count, found
+            ),
+        ));
+    }
+
+    check_point::<G2Compressed>(&data[..G2_COMPRESSED_SIZE], "Public key w")?;
+    check_point::<G1Compressed>(
+        &data[G2_COMPRESSED_SIZE..G2_COMPRESSED_SIZE + G1_COMPRESSED_SIZE],
+        "Public key h0",
+    )?;
+    for (i, h) in data[HEADER..].chunks(G1_COMPRESSED_SIZE).enumerate() {
+        check_point::<G1Compressed>(h, &format!("Public key h[{}]", i))?;
+    }
+    Ok(())
+}
+
+fn check_signature(data: &[u8]) -> Result<(), ValidationError> {
+    let g1_size = match data.len() {
+        SIGNATURE_COMPRESSED_SIZE => G1_COMPRESSED_SIZE,
+        SIGNATURE_UNCOMPRESSED_SIZE => G1_UNCOMPRESSED_SIZE,
+        l => {
+            return Err(ValidationError::new(
+                ValidationResult::InvalidLength,
+                format!(
+                    "Signature must be {} or {} bytes, found {}",
+                    SIGNATURE_COMPRESSED_SIZE, SIGNATURE_UNCOMPRESSED_SIZE, l
+                ),
+            ))
+        }
+    };
+    if g1_size == G1_COMPRESSED_SIZE {
+        check_point::<G1Compressed>(&data[..g1_size], "Signature A")?;
+    } else {
+        check_point::<G1Uncompressed>(&data[..g1_size], "Signature A")?;
+    }
+    check_scalar(&data[g1_size..g1_size + FR_COMPRESSED_SIZE], "Signature e")?;
+    check_scalar(&data[g1_size + FR_COMPRESSED_SIZE..], "Signature s")
+}
+
+fn report(res: Result<(), ValidationError>, err: &mut ExternError) -> i32 {
+    match res {
+        Ok(_) => {
+            *err = ExternError::success();
+            ValidationResult::Valid as i32
+        }
+        Err(e) => {
+            *err = ExternError::new_error(ErrorCode::new(e.reason as i32), e.message);
+            e.reason as i32
+        }
+    }
+}
+
+/// Check that `public_key` is a BLS public key in G2 that
+/// is on the curve, in the prime order subgroup and not the identity.
+/// Returns a `ValidationResult` code
+#[no_mangle]
+pub extern "C" fn bls_validate_public_key(public_key: ByteArray, err: *mut ExternError) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    report(check_g2_public_key(&public_key.to_vec()), err)
+}
+
+/// Check that `public_key` is a well formed BBS public key where
+/// `w`, `h0` and every message generator is a valid non-identity subgroup element.
+/// Returns a `ValidationResult` code
+#[no_mangle]
+pub extern "C" fn bbs_validate_public_key(public_key: ByteArray, err: *mut ExternError) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    report(check_bbs_public_key(&public_key.to_vec()), err)
+}
+
+/// Check that `signature` is a well formed BBS signature where `A` is a
+/// valid non-identity subgroup element and `e`, `s` are valid field elements.
+/// This does not verify the signature against any messages.
+/// Returns a `ValidationResult` code
+#[no_mangle]
+pub extern "C" fn bbs_validate_signature(signature: ByteArray, err: *mut ExternError) -> i32 {
+    let err: &mut ExternError = &mut ErrorSlot::new(err);
+    report(check_signature(&signature.to_vec()), err)
+}
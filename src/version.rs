@@ -0,0 +1,91 @@
+use crate::bls_ciphersuite::{BlsCiphersuite, MinPkSize, MinSigSize};
+use serde_json::json;
+use std::{ffi::CString, os::raw::c_char};
+
+/// Incremented whenever bbs.h changes incompatibly: a function is removed from the default
+/// build or changes its parameters, return value or what happens to its context handle, or a
+/// struct or enum changes layout. Adding functions does not change it. 2 since finishing a BLS
+/// aggregate or threshold signing context deletes it on failure too, and the COSE and JWP
+/// functions moved behind the `cose` and `jwp` features.
+/// Wrappers compare it with `bbs_abi_version` before calling anything else
+pub const BBS_ABI_VERSION: i32 = 2;
+
+/// The version of the proof framing written by `api::Proof::to_bytes`, as reported by `bbs_proof_inspect`
+pub const BBS_PROOF_FORMAT_VERSION: u32 = 1;
+
+/// The release version from package.json, see the note on `version` in Cargo.toml
+pub const LIBRARY_VERSION: &str = env!("BBS_LIBRARY_VERSION");
+
+/// Ciphersuite id for BBS+ signatures over BLS12-381 with messages hashed by Blake2b
+pub const BBS_CIPHERSUITE: &str = "BBS+_BLS12381G1_BLAKE2B";
+
+/// Optional proof capabilities beyond revealing and hiding signed messages
+const PROOF_EXTENSIONS: &[&str] = &["blind-commitment", "external-blinding", "blinded-key-proof"];
+
+/// Serialized forms accepted besides raw bytes, depending on the compiled features
+const ENCODINGS: &[(&str, bool)] = &[
+    ("envelope", true),
+    ("cose", cfg!(feature = "cose")),
+    ("jwp", cfg!(feature = "jwp")),
+];
+
+lazy_static! {
+    static ref LIBRARY_VERSION_C: CString = CString::new(LIBRARY_VERSION).unwrap();
+    static ref SUPPORTED_FEATURES_C: CString =
+        CString::new(supported_features().to_string()).unwrap();
+}
+
+fn ciphersuite_id<C: BlsCiphersuite>() -> &'static str {
+    std::str::from_utf8(C::SIG_DST).unwrap()
+}
+
+/// What this build of the library supports, as returned by `bbs_supported_features`
+pub fn supported_features() -> serde_json::Value {
+    json!({
+        "libraryVersion": LIBRARY_VERSION,
+        "abiVersion": BBS_ABI_VERSION,
+        "proofFormatVersion": BBS_PROOF_FORMAT_VERSION,
+        "ciphersuites": [
+            BBS_CIPHERSUITE,
+            ciphersuite_id::<MinSigSize>(),
+            ciphersuite_id::<MinPkSize>(),
+        ],
+        "proofExtensions": PROOF_EXTENSIONS,
+        "encodings": ENCODINGS
+            .iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>(),
+        "features": {
+            "java": cfg!(feature = "java"),
+            "wasm": cfg!(feature = "wasm"),
+            "napi": cfg!(feature = "napi"),
+            "python": cfg!(feature = "python"),
+            "uniffi": cfg!(feature = "uniffi"),
+            "deterministicRng": cfg!(feature = "deterministic-rng"),
+            "cose": cfg!(feature = "cose"),
+            "jwp": cfg!(feature = "jwp"),
+        },
+    })
+}
+
+/// The release version of this library, for example "0.2.0".
+/// The string is static and must not be freed
+#[no_mangle]
+pub extern "C" fn bbs_library_version() -> *const c_char {
+    LIBRARY_VERSION_C.as_ptr()
+}
+
+/// The `BBS_ABI_VERSION` this library was built with
+#[no_mangle]
+pub extern "C" fn bbs_abi_version() -> i32 {
+    BBS_ABI_VERSION
+}
+
+/// A JSON object with the `libraryVersion`, `abiVersion`, supported `ciphersuites`,
+/// `proofExtensions`, `encodings` and which optional cargo `features` are compiled in.
+/// The string is static and must not be freed
+#[no_mangle]
+pub extern "C" fn bbs_supported_features() -> *const c_char {
+    SUPPORTED_FEATURES_C.as_ptr()
+}
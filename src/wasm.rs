@@ -0,0 +1,324 @@
+//! WebAssembly exports over the `api` builders.
+//!
+//! Binary inputs and outputs are `Uint8Array`s in the same byte formats as the C ABI,
+//! message lists are arrays of `Uint8Array` and errors are thrown as JS `Error`s.
+
+use crate::api::{
+    self, BlindCommitmentBuilder, BlindSignBuilder, BlindSignature, BlindSignatureContext,
+    Commitment, CreateProofBuilder, DeterministicPublicKey, HashElem, HiddenMessage, Parse, Proof,
+    ProofMessage, ProofNonce, PublicKey, SecretKey, SignBuilder, Signature, SignatureBlinding,
+    SignatureMessage, ToVariableLengthBytes, VerifyBlindCommitmentBuilder, VerifyBuilder,
+    VerifyProofBuilder,
+};
+use js_sys::{Array, Object, Reflect, Uint8Array};
+use std::{collections::BTreeSet, fmt::Debug};
+use wasm_bindgen::prelude::*;
+
+fn js_error<E: Debug>(e: E) -> JsValue {
+    js_sys::Error::new(&format!("{:?}", e)).into()
+}
+
+impl From<api::Error> for JsValue {
+    fn from(e: api::Error) -> Self {
+        js_sys::Error::new(e.message()).into()
+    }
+}
+
+fn parse<T: Parse>(bytes: &[u8]) -> Result<T, JsValue> {
+    Ok(T::parse(bytes)?)
+}
+
+fn hash_messages(messages: &Array) -> Result<Vec<SignatureMessage>, JsValue> {
+    messages
+        .iter()
+        .map(|m| {
+            m.dyn_into::<Uint8Array>()
+                .map(|m| SignatureMessage::hash(m.to_vec()))
+                .map_err(|_| js_sys::Error::new("Messages must be Uint8Arrays").into())
+        })
+        .collect()
+}
+
+fn indexed_messages(
+    indices: &[u32],
+    messages: &Array,
+) -> Result<Vec<(usize, SignatureMessage)>, JsValue> {
+    let messages = hash_messages(messages)?;
+    if indices.len() != messages.len() {
+        return Err(js_sys::Error::new("Indices and messages are not equal").into());
+    }
+    Ok(indices.iter().map(|i| *i as usize).zip(messages).collect())
+}
+
+fn object(fields: &[(&str, Vec<u8>)]) -> Result<Object, JsValue> {
+    let obj = Object::new();
+    for (name, value) in fields {
+        Reflect::set(&obj, &(*name).into(), &Uint8Array::from(value.as_slice()))?;
+    }
+    Ok(obj)
+}
+
+/// Generate a BLS key pair in G2, deterministically from `seed` if set.
+/// Returns `{ publicKey, secretKey }`
+#[wasm_bindgen(js_name = generateKey)]
+pub fn generate_key(seed: Option<Vec<u8>>) -> Result<Object, JsValue> {
+    let (pk, sk) = api::generate_key(seed.as_deref())?;
+    object(&[
+        ("publicKey", pk.to_bytes_compressed_form().to_vec()),
+        ("secretKey", sk.to_bytes_compressed_form().to_vec()),
+    ])
+}
+
+/// Convert a BLS public key to a BBS public key for `message_count` messages
+#[wasm_bindgen(js_name = blsPublicKeyToBbsKey)]
+pub fn bls_public_key_to_bbs_key(
+    public_key: &[u8],
+    message_count: u32,
+) -> Result<Vec<u8>, JsValue> {
+    let dpk = parse::<DeterministicPublicKey>(public_key)?;
+    let pk = dpk
+        .to_public_key(message_count as usize)
+        .map_err(js_error)?;
+    Ok(pk.to_bytes_compressed_form())
+}
+
+/// Sign `messages`
+#[wasm_bindgen]
+pub fn sign(secret_key: &[u8], public_key: &[u8], messages: &Array) -> Result<Vec<u8>, JsValue> {
+    let sk = parse::<SecretKey>(secret_key)?;
+    let pk = parse::<PublicKey>(public_key)?;
+    let signature = SignBuilder::new(&pk)
+        .secret_key(&sk)
+        .prehashed_messages(hash_messages(messages)?)
+        .sign()?;
+    Ok(signature.to_bytes_compressed_form().to_vec())
+}
+
+/// `true` if `signature` is valid for `messages`
+#[wasm_bindgen]
+pub fn verify(public_key: &[u8], signature: &[u8], messages: &Array) -> Result<bool, JsValue> {
+    let pk = parse::<PublicKey>(public_key)?;
+    let signature = parse::<Signature>(signature)?;
+    Ok(VerifyBuilder::new(&pk, &signature)
+        .prehashed_messages(hash_messages(messages)?)
+        .verify()?)
+}
+
+/// Commit to the hidden `messages` at `indices`.
+/// Returns `{ commitment, context, blindingFactor }`
+#[wasm_bindgen(js_name = blindCommitment)]
+pub fn blind_commitment(
+    public_key: &[u8],
+    nonce: &[u8],
+    indices: &[u32],
+    messages: &Array,
+) -> Result<Object, JsValue> {
+    let pk = parse::<PublicKey>(public_key)?;
+    let nonce = ProofNonce::hash(nonce);
+    let res = BlindCommitmentBuilder::new(&pk, &nonce)
+        .prehashed_messages(indexed_messages(indices, messages)?)
+        .commit()?;
+    object(&[
+        (
+            "commitment",
+            res.commitment.to_bytes_compressed_form().to_vec(),
+        ),
+        ("context", res.context.to_bytes_compressed_form()),
+        (
+            "blindingFactor",
+            res.blinding_factor.to_bytes_compressed_form().to_vec(),
+        ),
+    ])
+}
+
+/// `true` if the proof in the commitment `context` is valid for the `blinded` indices
+#[wasm_bindgen(js_name = verifyBlindCommitment)]
+pub fn verify_blind_commitment(
+    public_key: &[u8],
+    context: &[u8],
+    nonce: &[u8],
+    blinded: &[u32],
+) -> Result<bool, JsValue> {
+    let pk = parse::<PublicKey>(public_key)?;
+    let context = parse::<BlindSignatureContext>(context)?;
+    let nonce = ProofNonce::hash(nonce);
+    Ok(blinded
+        .iter()
+        .fold(
+            VerifyBlindCommitmentBuilder::new(&pk, &context, &nonce),
+            |builder, i| builder.blinded(*i as usize),
+        )
+        .verify()?)
+}
+
+/// Sign the holder's `commitment` and the known `messages` at `indices`
+#[wasm_bindgen(js_name = blindSign)]
+pub fn blind_sign(
+    secret_key: &[u8],
+    public_key: &[u8],
+    commitment: &[u8],
+    indices: &[u32],
+    messages: &Array,
+) -> Result<Vec<u8>, JsValue> {
+    let sk = parse::<SecretKey>(secret_key)?;
+    let pk = parse::<PublicKey>(public_key)?;
+    let commitment = parse::<Commitment>(commitment)?;
+    let signature = BlindSignBuilder::new(&pk, &commitment)
+        .secret_key(&sk)
+        .prehashed_messages(indexed_messages(indices, messages)?)
+        .sign()?;
+    Ok(signature.to_bytes_compressed_form().to_vec())
+}
+
+/// Unblind `blind_signature` with the holder's `blinding_factor`
+#[wasm_bindgen(js_name = unblindSignature)]
+pub fn unblind_signature(
+    blind_signature: &[u8],
+    blinding_factor: &[u8],
+) -> Result<Vec<u8>, JsValue> {
+    let blind_signature = parse::<BlindSignature>(blind_signature)?;
+    let blinding_factor = parse::<SignatureBlinding>(blinding_factor)?;
+    let signature = api::unblind_signature(&blind_signature, &blinding_factor);
+    Ok(signature.to_bytes_compressed_form().to_vec())
+}
+
+/// Prove knowledge of `signature` over all signed `messages`, revealing the messages at `revealed`
+#[wasm_bindgen(js_name = createProof)]
+pub fn create_proof(
+    signature: &[u8],
+    public_key: &[u8],
+    nonce: &[u8],
+    messages: &Array,
+    revealed: &[u32],
+) -> Result<Vec<u8>, JsValue> {
+    let signature = parse::<Signature>(signature)?;
+    let pk = parse::<PublicKey>(public_key)?;
+    let nonce = ProofNonce::hash(nonce);
+    let revealed: BTreeSet<usize> = revealed.iter().map(|i| *i as usize).collect();
+    let messages = hash_messages(messages)?
+        .into_iter()
+        .enumerate()
+        .map(|(i, m)| {
+            if revealed.contains(&i) {
+                ProofMessage::Revealed(m)
+            } else {
+                ProofMessage::Hidden(HiddenMessage::ProofSpecificBlinding(m))
+            }
+        });
+    let proof = CreateProofBuilder::new(&pk, &signature, &nonce)
+        .proof_messages(messages)
+        .create()?;
+    Ok(proof.to_bytes())
+}
+
+/// `true` if `proof` is valid for the revealed `messages` in index order
+#[wasm_bindgen(js_name = verifyProof)]
+pub fn verify_proof(
+    public_key: &[u8],
+    proof: &[u8],
+    nonce: &[u8],
+    messages: &Array,
+) -> Result<bool, JsValue> {
+    let pk = parse::<PublicKey>(public_key)?;
+    let proof = parse::<Proof>(proof)?;
+    let nonce = ProofNonce::hash(nonce);
+    let status = VerifyProofBuilder::new(&pk, &proof, &nonce)
+        .prehashed_messages(hash_messages(messages)?)
+        .verify()?;
+    Ok(status.is_valid())
+}
+
+// Run with `wasm-bindgen-test-runner` on the path:
+// `cargo test --target wasm32-unknown-unknown --features wasm --lib`
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use crate::bbs_verify_proof::*;
+    use crate::ByteArray;
+    use ffi_support::ExternError;
+    use wasm_bindgen_test::*;
+
+    const NONCE: &[u8] = b"verifier nonce";
+
+    fn messages(ms: &[&[u8]]) -> js_sys::Array {
+        ms.iter()
+            .map(|m| JsValue::from(Uint8Array::from(*m)))
+            .collect()
+    }
+
+    fn field(obj: &js_sys::Object, name: &str) -> Vec<u8> {
+        Uint8Array::new(&Reflect::get(obj, &name.into()).unwrap()).to_vec()
+    }
+
+    fn keys(message_count: u32) -> (Vec<u8>, Vec<u8>) {
+        let key = generate_key(Some(b"wasm test seed".to_vec())).unwrap();
+        let pk = bls_public_key_to_bbs_key(&field(&key, "publicKey"), message_count).unwrap();
+        (pk, field(&key, "secretKey"))
+    }
+
+    #[wasm_bindgen_test]
+    fn can_sign_and_verify() {
+        let (pk, sk) = keys(2);
+        let signature = sign(&sk, &pk, &messages(&[b"one", b"two"])).unwrap();
+        assert!(verify(&pk, &signature, &messages(&[b"one", b"two"])).unwrap());
+        assert!(!verify(&pk, &signature, &messages(&[b"one", b"three"])).unwrap());
+    }
+
+    #[wasm_bindgen_test]
+    fn sign_throws_on_invalid_public_key() {
+        let (_, sk) = keys(2);
+        assert!(sign(&sk, &[0u8; 10], &messages(&[b"one", b"two"])).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn can_blind_sign() {
+        let (pk, sk) = keys(2);
+        let commitment = blind_commitment(&pk, NONCE, &[0], &messages(&[b"hidden"])).unwrap();
+        assert!(verify_blind_commitment(&pk, &field(&commitment, "context"), NONCE, &[0]).unwrap());
+
+        let blind_signature = blind_sign(
+            &sk,
+            &pk,
+            &field(&commitment, "commitment"),
+            &[1],
+            &messages(&[b"known"]),
+        )
+        .unwrap();
+        let signature =
+            unblind_signature(&blind_signature, &field(&commitment, "blindingFactor")).unwrap();
+        assert!(verify(&pk, &signature, &messages(&[b"hidden", b"known"])).unwrap());
+    }
+
+    #[wasm_bindgen_test]
+    fn can_create_and_verify_proof() {
+        let (pk, sk) = keys(3);
+        let all = messages(&[b"one", b"two", b"three"]);
+        let signature = sign(&sk, &pk, &all).unwrap();
+        let proof = create_proof(&signature, &pk, NONCE, &all, &[0, 2]).unwrap();
+
+        // Message count then the big-endian revealed bitvector, as from the C ABI
+        assert_eq!(&proof[..3], &[0, 3, 0b101]);
+
+        assert!(verify_proof(&pk, &proof, NONCE, &messages(&[b"one", b"three"])).unwrap());
+        assert!(!verify_proof(&pk, &proof, NONCE, &messages(&[b"one", b"two"])).unwrap());
+        assert!(
+            !verify_proof(&pk, &proof, b"other nonce", &messages(&[b"one", b"three"])).unwrap()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn c_abi_verifies_wasm_proof() {
+        let (pk, sk) = keys(2);
+        let all = messages(&[b"one", b"two"]);
+        let signature = sign(&sk, &pk, &all).unwrap();
+        let proof = create_proof(&signature, &pk, NONCE, &all, &[1]).unwrap();
+
+        let mut err = ExternError::success();
+        let handle = bbs_verify_proof_context_init(&mut err);
+        bbs_verify_proof_context_add_message_bytes(handle, ByteArray::from_slice(b"two"), &mut err);
+        bbs_verify_proof_context_set_proof(handle, ByteArray::from_slice(&proof), &mut err);
+        bbs_verify_proof_context_set_public_key(handle, ByteArray::from_slice(&pk), &mut err);
+        bbs_verify_proof_context_set_nonce_bytes(handle, ByteArray::from_slice(NONCE), &mut err);
+        assert_eq!(bbs_verify_proof_context_finish(handle, &mut err), 0);
+    }
+}
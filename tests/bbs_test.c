@@ -7,6 +7,103 @@
 #include <string.h>
 #include "bbs.h"
 
+/* Append `data` as a quoted unpadded base64url string to `out` and return the new end */
+static char *append_base64url(char *out, const uint8_t *data, uintptr_t length) {
+    static const char alphabet[] = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+    uintptr_t k;
+    uint32_t bits = 0;
+    int count = 0;
+
+    *out++ = '"';
+    for (k = 0; k < length; k++) {
+        bits = (bits << 8) | data[k];
+        count += 8;
+        while (count >= 6) {
+            count -= 6;
+            *out++ = alphabet[(bits >> count) & 0x3f];
+        }
+    }
+    if (count > 0) {
+        *out++ = alphabet[(bits << (6 - count)) & 0x3f];
+    }
+    *out++ = '"';
+    *out = '\0';
+    return out;
+}
+
+/* A json verify request for the first `count` messages */
+static void verify_request(char *out, ByteArray *public_key, ByteArray **messages, int count, ByteArray *signature) {
+    int k;
+
+    out += sprintf(out, "{\"op\":\"verify\",\"publicKey\":");
+    out = append_base64url(out, public_key->data, public_key->length);
+    out += sprintf(out, ",\"signature\":");
+    out = append_base64url(out, signature->data, signature->length);
+    out += sprintf(out, ",\"messages\":[");
+    for (k = 0; k < count; k++) {
+        if (k > 0) {
+            *out++ = ',';
+        }
+        out = append_base64url(out, messages[k]->data, messages[k]->length);
+    }
+    sprintf(out, "]}");
+}
+
+/* Simulates a key store that holds the secret key passed in `user_data` */
+static int32_t external_sign(void *user_data, ByteArray b, ByteArray e, uint8_t *a, uintptr_t a_len) {
+    ByteArray *secret_key = (ByteArray*) user_data;
+    ByteBuffer out;
+    ExternError err;
+
+    if (bbs_signer_compute(*secret_key, b, e, &out, &err) != 0) {
+        bbs_string_free(err.message);
+        return 1;
+    }
+    if (out.len != a_len) {
+        bbs_byte_buffer_free(out);
+        return 2;
+    }
+    memcpy(a, out.data, a_len);
+    bbs_byte_buffer_free(out);
+    return 0;
+}
+
+/* Deterministic xorshift generator, `user_data` holds the state */
+static int32_t xorshift_rng(void *user_data, uint8_t *buffer, uintptr_t length) {
+    uint64_t *state = (uint64_t*) user_data;
+    uintptr_t k;
+
+    for (k = 0; k < length; k++) {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        buffer[k] = (uint8_t) *state;
+    }
+    return 0;
+}
+
+static int32_t failing_rng(void *user_data, uint8_t *buffer, uintptr_t length) {
+    return 1;
+}
+
+/* Calls back into the library, which asks this callback for randomness again, before
+   filling `buffer` from the xorshift state in `user_data` */
+static int32_t reentrant_rng(void *user_data, uint8_t *buffer, uintptr_t length) {
+    static int depth = 0;
+    ExternError err;
+    ByteBuffer session;
+
+    if (depth == 0) {
+        depth++;
+        if (bbs_threshold_session_init(&session, &err) != 0) {
+            depth--;
+            return 1;
+        }
+        bbs_byte_buffer_free(session);
+        depth--;
+    }
+    return xorshift_rng(user_data, buffer, length);
+}
 
 int main(int argc, char** argv) {
     const int message_count = 5;
@@ -23,9 +120,54 @@ int main(int argc, char** argv) {
     ByteArray* blind_signature;
     ByteArray* unblind_signature;
     ByteArray* proof;
+    ByteArray* identity;
+    ByteArray* pop;
+    ByteArray* g1_public_key;
+    ByteArray* g1_secret_key;
+    ByteArray* g1_pop;
+    ByteArray* bls_signature;
+    ByteArray* bls_signature_2;
+    ByteArray* bls_public_key_2;
+    ByteArray* blinded_public_key;
+    ByteArray* blinded_secret_key;
+    ByteArray* key_blinding_factor;
+    ByteArray* blinded_key_proof;
+    ByteArray* unblinded_public_key;
+    ByteArray* expected_public_key;
+    ByteArray* external_signature;
+    ByteArray* external_blind_signature;
+    ByteArray* external_unblind_signature;
+    BbsSigner signer;
+    ByteArray* threshold_public_key;
+    ByteArray* key_shares;
+    ByteArray* session;
+    ByteArray** nonce_shares;
+    ByteArray** partial_signatures;
+    ByteArray* threshold_signature;
+    ByteArray* rng_proofs[2];
+    uint64_t rng_state;
+    ByteBuffer unused_signature;
+    char *json_response;
+    char json_request[4096];
+    ByteArray share;
+    ByteBuffer revealed_indices;
+    ByteBuffer proof_envelope, key_envelope, signature_envelope, opened;
+    ArtifactType artifact_type;
+    ByteBuffer cose_signature, cose_proof, cose_kid;
+    ByteBuffer issued_jwp, presented_jwp;
+    const uint8_t jwp_header[] = "{\"alg\":\"BBS+_BLS12381G1_BLAKE2B\",\"kid\":\"issuer-1\"}";
+    const uint8_t es256_header[] = "{\"alg\":\"ES256\"}";
+    const uint8_t presentation_header[] = "{\"nonce\":\"abc\"}";
+    const uint8_t kid[] = "issuer-1";
+    const uint8_t revealed_header[] = { 0x3a, 0x00, 0x01, 0x00, 0x00, 0x82, 0x01, 0x03 };
+    uint32_t total_count, hidden_count, format_version;
+    ByteArray forged;
+    uint8_t *forged_data;
+    const uint32_t threshold = 2;
+    const uint32_t party_count = 3;
     ExternError* err;
     uint64_t handle;
-    int i;
+    int i, j;
 
     seed = (ByteArray*) malloc(sizeof(ByteArray));
     public_key = (ByteArray*) malloc(sizeof(ByteArray));
@@ -39,6 +181,35 @@ int main(int argc, char** argv) {
     blind_signature = (ByteArray*) malloc(sizeof(ByteArray));
     unblind_signature = (ByteArray*) malloc(sizeof(ByteArray));
     proof = (ByteArray*) malloc(sizeof(ByteArray));
+    identity = NULL;
+    pop = (ByteArray*) calloc(1, sizeof(ByteArray));
+    g1_public_key = (ByteArray*) calloc(1, sizeof(ByteArray));
+    g1_secret_key = (ByteArray*) calloc(1, sizeof(ByteArray));
+    g1_pop = (ByteArray*) calloc(1, sizeof(ByteArray));
+    bls_signature = (ByteArray*) calloc(1, sizeof(ByteArray));
+    bls_signature_2 = (ByteArray*) calloc(1, sizeof(ByteArray));
+    bls_public_key_2 = (ByteArray*) calloc(1, sizeof(ByteArray));
+    blinded_public_key = (ByteArray*) calloc(1, sizeof(ByteArray));
+    blinded_secret_key = (ByteArray*) calloc(1, sizeof(ByteArray));
+    key_blinding_factor = (ByteArray*) calloc(1, sizeof(ByteArray));
+    blinded_key_proof = (ByteArray*) calloc(1, sizeof(ByteArray));
+    unblinded_public_key = (ByteArray*) calloc(1, sizeof(ByteArray));
+    expected_public_key = (ByteArray*) calloc(1, sizeof(ByteArray));
+    external_signature = (ByteArray*) calloc(1, sizeof(ByteArray));
+    external_blind_signature = (ByteArray*) calloc(1, sizeof(ByteArray));
+    external_unblind_signature = (ByteArray*) calloc(1, sizeof(ByteArray));
+    threshold_public_key = (ByteArray*) calloc(1, sizeof(ByteArray));
+    key_shares = (ByteArray*) calloc(1, sizeof(ByteArray));
+    session = (ByteArray*) calloc(1, sizeof(ByteArray));
+    nonce_shares = (ByteArray**) malloc(party_count * sizeof(ByteArray*));
+    partial_signatures = (ByteArray**) malloc(party_count * sizeof(ByteArray*));
+    for (i = 0; i < party_count; i++) {
+        nonce_shares[i] = (ByteArray*) calloc(1, sizeof(ByteArray));
+        partial_signatures[i] = (ByteArray*) calloc(1, sizeof(ByteArray));
+    }
+    threshold_signature = (ByteArray*) calloc(1, sizeof(ByteArray));
+    rng_proofs[0] = (ByteArray*) calloc(1, sizeof(ByteArray));
+    rng_proofs[1] = (ByteArray*) calloc(1, sizeof(ByteArray));
     err = (ExternError*) malloc(sizeof(ExternError));
 
     seed->length = 0;
@@ -75,378 +246,663 @@ int main(int argc, char** argv) {
     }
     printf("pass\n");
 
-    printf("Create BBS key from BLS key that can sign %d messages...", message_count);
+    printf("Validate BLS public key...");
     fflush(stdout);
-    if (bls_public_key_to_bbs_key(*public_key, message_count, (ByteBuffer*) public_key, err) != 0) {
+    if (bls_validate_public_key(*public_key, err) != Valid) {
         printf("fail\n");
         goto Fail;
     }
     printf("pass\n");
 
-    for (i = 0; i < message_count; i++) {
-        message = (ByteArray*) malloc(sizeof(ByteArray));
-        message->length = 10;
-        message->data = (uint8_t *)malloc(10);
-        memset((uint8_t *)message->data, i+1, 10);
-        messages[i] = message;
-    }
-
-    printf("Create sign context...");
+    printf("Create proof of possession for G2 key...");
     fflush(stdout);
-    handle = bbs_sign_context_init(err);
-
-    if (handle == 0) {
+    if (bls_pop_prove_g2(*secret_key, (ByteBuffer*) pop, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
     printf("pass\n");
 
-    printf("Set public key in sign context...");
+    printf("Verify proof of possession for G2 key...");
     fflush(stdout);
-    if (bbs_sign_context_set_public_key(handle, *public_key, err) != 0) {
+    if (bls_pop_verify_g2(*public_key, *pop, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
     printf("pass\n");
 
-    printf("Set secret key in sign context...");
+    printf("Create G1 key pair...");
     fflush(stdout);
-    if (bbs_sign_context_set_secret_key(handle, *secret_key, err) != 0) {
+    if (bls_generate_g1_key(*seed, (ByteBuffer*) g1_public_key, (ByteBuffer*) g1_secret_key, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
     printf("pass\n");
 
-    printf("Set messages sign context...");
+    printf("Create proof of possession for G1 key...");
     fflush(stdout);
-    for (i = 0; i < message_count; i++) {
-        if (bbs_sign_context_add_message_bytes(handle, *messages[i], err) != 0) {
-            printf("fail\n");
-            goto Fail;
-        }
+    if (bls_pop_prove_g1(*g1_secret_key, (ByteBuffer*) g1_pop, err) != 0) {
+        printf("fail\n");
+        goto Fail;
     }
     printf("pass\n");
 
-    printf("Sign %d messages ...", message_count);
+    printf("Verify proof of possession for G1 key...");
     fflush(stdout);
-    if (bbs_sign_context_finish(handle, (ByteBuffer*)signature, err) != 0) {
+    if (bls_pop_verify_g1(*g1_public_key, *g1_pop, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
     printf("pass\n");
 
-    printf("Signature is correct size...");
-    if (signature->length != bbs_blind_signature_size()) {
+    printf("Reject proof of possession for another key...");
+    fflush(stdout);
+    if (bls_pop_prove_g2(*g1_secret_key, (ByteBuffer*) g1_pop, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bls_pop_verify_g2(*public_key, *g1_pop, err) == 0) {
         printf("fail\n");
-        printf("Expected %d, found %lu\n", bbs_blind_signature_size(), signature->length);
         goto Exit;
     }
+    bbs_string_free(err->message);
     printf("pass\n");
 
-    printf("Create blind commitment context...");
+    printf("Create blinded G2 key pair...");
     fflush(stdout);
-    handle = bbs_blind_commitment_context_init(err);
-    if (handle == 0) {
+    if (bls_generate_blinded_g2_key(*seed, (ByteBuffer*) blinded_public_key, (ByteBuffer*) blinded_secret_key, (ByteBuffer*) key_blinding_factor, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
     printf("pass\n");
 
-    printf("Set messages to commitment...");
+    printf("Prove knowledge of blinded key...");
     fflush(stdout);
-    if (bbs_blind_commitment_context_add_message_bytes(handle, 0, *messages[0], err) != 0) {
+    if (bls_blinded_key_prove_g2(*blinded_secret_key, *key_blinding_factor, *nonce, (ByteBuffer*) blinded_key_proof, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
+    if (blinded_key_proof->length != bls_blinded_key_proof_size()) {
+        printf("fail\n");
+        printf("Expected %d, found %lu\n", bls_blinded_key_proof_size(), blinded_key_proof->length);
+        goto Exit;
+    }
     printf("pass\n");
 
-    printf("Set public key in blind sign commitment...");
+    printf("Verify knowledge of blinded key...");
     fflush(stdout);
-    if (bbs_blind_commitment_context_set_public_key(handle, *public_key, err) != 0) {
+    if (bls_blinded_key_verify_g2(*blinded_public_key, *nonce, *blinded_key_proof, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
     printf("pass\n");
 
-    printf("Set nonce in blind sign commitment...");
+    printf("Reject blinded key proof for another nonce...");
     fflush(stdout);
-    if (bbs_blind_commitment_context_set_nonce_bytes(handle, *nonce, err) != 0) {
+    if (bls_blinded_key_verify_g2(*blinded_public_key, *secret_key, *blinded_key_proof, err) == 0) {
         printf("fail\n");
-        goto Fail;
+        goto Exit;
     }
+    bbs_string_free(err->message);
     printf("pass\n");
 
-    printf("Get blind sign commitment...");
+    printf("Unblind blinded key...");
     fflush(stdout);
-    if (bbs_blind_commitment_context_finish(handle, (ByteBuffer*)commitment, (ByteBuffer*)blind_sign_context, (ByteBuffer*)blinding_factor, err) != 0) {
+    if (bls_blinded_key_unblind_g2(*blinded_public_key, *key_blinding_factor, (ByteBuffer*) unblinded_public_key, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
-    printf("pass\n");
-
-    printf("Create verify blind signing commitment...");
-    fflush(stdout);
-    handle = bbs_verify_blind_commitment_context_init(err);
-    if (handle == 0) {
+    if (bls_get_public_key(*blinded_secret_key, (ByteBuffer*) expected_public_key, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
+    if (unblinded_public_key->length != expected_public_key->length ||
+        memcmp(unblinded_public_key->data, expected_public_key->data, expected_public_key->length) != 0) {
+        printf("fail\n");
+        goto Exit;
+    }
     printf("pass\n");
 
-    printf("Add blinded index...");
+    printf("Sign with G2 key...");
     fflush(stdout);
-    if (bbs_verify_blind_commitment_context_add_blinded(handle, 0, err) != 0) {
+    if (bls_sign_g2(*secret_key, *nonce, (ByteBuffer*) bls_signature, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
     printf("pass\n");
 
-    printf("Set public key in verify blind sign context...");
+    printf("Verify G2 key signature...");
     fflush(stdout);
-    if (bbs_verify_blind_commitment_context_set_public_key(handle, *public_key, err) != 0) {
+    if (bls_verify_g2(*public_key, *nonce, *bls_signature, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
     printf("pass\n");
 
-    printf("Set nonce in verify blind sign context...");
+    printf("Sign and verify with G1 key...");
     fflush(stdout);
-    if (bbs_verify_blind_commitment_context_set_nonce_bytes(handle, *nonce, err) != 0) {
+    if (bls_sign_g1(*g1_secret_key, *nonce, (ByteBuffer*) g1_pop, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
-    printf("pass\n");
-
-    printf("Set proof in verify blind sign context...");
-    fflush(stdout);
-    if (bbs_verify_blind_commitment_context_set_proof(handle, *blind_sign_context, err) != 0) {
+    if (bls_verify_g1(*g1_public_key, *nonce, *g1_pop, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
     printf("pass\n");
 
-    printf("Verify blind sign context...");
+    printf("Aggregate G1 signatures...");
     fflush(stdout);
-    if (bbs_verify_blind_commitment_context_finish(handle, err) != 0) {
+    if (bls_get_public_key(*g1_secret_key, (ByteBuffer*) bls_public_key_2, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
-    printf("pass\n");
-
-    printf("Create blind signing context...");
-    fflush(stdout);
-    handle = bbs_blind_sign_context_init(err);
+    if (bls_sign_g2(*g1_secret_key, *nonce, (ByteBuffer*) bls_signature_2, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    handle = bls_aggregate_context_init(err);
     if (handle == 0) {
         printf("fail\n");
         goto Fail;
     }
-    printf("pass\n");
-
-    printf("Set messages blind sign context...");
-    fflush(stdout);
-    for (i = 1; i < message_count; i++) {
-        if (bbs_blind_sign_context_add_message_bytes(handle, i, *messages[i], err) != 0) {
-            printf("fail\n");
-            goto Fail;
-        }
+    if (bls_aggregate_context_add_signature(handle, *bls_signature, err) != 0) {
+        printf("fail\n");
+        goto Fail;
     }
-    printf("pass\n");
-
-    printf("Set public key in blind sign context...");
-    fflush(stdout);
-    if (bbs_blind_sign_context_set_public_key(handle, *public_key, err) != 0) {
+    if (bls_aggregate_context_add_signature(handle, *bls_signature_2, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
-    printf("pass\n");
-
-    printf("Set secret key in blind sign context...");
-    fflush(stdout);
-    if (bbs_blind_sign_context_set_secret_key(handle, *secret_key, err) != 0) {
+    if (bls_aggregate_context_finish(handle, (ByteBuffer*) bls_signature_2, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
     printf("pass\n");
 
-    printf("Set commitment in blind sign context...");
+    printf("Fast aggregate verify...");
     fflush(stdout);
-    if (bbs_blind_sign_context_set_commitment(handle, *commitment, err) != 0) {
+    handle = bls_aggregate_verify_context_init(err);
+    if (handle == 0) {
         printf("fail\n");
         goto Fail;
     }
-    printf("pass\n");
-
-    printf("Creating blind signature...");
-    fflush(stdout);
-    if (bbs_blind_sign_context_finish(handle, (ByteBuffer*)blind_signature, err) != 0) {
+    if (bls_aggregate_verify_context_add_public_key(handle, *public_key, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
-    printf("pass\n");
-
-    printf("Unblinding signature...");
-    fflush(stdout);
-    if (bbs_unblind_signature(*blind_signature, *blinding_factor, (ByteBuffer*)unblind_signature, err) != 0) {
+    if (bls_aggregate_verify_context_add_public_key(handle, *bls_public_key_2, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bls_aggregate_verify_context_add_message(handle, *nonce, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bls_aggregate_verify_context_set_signature(handle, *bls_signature_2, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bls_fast_aggregate_verify_context_finish(handle, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
     printf("pass\n");
 
-    printf("Create new verify signature context...");
+    printf("Aggregate verify...");
     fflush(stdout);
-    handle = bbs_verify_context_init(err);
+    handle = bls_aggregate_verify_context_init(err);
     if (handle == 0) {
         printf("fail\n");
         goto Fail;
     }
-    printf("pass\n");
-
-    printf("Set messages in verify signature context...");
-    fflush(stdout);
-    for (i = 0; i < message_count; i++) {
-        if (bbs_verify_context_add_message_bytes(handle, *messages[i], err) != 0) {
+    for (i = 0; i < 2; i++) {
+        if (bls_aggregate_verify_context_add_public_key(handle, i == 0 ? *public_key : *bls_public_key_2, err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+        if (bls_aggregate_verify_context_add_message(handle, *nonce, err) != 0) {
             printf("fail\n");
             goto Fail;
         }
     }
-    printf("pass\n");
-
-    printf("Set public key in verify signature context...");
-    fflush(stdout);
-    if (bbs_verify_context_set_public_key(handle, *public_key, err) != 0) {
+    if (bls_aggregate_verify_context_set_signature(handle, *bls_signature_2, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
-    printf("pass\n");
-
-    printf("Set signature in verify signature context...");
-    fflush(stdout);
-    if (bbs_verify_context_set_signature(handle, *unblind_signature, err) != 0) {
+    if (bls_aggregate_verify_context_finish(handle, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
     printf("pass\n");
 
-    printf("Verifying signature...");
+    printf("Reject aggregate missing a signer...");
     fflush(stdout);
-    if (bbs_verify_context_finish(handle, err) != 0) {
+    handle = bls_aggregate_verify_context_init(err);
+    bls_aggregate_verify_context_add_public_key(handle, *public_key, err);
+    bls_aggregate_verify_context_add_message(handle, *nonce, err);
+    bls_aggregate_verify_context_set_signature(handle, *bls_signature_2, err);
+    if (bls_fast_aggregate_verify_context_finish(handle, err) == 0) {
         printf("fail\n");
-        goto Fail;
+        goto Exit;
     }
-    printf("pass\n");
-
-    printf("Create new proof context...");
-    fflush(stdout);
-    handle = bbs_create_proof_context_init(err);
-    if (handle == 0) {
+    bbs_string_free(err->message);
+    /* The failed finish deleted the context */
+    if (bls_aggregate_verify_context_add_message(handle, *nonce, err) == 0) {
         printf("fail\n");
-        goto Fail;
+        goto Exit;
     }
+    bbs_string_free(err->message);
     printf("pass\n");
 
-    printf("Adding messages to proof context...");
+    printf("Reject identity BLS public key...");
     fflush(stdout);
-    if (bbs_create_proof_context_add_proof_message_bytes(handle, *messages[0], Revealed, *blinding_factor, err) != 0) {
-        printf("fail\n");
-        goto Fail;
-    }
-    if (bbs_create_proof_context_add_proof_message_bytes(handle, *messages[1], Revealed, *blinding_factor, err) != 0) {
+    identity = (ByteArray*) malloc(sizeof(ByteArray));
+    identity->length = bls_public_key_g2_size();
+    identity->data = (uint8_t *)calloc(identity->length, 1);
+    ((uint8_t *)identity->data)[0] = 0xc0;
+    if (bls_validate_public_key(*identity, err) != IdentityElement) {
         printf("fail\n");
-        goto Fail;
-    }
-    for (i = 2; i < message_count; i++) {
-        if (bbs_create_proof_context_add_proof_message_bytes(handle, *messages[i], HiddenProofSpecificBlinding, *blinding_factor, err) != 0) {
-            printf("fail\n");
-            goto Fail;
-        }
+        goto Exit;
     }
+    bbs_string_free(err->message);
     printf("pass\n");
 
-    printf("Setting signature in proof context...");
+    printf("Create BBS key from BLS key that can sign %d messages...", message_count);
     fflush(stdout);
-    if (bbs_create_proof_context_set_signature(handle, *unblind_signature, err) != 0) {
+    if (bls_public_key_to_bbs_key(*public_key, message_count, (ByteBuffer*) public_key, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
     printf("pass\n");
 
-    printf("Set public key in proof context...");
+    printf("Validate BBS public key...");
     fflush(stdout);
-    if (bbs_create_proof_context_set_public_key(handle, *public_key, err) != 0) {
+    if (bbs_validate_public_key(*public_key, err) != Valid) {
         printf("fail\n");
         goto Fail;
     }
     printf("pass\n");
 
-    printf("Set nonce in proof context...");
+    for (i = 0; i < message_count; i++) {
+        message = (ByteArray*) malloc(sizeof(ByteArray));
+        message->length = 10;
+        message->data = (uint8_t *)malloc(10);
+        memset((uint8_t *)message->data, i+1, 10);
+        messages[i] = message;
+    }
+
+    printf("Create sign context...");
     fflush(stdout);
-    if (bbs_create_proof_context_set_nonce_bytes(handle, *nonce, err) != 0) {
+    handle = bbs_sign_context_init(err);
+
+    if (handle == 0) {
         printf("fail\n");
         goto Fail;
     }
     printf("pass\n");
 
-    printf("Creating proof...");
+    printf("Set public key in sign context...");
     fflush(stdout);
-    if (bbs_create_proof_context_finish(handle, (ByteBuffer*)proof, err) != 0) {
+    if (bbs_sign_context_set_public_key(handle, *public_key, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
     printf("pass\n");
 
-    printf("Create verify proof context...");
+    printf("Set secret key in sign context...");
     fflush(stdout);
-    handle = bbs_verify_proof_context_init(err);
-    if (handle == 0) {
+    if (bbs_sign_context_set_secret_key(handle, *secret_key, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
     printf("pass\n");
 
-    printf("Adding revealed messages to verify proof context...");
+    printf("Set messages sign context...");
     fflush(stdout);
-    for (i = 0; i < 2; i++) {
-        if (bbs_verify_proof_context_add_message_bytes(handle, *messages[i], err) != 0) {
+    for (i = 0; i < message_count; i++) {
+        if (bbs_sign_context_add_message_bytes(handle, *messages[i], err) != 0) {
             printf("fail\n");
             goto Fail;
         }
     }
     printf("pass\n");
 
-    printf("Set proof in verify proof context...");
+    printf("Sign %d messages ...", message_count);
     fflush(stdout);
-    if (bbs_verify_proof_context_set_proof(handle, *proof, err) != 0) {
+    if (bbs_sign_context_finish(handle, (ByteBuffer*)signature, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
     printf("pass\n");
 
-    printf("Set public key in verify proof context...");
-    fflush(stdout);
-    if (bbs_verify_proof_context_set_public_key(handle, *public_key, err) != 0) {
+    printf("Signature is correct size...");
+    if (signature->length != bbs_blind_signature_size()) {
         printf("fail\n");
-        goto Fail;
+        printf("Expected %d, found %lu\n", bbs_blind_signature_size(), signature->length);
+        goto Exit;
     }
     printf("pass\n");
 
-    printf("Set nonce in verify proof context..");
+    printf("Sign %d messages with external signer...", message_count);
     fflush(stdout);
-    if (bbs_verify_proof_context_set_nonce_bytes(handle, *nonce, err) != 0) {
+    signer.user_data = secret_key;
+    signer.sign = external_sign;
+    handle = bbs_sign_context_init(err);
+    if (handle == 0) {
         printf("fail\n");
         goto Fail;
     }
-    printf("pass\n");
-
-    printf("Verify blind sign context...");
-    fflush(stdout);
-    if (bbs_verify_proof_context_finish(handle, err) != 0) {
+    if (bbs_sign_context_set_public_key(handle, *public_key, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
-    printf("pass\n");
-
-    printf("Create new proof context 2...");
-    fflush(stdout);
+    if (bbs_sign_context_set_signer(handle, signer, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    for (i = 0; i < message_count; i++) {
+        if (bbs_sign_context_add_message_bytes(handle, *messages[i], err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+    }
+    if (bbs_sign_context_finish(handle, (ByteBuffer*)external_signature, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Verify external signer signature...");
+    fflush(stdout);
+    handle = bbs_verify_context_init(err);
+    if (handle == 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    for (i = 0; i < message_count; i++) {
+        if (bbs_verify_context_add_message_bytes(handle, *messages[i], err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+    }
+    if (bbs_verify_context_set_public_key(handle, *public_key, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_verify_context_set_signature(handle, *external_signature, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_verify_context_finish(handle, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Validate signature...");
+    fflush(stdout);
+    if (bbs_validate_signature(*signature, err) != Valid) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Create blind commitment context...");
+    fflush(stdout);
+    handle = bbs_blind_commitment_context_init(err);
+    if (handle == 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Set messages to commitment...");
+    fflush(stdout);
+    if (bbs_blind_commitment_context_add_message_bytes(handle, 0, *messages[0], err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Set public key in blind sign commitment...");
+    fflush(stdout);
+    if (bbs_blind_commitment_context_set_public_key(handle, *public_key, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Set nonce in blind sign commitment...");
+    fflush(stdout);
+    if (bbs_blind_commitment_context_set_nonce_bytes(handle, *nonce, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Get blind sign commitment...");
+    fflush(stdout);
+    if (bbs_blind_commitment_context_finish(handle, (ByteBuffer*)commitment, (ByteBuffer*)blind_sign_context, (ByteBuffer*)blinding_factor, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Create verify blind signing commitment...");
+    fflush(stdout);
+    handle = bbs_verify_blind_commitment_context_init(err);
+    if (handle == 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Add blinded index...");
+    fflush(stdout);
+    if (bbs_verify_blind_commitment_context_add_blinded(handle, 0, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Set public key in verify blind sign context...");
+    fflush(stdout);
+    if (bbs_verify_blind_commitment_context_set_public_key(handle, *public_key, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Set nonce in verify blind sign context...");
+    fflush(stdout);
+    if (bbs_verify_blind_commitment_context_set_nonce_bytes(handle, *nonce, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Set proof in verify blind sign context...");
+    fflush(stdout);
+    if (bbs_verify_blind_commitment_context_set_proof(handle, *blind_sign_context, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Verify blind sign context...");
+    fflush(stdout);
+    if (bbs_verify_blind_commitment_context_finish(handle, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Create blind signing context...");
+    fflush(stdout);
+    handle = bbs_blind_sign_context_init(err);
+    if (handle == 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Set messages blind sign context...");
+    fflush(stdout);
+    for (i = 1; i < message_count; i++) {
+        if (bbs_blind_sign_context_add_message_bytes(handle, i, *messages[i], err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+    }
+    printf("pass\n");
+
+    printf("Set public key in blind sign context...");
+    fflush(stdout);
+    if (bbs_blind_sign_context_set_public_key(handle, *public_key, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Set secret key in blind sign context...");
+    fflush(stdout);
+    if (bbs_blind_sign_context_set_secret_key(handle, *secret_key, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Set commitment in blind sign context...");
+    fflush(stdout);
+    if (bbs_blind_sign_context_set_commitment(handle, *commitment, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Creating blind signature...");
+    fflush(stdout);
+    if (bbs_blind_sign_context_finish(handle, (ByteBuffer*)blind_signature, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Unblinding signature...");
+    fflush(stdout);
+    if (bbs_unblind_signature(*blind_signature, *blinding_factor, (ByteBuffer*)unblind_signature, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Create new verify signature context...");
+    fflush(stdout);
+    handle = bbs_verify_context_init(err);
+    if (handle == 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Set messages in verify signature context...");
+    fflush(stdout);
+    for (i = 0; i < message_count; i++) {
+        if (bbs_verify_context_add_message_bytes(handle, *messages[i], err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+    }
+    printf("pass\n");
+
+    printf("Set public key in verify signature context...");
+    fflush(stdout);
+    if (bbs_verify_context_set_public_key(handle, *public_key, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Set signature in verify signature context...");
+    fflush(stdout);
+    if (bbs_verify_context_set_signature(handle, *unblind_signature, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Verifying signature...");
+    fflush(stdout);
+    if (bbs_verify_context_finish(handle, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Blind sign with external signer...");
+    fflush(stdout);
+    handle = bbs_blind_sign_context_init(err);
+    if (handle == 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    for (i = 1; i < message_count; i++) {
+        if (bbs_blind_sign_context_add_message_bytes(handle, i, *messages[i], err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+    }
+    if (bbs_blind_sign_context_set_public_key(handle, *public_key, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_blind_sign_context_set_signer(handle, signer, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_blind_sign_context_set_commitment(handle, *commitment, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_blind_sign_context_finish(handle, (ByteBuffer*)external_blind_signature, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_unblind_signature(*external_blind_signature, *blinding_factor, (ByteBuffer*)external_unblind_signature, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Verify external signer blind signature...");
+    fflush(stdout);
+    handle = bbs_verify_context_init(err);
+    if (handle == 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    for (i = 0; i < message_count; i++) {
+        if (bbs_verify_context_add_message_bytes(handle, *messages[i], err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+    }
+    if (bbs_verify_context_set_public_key(handle, *public_key, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_verify_context_set_signature(handle, *external_unblind_signature, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_verify_context_finish(handle, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Create new proof context...");
+    fflush(stdout);
     handle = bbs_create_proof_context_init(err);
     if (handle == 0) {
         printf("fail\n");
@@ -454,114 +910,1105 @@ int main(int argc, char** argv) {
     }
     printf("pass\n");
 
-    printf("Adding messages to proof context 2...");
+    printf("Adding messages to proof context...");
+    fflush(stdout);
+    if (bbs_create_proof_context_add_proof_message_bytes(handle, *messages[0], Revealed, *blinding_factor, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_create_proof_context_add_proof_message_bytes(handle, *messages[1], Revealed, *blinding_factor, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    for (i = 2; i < message_count; i++) {
+        if (bbs_create_proof_context_add_proof_message_bytes(handle, *messages[i], HiddenProofSpecificBlinding, *blinding_factor, err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+    }
+    printf("pass\n");
+
+    printf("Setting signature in proof context...");
+    fflush(stdout);
+    if (bbs_create_proof_context_set_signature(handle, *unblind_signature, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Set public key in proof context...");
+    fflush(stdout);
+    if (bbs_create_proof_context_set_public_key(handle, *public_key, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Set nonce in proof context...");
+    fflush(stdout);
+    if (bbs_create_proof_context_set_nonce_bytes(handle, *nonce, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Creating proof...");
+    fflush(stdout);
+    if (bbs_create_proof_context_finish(handle, (ByteBuffer*)proof, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Create verify proof context...");
+    fflush(stdout);
+    handle = bbs_verify_proof_context_init(err);
+    if (handle == 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Adding revealed messages to verify proof context...");
+    fflush(stdout);
+    for (i = 0; i < 2; i++) {
+        if (bbs_verify_proof_context_add_message_bytes(handle, *messages[i], err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+    }
+    printf("pass\n");
+
+    printf("Set proof in verify proof context...");
+    fflush(stdout);
+    if (bbs_verify_proof_context_set_proof(handle, *proof, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Set public key in verify proof context...");
+    fflush(stdout);
+    if (bbs_verify_proof_context_set_public_key(handle, *public_key, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Set nonce in verify proof context..");
+    fflush(stdout);
+    if (bbs_verify_proof_context_set_nonce_bytes(handle, *nonce, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Verify blind sign context...");
+    fflush(stdout);
+    if (bbs_verify_proof_context_finish(handle, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Create new proof context 2...");
+    fflush(stdout);
+    handle = bbs_create_proof_context_init(err);
+    if (handle == 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Adding messages to proof context 2...");
+    fflush(stdout);
+    if (bbs_create_proof_context_add_proof_message_bytes(handle, *messages[0], HiddenProofSpecificBlinding, *blinding_factor, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_create_proof_context_add_proof_message_bytes(handle, *messages[1], Revealed, *blinding_factor, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_create_proof_context_add_proof_message_bytes(handle, *messages[2], HiddenProofSpecificBlinding, *blinding_factor, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_create_proof_context_add_proof_message_bytes(handle, *messages[3], Revealed, *blinding_factor, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_create_proof_context_add_proof_message_bytes(handle, *messages[4], HiddenProofSpecificBlinding, *blinding_factor, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+
+    printf("pass\n");
+
+    printf("Setting signature in proof context 2...");
+    fflush(stdout);
+    if (bbs_create_proof_context_set_signature(handle, *unblind_signature, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Set public key in proof context 2...");
+    fflush(stdout);
+    if (bbs_create_proof_context_set_public_key(handle, *public_key, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Set nonce in proof context 2...");
+    fflush(stdout);
+    if (bbs_create_proof_context_set_nonce_bytes(handle, *nonce, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Creating proof 2...");
+    fflush(stdout);
+    if (bbs_create_proof_context_finish(handle, (ByteBuffer*)proof, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Create verify proof context 2...");
+    fflush(stdout);
+    handle = bbs_verify_proof_context_init(err);
+    if (handle == 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Adding revealed messages to verify proof context 2...");
+    fflush(stdout);
+    if (bbs_verify_proof_context_add_message_bytes(handle, *messages[1], err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_verify_proof_context_add_message_bytes(handle, *messages[3], err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Set proof in verify proof context 2...");
+    fflush(stdout);
+    if (bbs_verify_proof_context_set_proof(handle, *proof, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Set public key in verify proof context 2...");
+    fflush(stdout);
+    if (bbs_verify_proof_context_set_public_key(handle, *public_key, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Set nonce in verify proof context 2...");
+    fflush(stdout);
+    if (bbs_verify_proof_context_set_nonce_bytes(handle, *nonce, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Verify blind sign context 2...");
+    fflush(stdout);
+    if (bbs_verify_proof_context_finish(handle, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Reject a threshold that needs more than %d signers...", party_count);
+    fflush(stdout);
+    if (bls_generate_g2_key_shares(*seed, threshold + 1, party_count, (ByteBuffer*) threshold_public_key, (ByteBuffer*) key_shares, err) == 0 ||
+        strstr(err->message, "Threshold must be between 1 and 2") == NULL) {
+        printf("fail\n");
+        goto Exit;
+    }
+    bbs_string_free(err->message);
+    if (bbs_threshold_generate_nonce_shares(threshold + 1, party_count, (ByteBuffer*) nonce_shares[0], err) == 0) {
+        printf("fail\n");
+        goto Exit;
+    }
+    bbs_string_free(err->message);
+    printf("pass\n");
+
+    printf("Generate %d of %d secret key shares...", threshold, party_count);
+    fflush(stdout);
+    if (bls_generate_g2_key_shares(*seed, threshold, party_count, (ByteBuffer*) threshold_public_key, (ByteBuffer*) key_shares, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (key_shares->length != party_count * bls_secret_key_share_size()) {
+        printf("fail\n");
+        printf("Expected %d, found %lu\n", party_count * bls_secret_key_share_size(), key_shares->length);
+        goto Exit;
+    }
+    printf("pass\n");
+
+    printf("Create BBS key from threshold BLS key...");
+    fflush(stdout);
+    if (bls_public_key_to_bbs_key(*threshold_public_key, message_count, (ByteBuffer*) threshold_public_key, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Create threshold signing session...");
+    fflush(stdout);
+    if (bbs_threshold_session_init((ByteBuffer*) session, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Generate nonce shares for each party...");
+    fflush(stdout);
+    for (i = 0; i < party_count; i++) {
+        if (bbs_threshold_generate_nonce_shares(threshold, party_count, (ByteBuffer*) nonce_shares[i], err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+        if (nonce_shares[i]->length != party_count * bbs_threshold_nonce_share_size()) {
+            printf("fail\n");
+            goto Exit;
+        }
+    }
+    printf("pass\n");
+
+    printf("Create partial signature for each party...");
+    fflush(stdout);
+    share.length = bls_secret_key_share_size();
+    for (j = 0; j < party_count; j++) {
+        handle = bbs_sign_context_init(err);
+        if (handle == 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+        if (bbs_sign_context_set_public_key(handle, *threshold_public_key, err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+        for (i = 0; i < message_count; i++) {
+            if (bbs_sign_context_add_message_bytes(handle, *messages[i], err) != 0) {
+                printf("fail\n");
+                goto Fail;
+            }
+        }
+        share.data = key_shares->data + j * share.length;
+        if (bbs_sign_context_set_secret_key_share(handle, share, err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+        share.length = bbs_threshold_nonce_share_size();
+        for (i = 0; i < party_count; i++) {
+            share.data = nonce_shares[i]->data + j * share.length;
+            if (bbs_sign_context_add_nonce_share(handle, share, err) != 0) {
+                printf("fail\n");
+                goto Fail;
+            }
+        }
+        share.length = bls_secret_key_share_size();
+        if (bbs_sign_context_set_session(handle, *session, err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+        if (bbs_sign_context_partial_finish(handle, (ByteBuffer*) partial_signatures[j], err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+    }
+    printf("pass\n");
+
+    printf("Reject combining too few partial signatures...");
+    fflush(stdout);
+    handle = bbs_sign_context_init(err);
+    if (handle == 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_sign_context_set_public_key(handle, *threshold_public_key, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    for (i = 0; i < message_count; i++) {
+        if (bbs_sign_context_add_message_bytes(handle, *messages[i], err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+    }
+    if (bbs_sign_context_set_session(handle, *session, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    for (j = 0; j < threshold; j++) {
+        if (bbs_sign_context_add_partial_signature(handle, *partial_signatures[j], err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+    }
+    if (bbs_sign_context_combine_finish(handle, (ByteBuffer*) threshold_signature, err) == 0) {
+        printf("fail\n");
+        goto Exit;
+    }
+    bbs_string_free(err->message);
+    /* The failed combine deleted the context */
+    if (bbs_sign_context_add_partial_signature(handle, *partial_signatures[0], err) == 0) {
+        printf("fail\n");
+        goto Exit;
+    }
+    bbs_string_free(err->message);
+    printf("pass\n");
+
+    printf("Combine partial signatures...");
+    fflush(stdout);
+    handle = bbs_sign_context_init(err);
+    if (handle == 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_sign_context_set_public_key(handle, *threshold_public_key, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    for (i = 0; i < message_count; i++) {
+        if (bbs_sign_context_add_message_bytes(handle, *messages[i], err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+    }
+    if (bbs_sign_context_set_session(handle, *session, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    for (j = 0; j < party_count; j++) {
+        if (bbs_sign_context_add_partial_signature(handle, *partial_signatures[j], err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+    }
+    if (bbs_sign_context_combine_finish(handle, (ByteBuffer*) threshold_signature, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Verify threshold signature...");
+    fflush(stdout);
+    handle = bbs_verify_context_init(err);
+    if (handle == 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    for (i = 0; i < message_count; i++) {
+        if (bbs_verify_context_add_message_bytes(handle, *messages[i], err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+    }
+    if (bbs_verify_context_set_public_key(handle, *threshold_public_key, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_verify_context_set_signature(handle, *threshold_signature, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_verify_context_finish(handle, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Create proofs with the same seeded rng...");
+    fflush(stdout);
+    for (j = 0; j < 2; j++) {
+        rng_state = 0x2545F4914F6CDD1DULL;
+        if (bbs_set_rng_callback(xorshift_rng, &rng_state, err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+        handle = bbs_create_proof_context_init(err);
+        if (handle == 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+        for (i = 0; i < message_count; i++) {
+            if (bbs_create_proof_context_add_proof_message_bytes(handle, *messages[i], i < 2 ? Revealed : HiddenProofSpecificBlinding, *blinding_factor, err) != 0) {
+                printf("fail\n");
+                goto Fail;
+            }
+        }
+        if (bbs_create_proof_context_set_signature(handle, *unblind_signature, err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+        if (bbs_create_proof_context_set_public_key(handle, *public_key, err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+        if (bbs_create_proof_context_set_nonce_bytes(handle, *nonce, err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+        if (bbs_create_proof_context_finish(handle, (ByteBuffer*) rng_proofs[j], err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+    }
+    if (rng_proofs[0]->length != rng_proofs[1]->length || memcmp(rng_proofs[0]->data, rng_proofs[1]->data, rng_proofs[0]->length) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Verify seeded rng proof...");
+    fflush(stdout);
+    handle = bbs_verify_proof_context_init(err);
+    if (handle == 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    for (i = 0; i < 2; i++) {
+        if (bbs_verify_proof_context_add_message_bytes(handle, *messages[i], err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+    }
+    if (bbs_verify_proof_context_set_proof(handle, *rng_proofs[0], err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_verify_proof_context_set_public_key(handle, *public_key, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_verify_proof_context_set_nonce_bytes(handle, *nonce, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_verify_proof_context_finish(handle, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Failing rng callback is an error...");
+    fflush(stdout);
+    if (bbs_set_rng_callback(failing_rng, NULL, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    handle = bbs_blind_commitment_context_init(err);
+    if (handle == 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_blind_commitment_context_add_message_bytes(handle, 0, *messages[0], err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_blind_commitment_context_set_public_key(handle, *public_key, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_blind_commitment_context_set_nonce_bytes(handle, *nonce, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_blind_commitment_context_finish(handle, (ByteBuffer*)commitment, (ByteBuffer*)blind_sign_context, (ByteBuffer*)blinding_factor, err) == 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    bbs_string_free(err->message);
+    if (bbs_set_rng_callback(NULL, NULL, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Rng callback can call into the library...");
+    fflush(stdout);
+    rng_state = 0x2545F4914F6CDD1DULL;
+    if (bbs_set_rng_callback(reentrant_rng, &rng_state, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_threshold_session_init(&unused_signature, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    bbs_byte_buffer_free(unused_signature);
+    if (bbs_set_rng_callback(NULL, NULL, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Last error with a null err...");
+    fflush(stdout);
+    handle = bbs_sign_context_init(NULL);
+    if (handle == 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_sign_context_finish(handle, &unused_signature, NULL) == 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_get_last_error(err) == 0 || err->message == NULL) {
+        printf("fail\n");
+        goto Fail;
+    }
+    bbs_string_free(err->message);
+    free_bbs_sign(handle, NULL);
+    if (bbs_get_last_error(err) != 0 || err->message != NULL) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Generate key with json call...");
     fflush(stdout);
-    if (bbs_create_proof_context_add_proof_message_bytes(handle, *messages[0], HiddenProofSpecificBlinding, *blinding_factor, err) != 0) {
+    json_response = bbs_call_json("{\"op\":\"generate_key\",\"seed\":\"c2VlZA\"}");
+    if (strncmp(json_response, "{\"result\":", 10) != 0 || strstr(json_response, "\"secretKey\"") == NULL) {
         printf("fail\n");
+        bbs_string_free(json_response);
         goto Fail;
     }
-    if (bbs_create_proof_context_add_proof_message_bytes(handle, *messages[1], Revealed, *blinding_factor, err) != 0) {
+    bbs_string_free(json_response);
+    printf("pass\n");
+
+    printf("Json call error object...");
+    fflush(stdout);
+    json_response = bbs_call_json("{\"op\":\"sign\",\"secretKey\":\"!\"}");
+    if (strncmp(json_response, "{\"error\":{\"code\":1,", 19) != 0) {
         printf("fail\n");
+        bbs_string_free(json_response);
         goto Fail;
     }
-    if (bbs_create_proof_context_add_proof_message_bytes(handle, *messages[2], HiddenProofSpecificBlinding, *blinding_factor, err) != 0) {
+    bbs_string_free(json_response);
+    printf("pass\n");
+
+    printf("Verify with json call...");
+    fflush(stdout);
+    verify_request(json_request, public_key, messages, message_count, signature);
+    json_response = bbs_call_json(json_request);
+    if (strcmp(json_response, "{\"result\":{\"verified\":true}}") != 0) {
+        printf("fail\n");
+        printf("    Found: %s\n", json_response);
+        bbs_string_free(json_response);
+        goto Exit;
+    }
+    bbs_string_free(json_response);
+    /* Deleting the used context does not leave an error behind */
+    if (bbs_get_last_error(err) != 0) {
+        printf("fail\n");
+        bbs_string_free(err->message);
+        goto Exit;
+    }
+    verify_request(json_request, public_key, messages + 1, message_count - 1, signature);
+    strcpy(strstr(json_request, "]}"), ",\"AA\"]}");
+    json_response = bbs_call_json(json_request);
+    if (strcmp(json_response, "{\"result\":{\"verified\":false}}") != 0) {
+        printf("fail\n");
+        printf("    Found: %s\n", json_response);
+        bbs_string_free(json_response);
+        goto Exit;
+    }
+    bbs_string_free(json_response);
+    printf("pass\n");
+
+    printf("Json verify with the wrong message count is an error...");
+    fflush(stdout);
+    verify_request(json_request, public_key, messages, message_count - 1, signature);
+    json_response = bbs_call_json(json_request);
+    if (strncmp(json_response, "{\"error\":{\"code\":1,", 19) != 0) {
+        printf("fail\n");
+        printf("    Found: %s\n", json_response);
+        bbs_string_free(json_response);
+        goto Exit;
+    }
+    bbs_string_free(json_response);
+    printf("pass\n");
+
+    printf("ABI version matches header...");
+    fflush(stdout);
+    if (bbs_abi_version() != BBS_ABI_VERSION) {
+        printf("fail\n");
+        printf("    Expected %d, Found: %d\n", BBS_ABI_VERSION, bbs_abi_version());
+        goto Exit;
+    }
+    printf("pass\n");
+
+    printf("Library version...");
+    fflush(stdout);
+    if (bbs_library_version() == NULL || strlen(bbs_library_version()) == 0) {
+        printf("fail\n");
+        goto Exit;
+    }
+    printf("pass\n");
+
+    printf("Supported features...");
+    fflush(stdout);
+    if (strstr(bbs_supported_features(), "\"ciphersuites\":[\"BBS+_BLS12381G1_BLAKE2B\"") == NULL ||
+        strstr(bbs_supported_features(), bbs_library_version()) == NULL) {
+        printf("fail\n");
+        printf("    Found: %s\n", bbs_supported_features());
+        goto Exit;
+    }
+    printf("pass\n");
+
+    printf("Verify proof with revealed messages by index...");
+    fflush(stdout);
+    handle = bbs_verify_proof_context_init(err);
+    if (bbs_verify_proof_context_add_revealed_message(handle, 3, *messages[3], err) != 0 ||
+        bbs_verify_proof_context_add_revealed_message(handle, 1, *messages[1], err) != 0 ||
+        bbs_verify_proof_context_set_proof(handle, *proof, err) != 0 ||
+        bbs_verify_proof_context_set_public_key(handle, *public_key, err) != 0 ||
+        bbs_verify_proof_context_set_nonce_bytes(handle, *nonce, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
-    if (bbs_create_proof_context_add_proof_message_bytes(handle, *messages[3], Revealed, *blinding_factor, err) != 0) {
+    if (bbs_verify_proof_context_finish(handle, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
-    if (bbs_create_proof_context_add_proof_message_bytes(handle, *messages[4], HiddenProofSpecificBlinding, *blinding_factor, err) != 0) {
+    printf("pass\n");
+
+    printf("Report missing and extra revealed indices...");
+    fflush(stdout);
+    handle = bbs_verify_proof_context_init(err);
+    if (bbs_verify_proof_context_add_revealed_message(handle, 1, *messages[1], err) != 0 ||
+        bbs_verify_proof_context_add_revealed_message(handle, 4, *messages[4], err) != 0 ||
+        bbs_verify_proof_context_set_proof(handle, *proof, err) != 0 ||
+        bbs_verify_proof_context_set_public_key(handle, *public_key, err) != 0 ||
+        bbs_verify_proof_context_set_nonce_bytes(handle, *nonce, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
+    if (bbs_verify_proof_context_finish(handle, err) == 0 ||
+        strstr(err->message, "missing messages for revealed indices [3]") == NULL ||
+        strstr(err->message, "unexpected messages for unrevealed indices [4]") == NULL) {
+        printf("    Found: %s\n", err->message);
+        printf("fail\n");
+        goto Fail;
+    }
+    bbs_string_free(err->message);
+    printf("pass\n");
 
+    printf("Seal proof, public key and signature in envelopes...");
+    fflush(stdout);
+    if (bbs_envelope_seal(ProofArtifact, *proof, &proof_envelope, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_envelope_seal(PublicKeyArtifact, *public_key, &key_envelope, err) != 0) {
+        bbs_byte_buffer_free(proof_envelope);
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_envelope_seal(SignatureArtifact, *signature, &signature_envelope, err) != 0) {
+        bbs_byte_buffer_free(proof_envelope);
+        bbs_byte_buffer_free(key_envelope);
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_envelope_seal(ProofArtifact, *signature, &opened, err) == 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    bbs_string_free(err->message);
     printf("pass\n");
 
-    printf("Setting signature in proof context 2...");
+    printf("Verify proof from envelopes...");
     fflush(stdout);
-    if (bbs_create_proof_context_set_signature(handle, *unblind_signature, err) != 0) {
+    if (bbs_get_total_messages_count_for_proof(*(ByteArray*)&proof_envelope) != 5 ||
+        bbs_get_total_messages_count_for_proof(*(ByteArray*)&signature_envelope) != -1) {
+        printf("fail\n");
+        goto Fail;
+    }
+    handle = bbs_verify_proof_context_init(err);
+    if (bbs_verify_proof_context_add_revealed_message(handle, 1, *messages[1], err) != 0 ||
+        bbs_verify_proof_context_add_revealed_message(handle, 3, *messages[3], err) != 0 ||
+        bbs_verify_proof_context_set_proof(handle, *(ByteArray*)&proof_envelope, err) != 0 ||
+        bbs_verify_proof_context_set_public_key(handle, *(ByteArray*)&key_envelope, err) != 0 ||
+        bbs_verify_proof_context_set_nonce_bytes(handle, *nonce, err) != 0 ||
+        bbs_verify_proof_context_finish(handle, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
     printf("pass\n");
 
-    printf("Set public key in proof context 2...");
+    printf("Reject envelope of the wrong type...");
     fflush(stdout);
-    if (bbs_create_proof_context_set_public_key(handle, *public_key, err) != 0) {
+    handle = bbs_verify_proof_context_init(err);
+    if (bbs_verify_proof_context_set_proof(handle, *(ByteArray*)&signature_envelope, err) == 0 ||
+        strstr(err->message, "Expected a proof envelope, found a signature") == NULL) {
         printf("fail\n");
         goto Fail;
     }
+    bbs_string_free(err->message);
+    free_verify_proof(handle, err);
     printf("pass\n");
 
-    printf("Set nonce in proof context 2...");
+    printf("Reject envelope for another ciphersuite...");
     fflush(stdout);
-    if (bbs_create_proof_context_set_nonce_bytes(handle, *nonce, err) != 0) {
+    signature_envelope.data[7] ^= 1;
+    handle = bbs_verify_context_init(err);
+    if (bbs_verify_context_set_signature(handle, *(ByteArray*)&signature_envelope, err) == 0 ||
+        strstr(err->message, "ciphersuite") == NULL) {
         printf("fail\n");
         goto Fail;
     }
+    bbs_string_free(err->message);
+    signature_envelope.data[7] ^= 1;
     printf("pass\n");
 
-    printf("Creating proof 2...");
+    printf("Open envelope...");
     fflush(stdout);
-    if (bbs_create_proof_context_finish(handle, (ByteBuffer*)proof, err) != 0) {
+    if (bbs_envelope_open(*(ByteArray*)&signature_envelope, &artifact_type, &opened, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (artifact_type != SignatureArtifact || opened.len != signature->length ||
+        memcmp(opened.data, signature->data, signature->length) != 0) {
+        bbs_byte_buffer_free(opened);
+        printf("fail\n");
+        goto Exit;
+    }
+    bbs_byte_buffer_free(opened);
+    if (bbs_envelope_open(*signature, &artifact_type, &opened, err) == 0) {
         printf("fail\n");
         goto Fail;
     }
+    bbs_string_free(err->message);
+    bbs_byte_buffer_free(proof_envelope);
+    bbs_byte_buffer_free(key_envelope);
+    bbs_byte_buffer_free(signature_envelope);
     printf("pass\n");
 
-    printf("Create verify proof context 2...");
+#if defined(DEFINE_COSE)
+    printf("COSE encode signature and proof...");
+    fflush(stdout);
+    if (bbs_cose_encode(SignatureArtifact, *signature, (ByteArray){ sizeof(kid) - 1, kid }, &cose_signature, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_cose_encode(ProofArtifact, *proof, (ByteArray){ sizeof(kid) - 1, kid }, &cose_proof, err) != 0) {
+        bbs_byte_buffer_free(cose_signature);
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_cose_encode(CommitmentArtifact, *signature, (ByteArray){ sizeof(kid) - 1, kid }, &opened, err) == 0) {
+        printf("fail\n");
+        goto Exit;
+    }
+    bbs_string_free(err->message);
+    printf("pass\n");
+
+    printf("Verify COSE signature...");
+    fflush(stdout);
+    handle = bbs_verify_context_init(err);
+    for (i = 0; i < message_count; i++) {
+        if (bbs_verify_context_add_message_bytes(handle, *messages[i], err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+    }
+    if (bbs_verify_context_set_public_key(handle, *public_key, err) != 0 ||
+        bbs_verify_context_set_cose_signature(handle, *(ByteArray*)&cose_signature, err) != 0 ||
+        bbs_verify_context_finish(handle, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    printf("pass\n");
+
+    printf("Verify COSE proof...");
     fflush(stdout);
     handle = bbs_verify_proof_context_init(err);
-    if (handle == 0) {
+    if (bbs_verify_proof_context_add_revealed_message(handle, 1, *messages[1], err) != 0 ||
+        bbs_verify_proof_context_add_revealed_message(handle, 3, *messages[3], err) != 0 ||
+        bbs_verify_proof_context_set_cose_proof(handle, *(ByteArray*)&cose_proof, err) != 0 ||
+        bbs_verify_proof_context_set_public_key(handle, *public_key, err) != 0 ||
+        bbs_verify_proof_context_set_nonce_bytes(handle, *nonce, err) != 0 ||
+        bbs_verify_proof_context_finish(handle, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
     printf("pass\n");
 
-    printf("Adding revealed messages to verify proof context 2...");
+    printf("Decode COSE proof...");
     fflush(stdout);
-    if (bbs_verify_proof_context_add_message_bytes(handle, *messages[1], err) != 0) {
+    if (bbs_cose_decode(*(ByteArray*)&cose_proof, &artifact_type, &cose_kid, &opened, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
-    if (bbs_verify_proof_context_add_message_bytes(handle, *messages[3], err) != 0) {
+    if (artifact_type != ProofArtifact || cose_kid.len != sizeof(kid) - 1 ||
+        memcmp(cose_kid.data, kid, cose_kid.len) != 0 || opened.len != proof->length ||
+        memcmp(opened.data, proof->data, proof->length) != 0) {
+        bbs_byte_buffer_free(cose_kid);
+        bbs_byte_buffer_free(opened);
+        printf("fail\n");
+        goto Exit;
+    }
+    bbs_byte_buffer_free(cose_kid);
+    bbs_byte_buffer_free(opened);
+    printf("pass\n");
+
+    printf("Reject COSE proof with mismatched headers...");
+    fflush(stdout);
+    handle = bbs_verify_proof_context_init(err);
+    if (bbs_verify_proof_context_set_cose_proof(handle, *(ByteArray*)&cose_signature, err) == 0 ||
+        strstr(err->message, "Expected a COSE proof, found a signature") == NULL) {
+        printf("fail\n");
+        goto Fail;
+    }
+    bbs_string_free(err->message);
+    for (i = 0; i + sizeof(revealed_header) <= cose_proof.len; i++) {
+        if (memcmp(cose_proof.data + i, revealed_header, sizeof(revealed_header)) == 0) {
+            cose_proof.data[i + sizeof(revealed_header) - 1] = 0x02;
+            break;
+        }
+    }
+    if (bbs_verify_proof_context_set_cose_proof(handle, *(ByteArray*)&cose_proof, err) == 0 ||
+        strstr(err->message, "COSE header reveals indices [1, 2] but the proof reveals [1, 3]") == NULL) {
         printf("fail\n");
         goto Fail;
     }
+    bbs_string_free(err->message);
+    free_verify_proof(handle, err);
+    bbs_byte_buffer_free(cose_signature);
+    bbs_byte_buffer_free(cose_proof);
     printf("pass\n");
+#endif
 
-    printf("Set proof in verify proof context 2...");
+#if defined(DEFINE_JWP)
+    printf("Issue JWP...");
     fflush(stdout);
-    if (bbs_verify_proof_context_set_proof(handle, *proof, err) != 0) {
+    handle = bbs_sign_context_init(err);
+    if (bbs_sign_context_set_secret_key(handle, *secret_key, err) != 0 ||
+        bbs_sign_context_set_public_key(handle, *public_key, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    for (i = 0; i + 1 < message_count; i++) {
+        if (bbs_sign_context_add_jwp_payload(handle, *messages[i], err) != 0) {
+            printf("fail\n");
+            goto Fail;
+        }
+    }
+    if (bbs_sign_context_finish_jwp(handle, (ByteArray){ sizeof(es256_header) - 1, es256_header }, &issued_jwp, err) == 0 ||
+        strstr(err->message, "alg must be BBS+_BLS12381G1_BLAKE2B") == NULL) {
+        printf("fail\n");
+        goto Fail;
+    }
+    bbs_string_free(err->message);
+    if (bbs_sign_context_finish_jwp(handle, (ByteArray){ sizeof(jwp_header) - 1, jwp_header }, &issued_jwp, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
+    if (memchr(issued_jwp.data, '.', issued_jwp.len) == NULL || memchr(issued_jwp.data, '~', issued_jwp.len) == NULL) {
+        bbs_byte_buffer_free(issued_jwp);
+        printf("fail\n");
+        goto Exit;
+    }
     printf("pass\n");
 
-    printf("Set public key in verify proof context 2...");
+    printf("Present JWP...");
     fflush(stdout);
-    if (bbs_verify_proof_context_set_public_key(handle, *public_key, err) != 0) {
+    handle = bbs_create_proof_context_init(err);
+    if (bbs_create_proof_context_set_jwp(handle, *(ByteArray*)&issued_jwp, err) != 0 ||
+        bbs_create_proof_context_set_public_key(handle, *public_key, err) != 0 ||
+        bbs_create_proof_context_disclose_jwp_payload(handle, 9, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_create_proof_context_finish_jwp(handle, (ByteArray){ sizeof(presentation_header) - 1, presentation_header }, &presented_jwp, err) == 0 ||
+        strstr(err->message, "Cannot disclose payload 9, the JWP has 4 payloads") == NULL) {
+        printf("fail\n");
+        goto Fail;
+    }
+    bbs_string_free(err->message);
+    free_create_proof(handle, err);
+    handle = bbs_create_proof_context_init(err);
+    if (bbs_create_proof_context_set_jwp(handle, *(ByteArray*)&issued_jwp, err) != 0 ||
+        bbs_create_proof_context_set_public_key(handle, *public_key, err) != 0 ||
+        bbs_create_proof_context_disclose_jwp_payload(handle, 1, err) != 0 ||
+        bbs_create_proof_context_finish_jwp(handle, (ByteArray){ sizeof(presentation_header) - 1, presentation_header }, &presented_jwp, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
+    bbs_byte_buffer_free(issued_jwp);
     printf("pass\n");
 
-    printf("Set nonce in verify proof context 2...");
+    printf("Verify presented JWP...");
     fflush(stdout);
-    if (bbs_verify_proof_context_set_nonce_bytes(handle, *nonce, err) != 0) {
+    handle = bbs_verify_proof_context_init(err);
+    if (bbs_verify_proof_context_set_jwp(handle, *(ByteArray*)&presented_jwp, "abc", err) != 0 ||
+        bbs_verify_proof_context_set_public_key(handle, *public_key, err) != 0 ||
+        bbs_verify_proof_context_finish(handle, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
     printf("pass\n");
 
-    printf("Verify blind sign context 2...");
+    printf("Reject JWP presented for another nonce...");
     fflush(stdout);
-    if (bbs_verify_proof_context_finish(handle, err) != 0) {
+    handle = bbs_verify_proof_context_init(err);
+    if (bbs_verify_proof_context_set_jwp(handle, *(ByteArray*)&presented_jwp, "xyz", err) == 0 ||
+        strstr(err->message, "nonce does not match the expected nonce") == NULL) {
+        printf("fail\n");
+        goto Fail;
+    }
+    bbs_string_free(err->message);
+    if (bbs_verify_proof_context_set_nonce_string(handle, "abc", err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_verify_proof_context_set_jwp(handle, *(ByteArray*)&presented_jwp, "abc", err) == 0 ||
+        strstr(err->message, "The nonce comes from the JWP and cannot be set") == NULL) {
+        printf("fail\n");
+        goto Fail;
+    }
+    bbs_string_free(err->message);
+    free_verify_proof(handle, err);
+    printf("pass\n");
+
+    printf("Reject messages added to a JWP...");
+    fflush(stdout);
+    handle = bbs_verify_proof_context_init(err);
+    if (bbs_verify_proof_context_set_jwp(handle, *(ByteArray*)&presented_jwp, "abc", err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_verify_proof_context_add_message_bytes(handle, *messages[0], err) == 0 ||
+        strstr(err->message, "Messages come from the JWP and cannot be added") == NULL) {
+        printf("fail\n");
+        goto Fail;
+    }
+    bbs_string_free(err->message);
+    if (bbs_verify_proof_context_add_revealed_message(handle, 2, *messages[0], err) == 0 ||
+        strstr(err->message, "Messages come from the JWP and cannot be added") == NULL) {
+        printf("fail\n");
+        goto Fail;
+    }
+    bbs_string_free(err->message);
+    free_verify_proof(handle, err);
+    printf("pass\n");
+
+    printf("Reject JWP with another presentation header...");
+    fflush(stdout);
+    memcpy(presented_jwp.data, "eyJub25jZSI6Inh5eiJ9", 20);
+    handle = bbs_verify_proof_context_init(err);
+    if (bbs_verify_proof_context_set_jwp(handle, *(ByteArray*)&presented_jwp, "xyz", err) != 0 ||
+        bbs_verify_proof_context_set_public_key(handle, *public_key, err) != 0) {
+        printf("fail\n");
+        goto Fail;
+    }
+    if (bbs_verify_proof_context_finish(handle, err) == 0) {
+        printf("fail\n");
+        goto Exit;
+    }
+    bbs_string_free(err->message);
+    free_verify_proof(handle, err);
+    bbs_byte_buffer_free(presented_jwp);
+    printf("pass\n");
+#endif
+
+    printf("Inspect proof...");
+    fflush(stdout);
+    if (bbs_proof_inspect(*proof, &total_count, &revealed_indices, &hidden_count, &format_version, err) != 0) {
         printf("fail\n");
         goto Fail;
     }
+    if (total_count != message_count || hidden_count != 3 || format_version != BBS_PROOF_FORMAT_VERSION ||
+        revealed_indices.len != 8 || revealed_indices.data[3] != 1 || revealed_indices.data[7] != 3) {
+        bbs_byte_buffer_free(revealed_indices);
+        printf("fail\n");
+        printf("    Found %u messages, %u hidden, version %u\n", total_count, hidden_count, format_version);
+        goto Exit;
+    }
+    bbs_byte_buffer_free(revealed_indices);
+    if (bbs_proof_inspect((ByteArray){ 1, proof->data }, &total_count, &revealed_indices, &hidden_count, &format_version, err) == 0) {
+        printf("fail\n");
+        goto Exit;
+    }
+    bbs_string_free(err->message);
+    printf("pass\n");
+
+    printf("Reject truncated proofs...");
+    fflush(stdout);
+    if (bbs_get_total_messages_count_for_proof((ByteArray){ 1, proof->data }) != -1) {
+        printf("fail\n");
+        goto Exit;
+    }
+    handle = bbs_verify_proof_context_init(err);
+    for (i = 1; i < proof->length; i += 37) {
+        if (bbs_verify_proof_context_set_proof(handle, (ByteArray){ i, proof->data }, err) == 0) {
+            printf("fail\n");
+            printf("    Accepted a proof of %d bytes\n", i);
+            goto Exit;
+        }
+        bbs_string_free(err->message);
+    }
+    free_verify_proof(handle, err);
+    printf("pass\n");
+
+    printf("Reject proof with forged message count...");
+    fflush(stdout);
+    forged_data = (uint8_t *)malloc(proof->length);
+    memcpy(forged_data, proof->data, proof->length);
+    forged_data[0] = 0xff;
+    forged_data[1] = 0xff;
+    forged.length = proof->length;
+    forged.data = forged_data;
+    handle = bbs_verify_proof_context_init(err);
+    if (bbs_verify_proof_context_set_proof(handle, forged, err) == 0) {
+        free(forged_data);
+        printf("fail\n");
+        goto Exit;
+    }
+    bbs_string_free(err->message);
+    free_verify_proof(handle, err);
+    free(forged_data);
+    printf("pass\n");
+
+    printf("Reject public key with forged generator count...");
+    fflush(stdout);
+    forged_data = (uint8_t *)malloc(public_key->length);
+    memcpy(forged_data, public_key->data, public_key->length);
+    memset(forged_data + 144, 0xff, 4);
+    forged.length = public_key->length;
+    forged.data = forged_data;
+    handle = bbs_sign_context_init(err);
+    if (bbs_sign_context_set_public_key(handle, forged, err) == 0 ||
+        strstr(err->message, "declares") == NULL) {
+        free(forged_data);
+        printf("fail\n");
+        goto Exit;
+    }
+    bbs_string_free(err->message);
+    if (bbs_sign_context_set_public_key(handle, (ByteArray){ 3, public_key->data }, err) == 0) {
+        free(forged_data);
+        printf("fail\n");
+        goto Exit;
+    }
+    bbs_string_free(err->message);
+    free_bbs_sign(handle, err);
+    free(forged_data);
     printf("pass\n");
 
     printf("Tests Passed\n");
@@ -575,7 +2022,7 @@ Err:
 Exit:
     bbs_byte_buffer_free(*(ByteBuffer*)seed);
     bbs_byte_buffer_free(*(ByteBuffer*)public_key);
-    bbs_byte_buffer_free(*(ByteBuffer*)secret_key);
+    bbs_byte_buffer_free_secure(*(ByteBuffer*)secret_key);
     bbs_byte_buffer_free(*(ByteBuffer*)signature);
     for (i = 0; i < message_count; i++) {
         bbs_byte_buffer_free(*(ByteBuffer*)messages[i]);
@@ -583,10 +2030,38 @@ Exit:
     }
     bbs_byte_buffer_free(*(ByteBuffer*)nonce);
     bbs_byte_buffer_free(*(ByteBuffer*)commitment);
-    bbs_byte_buffer_free(*(ByteBuffer*)blind_sign_context);
-    bbs_byte_buffer_free(*(ByteBuffer*)blinding_factor);
+    bbs_byte_buffer_free_secure(*(ByteBuffer*)blind_sign_context);
+    bbs_byte_buffer_free_secure(*(ByteBuffer*)blinding_factor);
     bbs_byte_buffer_free(*(ByteBuffer*)blind_signature);
     bbs_byte_buffer_free(*(ByteBuffer*)proof);
+    bbs_byte_buffer_free(*(ByteBuffer*)pop);
+    bbs_byte_buffer_free(*(ByteBuffer*)g1_public_key);
+    bbs_byte_buffer_free_secure(*(ByteBuffer*)g1_secret_key);
+    bbs_byte_buffer_free(*(ByteBuffer*)g1_pop);
+    bbs_byte_buffer_free(*(ByteBuffer*)bls_signature);
+    bbs_byte_buffer_free(*(ByteBuffer*)bls_signature_2);
+    bbs_byte_buffer_free(*(ByteBuffer*)bls_public_key_2);
+    bbs_byte_buffer_free(*(ByteBuffer*)blinded_public_key);
+    bbs_byte_buffer_free_secure(*(ByteBuffer*)blinded_secret_key);
+    bbs_byte_buffer_free_secure(*(ByteBuffer*)key_blinding_factor);
+    bbs_byte_buffer_free(*(ByteBuffer*)blinded_key_proof);
+    bbs_byte_buffer_free(*(ByteBuffer*)unblinded_public_key);
+    bbs_byte_buffer_free(*(ByteBuffer*)expected_public_key);
+    bbs_byte_buffer_free(*(ByteBuffer*)external_signature);
+    bbs_byte_buffer_free(*(ByteBuffer*)external_blind_signature);
+    bbs_byte_buffer_free(*(ByteBuffer*)external_unblind_signature);
+    bbs_byte_buffer_free(*(ByteBuffer*)threshold_public_key);
+    bbs_byte_buffer_free_secure(*(ByteBuffer*)key_shares);
+    bbs_byte_buffer_free(*(ByteBuffer*)session);
+    for (i = 0; i < party_count; i++) {
+        bbs_byte_buffer_free(*(ByteBuffer*)nonce_shares[i]);
+        bbs_byte_buffer_free(*(ByteBuffer*)partial_signatures[i]);
+        free(nonce_shares[i]);
+        free(partial_signatures[i]);
+    }
+    bbs_byte_buffer_free(*(ByteBuffer*)threshold_signature);
+    bbs_byte_buffer_free(*(ByteBuffer*)rng_proofs[0]);
+    bbs_byte_buffer_free(*(ByteBuffer*)rng_proofs[1]);
     free(nonce);
     free(proof);
     free(signature);
@@ -598,4 +2073,32 @@ Exit:
     free(commitment);
     free(blind_sign_context);
     free(blinding_factor);
+    free(pop);
+    free(g1_public_key);
+    free(g1_secret_key);
+    free(g1_pop);
+    free(bls_signature);
+    free(bls_signature_2);
+    free(bls_public_key_2);
+    free(blinded_public_key);
+    free(blinded_secret_key);
+    free(key_blinding_factor);
+    free(blinded_key_proof);
+    free(unblinded_public_key);
+    free(expected_public_key);
+    free(external_signature);
+    free(external_blind_signature);
+    free(external_unblind_signature);
+    free(threshold_public_key);
+    free(key_shares);
+    free(session);
+    free(nonce_shares);
+    free(partial_signatures);
+    free(threshold_signature);
+    free(rng_proofs[0]);
+    free(rng_proofs[1]);
+    if (identity != NULL) {
+        free((uint8_t *)identity->data);
+        free(identity);
+    }
 }
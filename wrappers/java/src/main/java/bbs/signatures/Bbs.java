@@ -7,14 +7,25 @@ package bbs.signatures;
  * used by the src/android.rs
  */
 
+import java.util.Arrays;
 import java.util.Map;
 
 public class Bbs {
 
+    /** The native ABI version this wrapper was written against, see `BBS_ABI_VERSION` in bbs.h */
+    public static final int ABI_VERSION = 2;
+
     static {
         System.loadLibrary("bbs");
+        checkAbiVersion();
     }
 
+    private static native String bbs_library_version();
+
+    private static native int bbs_abi_version();
+
+    private static native String bbs_supported_features();
+
     private static native int bls_public_key_g1_size();
 
     private static native int bls_public_key_g2_size();
@@ -45,6 +56,8 @@ public class Bbs {
 
     private static native int bbs_sign_add_message_bytes(long handle, byte[] message);
 
+    private static native int bbs_sign_add_message_string(long handle, String message);
+
     private static native int bbs_sign_add_message_prehashed(long handle, byte[] hash); // ?
 
     private static native int bbs_sign_finish(long handle, byte[] signature);
@@ -53,6 +66,8 @@ public class Bbs {
 
     private static native int bbs_verify_add_message_bytes(long handle, byte[] message);
 
+    private static native int bbs_verify_add_message_string(long handle, String message);
+
     private static native int bbs_verify_add_message_prehashed(long handle, byte[] hash); // ?
 
     private static native int bbs_verify_set_public_key(long handle, byte[] public_key);
@@ -67,12 +82,18 @@ public class Bbs {
 
     private static native int bbs_blind_commitment_add_message_bytes(long handle, int index, byte[] message);
 
+    private static native int bbs_blind_commitment_add_message_string(long handle, int index, String message);
+
     private static native int bbs_blind_commitment_add_prehashed(long handle, int index, byte[] hash); // ?
 
     private static native int bbs_blind_commitment_set_public_key(long handle, byte[] public_key);
 
     private static native int bbs_blind_commitment_set_nonce_bytes(long handle, byte[] nonce);
 
+    private static native int bbs_blind_commitment_set_nonce_string(long handle, String nonce);
+
+    private static native int bbs_blind_commitment_set_nonce_prehashed(long handle, byte[] nonce);
+
     private static native byte[] bbs_blind_commitment_finish(long handle, byte[] commitment, byte[] blinding_factor);
 
     private static native long bbs_blind_sign_init();
@@ -85,6 +106,8 @@ public class Bbs {
 
     private static native int bbs_blind_sign_add_message_bytes(long handle, int index, byte[] message);
 
+    private static native int bbs_blind_sign_add_message_string(long handle, int index, String message);
+
     private static native int bbs_blind_sign_add_prehashed(long handle, int index, byte[] hash);
 
     private static native int bbs_blind_sign_finish(long handle, byte[] blind_signature);
@@ -99,8 +122,16 @@ public class Bbs {
 
     private static native int bbs_create_proof_context_set_nonce_bytes(long handle, byte[] message);
 
+    private static native int bbs_create_proof_context_set_nonce_string(long handle, String nonce);
+
+    private static native int bbs_create_proof_context_set_nonce_prehashed(long handle, byte[] nonce);
+
     private static native int bbs_create_proof_context_add_proof_message_bytes(long handle, byte[] message, int xtype, byte[] blinding_factor);
 
+    private static native int bbs_create_proof_context_add_proof_message_string(long handle, String message, int xtype, byte[] blinding_factor);
+
+    private static native int bbs_create_proof_context_add_proof_message_prehashed(long handle, byte[] hash, int xtype, byte[] blinding_factor);
+
     private static native int bbs_create_proof_context_finish(long handle, byte[] proof);
 
     private static native int bbs_create_proof_size(long handle);
@@ -109,6 +140,10 @@ public class Bbs {
 
     private static native int bbs_verify_proof_context_add_message_bytes(long handle, byte[] message);
 
+    private static native int bbs_verify_proof_context_add_revealed_message(long handle, int index, byte[] message);
+
+    private static native int bbs_verify_proof_context_add_message_string(long handle, String message);
+
     private static native int bbs_verify_proof_context_add_message_prehashed(long handle, byte[] hash);
 
     private static native int bbs_verify_proof_context_set_proof(long handle, byte[] proof);
@@ -117,11 +152,57 @@ public class Bbs {
 
     private static native int bbs_verify_proof_context_set_nonce_bytes(long handle, byte[] nonce);
 
+    private static native int bbs_verify_proof_context_set_nonce_string(long handle, String nonce);
+
+    private static native int bbs_verify_proof_context_set_nonce_prehashed(long handle, byte[] nonce);
+
     private static native int bbs_verify_proof_context_finish(long handle);
 
+    private static native long bbs_verify_blind_commitment_context_init();
+
+    private static native int bbs_verify_blind_commitment_context_add_blinded(long handle, int index);
+
+    private static native int bbs_verify_blind_commitment_context_set_public_key(long handle, byte[] public_key);
+
+    private static native int bbs_verify_blind_commitment_context_set_nonce_bytes(long handle, byte[] nonce);
+
+    private static native int bbs_verify_blind_commitment_context_set_nonce_string(long handle, String nonce);
+
+    private static native int bbs_verify_blind_commitment_context_set_nonce_prehashed(long handle, byte[] nonce);
+
+    private static native int bbs_verify_blind_commitment_context_set_proof(long handle, byte[] proof);
+
+    private static native int bbs_verify_blind_commitment_context_finish(long handle);
+
     private static native int bbs_get_total_messages_count_for_proof(byte[] proof);
 
-    private static native String get_last_error();
+    private static native int[] bbs_proof_inspect(byte[] proof);
+
+    /* Refuse a native library built for a different ABI instead of failing on a missing symbol later */
+    private static void checkAbiVersion() {
+        int version;
+        try {
+            version = bbs_abi_version();
+        } catch (UnsatisfiedLinkError e) {
+            throw new UnsatisfiedLinkError("The loaded bbs native library predates bbs_abi_version, expected ABI version " + ABI_VERSION);
+        }
+        if (version != ABI_VERSION) {
+            throw new UnsatisfiedLinkError("The loaded bbs native library has ABI version " + version + ", expected " + ABI_VERSION);
+        }
+    }
+
+    public static String getLibraryVersion() {
+        return bbs_library_version();
+    }
+
+    public static int getAbiVersion() {
+        return bbs_abi_version();
+    }
+
+    /* A JSON object with the libraryVersion, abiVersion, ciphersuites, proofExtensions, encodings and compiled in features */
+    public static String getSupportedFeatures() {
+        return bbs_supported_features();
+    }
 
     public static int getBls12381G1PublicKeySize() {
         return bls_public_key_g1_size();
@@ -147,320 +228,225 @@ public class Bbs {
         return bbs_blind_signature_size();
     }
 
-    public static int getTotalMessagesCountForProof(byte[] proof) throws Exception {
-        int messages_count = bbs_get_total_messages_count_for_proof(proof);
-        if (messages_count == -1) {
-            throw new Exception("Unable to get messages count");
-        }
-        return messages_count;
+    public static int getTotalMessagesCountForProof(byte[] proof) throws BbsException {
+        return bbs_get_total_messages_count_for_proof(proof);
+    }
+
+    public static ProofInfo inspectProof(byte[] proof) throws BbsException {
+        int[] info = bbs_proof_inspect(proof);
+        return new ProofInfo(info[0], info[1], info[2], Arrays.copyOfRange(info, 3, info.length));
     }
 
-    public static KeyPair generateBls12381G1Key(byte[] seed) throws Exception {
+    public static KeyPair generateBls12381G1Key(byte[] seed) throws BbsException {
         byte[] public_key = new byte[bls_public_key_g1_size()];
         byte[] secret_key = new byte[bls_secret_key_size()];
-        if (0 != bls_generate_g1_key(seed, public_key, secret_key)) {
-            throw new Exception("Unable to generate keys");
-        }
+        bls_generate_g1_key(seed, public_key, secret_key);
         return new KeyPair(public_key, secret_key);
     }
 
-    public static KeyPair generateBls12381G2Key(byte[] seed) throws Exception {
+    public static KeyPair generateBls12381G2Key(byte[] seed) throws BbsException {
         byte[] public_key = new byte[bls_public_key_g2_size()];
         byte[] secret_key = new byte[bls_secret_key_size()];
-        if (0 != bls_generate_g2_key(seed, public_key, secret_key)) {
-            throw new Exception("Unable to generate keys");
-        }
+        bls_generate_g2_key(seed, public_key, secret_key);
         return new KeyPair(public_key, secret_key);
     }
 
-    public static BlindedKeyPair generateBlindedBls12381G1Key(byte[] seed) throws Exception {
+    public static BlindedKeyPair generateBlindedBls12381G1Key(byte[] seed) throws BbsException {
         byte[] public_key = new byte[bls_public_key_g1_size()];
         byte[] secret_key = new byte[bls_public_key_g1_size()]; // TODO Check secret key size, 32b throws exception
         byte[] blinding_factor = new byte[blinding_factor_size()];
-        if (0 != bls_generate_blinded_g1_key(seed, public_key, secret_key, blinding_factor)) {
-            throw new Exception("Unable to generate keys");
-        }
+        bls_generate_blinded_g1_key(seed, public_key, secret_key, blinding_factor);
         return new BlindedKeyPair(public_key, secret_key, blinding_factor);
     }
 
-    public static BlindedKeyPair generateBlindedBls12381G2Key(byte[] seed) throws Exception {
+    public static BlindedKeyPair generateBlindedBls12381G2Key(byte[] seed) throws BbsException {
         byte[] public_key = new byte[bls_public_key_g2_size()];
         byte[] secret_key = new byte[bls_public_key_g2_size()]; // TODO Check secret key size, 32b throws exception
         byte[] blinding_factor = new byte[blinding_factor_size()];
-
-        if (0 != bls_generate_blinded_g2_key(seed, public_key, secret_key, blinding_factor)) {
-            throw new Exception("Unable to generate keys");
-        }
+        bls_generate_blinded_g2_key(seed, public_key, secret_key, blinding_factor);
         return new BlindedKeyPair(public_key, secret_key, blinding_factor);
     }
 
-    public static byte[] blsPublicToBbsPublicKey(byte[] blsPublicKey, int messages) {
+    public static byte[] blsPublicToBbsPublicKey(byte[] blsPublicKey, int messages) throws BbsException {
         return bls_public_key_to_bbs_key(blsPublicKey, messages);
     }
 
-    public static byte[] blsSecretToBbsPublicKey(byte[] blsSecretKey, int messages) {
+    public static byte[] blsSecretToBbsPublicKey(byte[] blsSecretKey, int messages) throws BbsException {
         return bls_secret_key_to_bbs_key(blsSecretKey, messages);
     }
 
-    public static byte[] sign(byte[] secret_key, byte[] public_key, byte[][] messages) throws Exception {
+    public static byte[] sign(byte[] secret_key, byte[] public_key, byte[][] messages) throws BbsException {
         long handle = bbs_sign_init();
-        if (0 == handle) {
-            throw new Exception("Unable to create signing context");
-        }
-        if (0 != bbs_sign_set_secret_key(handle, secret_key)) {
-            throw new Exception("Unable to set secret key");
-        }
-        if (0 != bbs_sign_set_public_key(handle, public_key)) {
-            throw new Exception("Unable to set public key");
-        }
+        bbs_sign_set_secret_key(handle, secret_key);
+        bbs_sign_set_public_key(handle, public_key);
         for (byte[] msg : messages) {
-            if (0 != bbs_sign_add_message_bytes(handle, msg)) {
-                throw new Exception("Unable to add message");
-            }
-        }
-        byte[] signature = new byte[bbs_blind_signature_size()];
-        if (0 != bbs_sign_finish(handle, signature)) {
-            throw new Exception("Unable to create signature");
+            bbs_sign_add_message_bytes(handle, msg);
         }
+        byte[] signature = new byte[bbs_signature_size()];
+        bbs_sign_finish(handle, signature);
         return signature;
     }
 
-    public static byte[] blsSign(byte[] secret_key, byte[] public_key, byte[][] messages) throws Exception {
-        byte[] bbs_public_key = Bbs.blsPublicToBbsPublicKey(public_key, messages.length);
+    public static byte[] sign(byte[] secret_key, byte[] public_key, String[] messages) throws BbsException {
         long handle = bbs_sign_init();
-        if (0 == handle) {
-            throw new Exception("Unable to create signing context");
-        }
-        if (0 != bbs_sign_set_secret_key(handle, secret_key)) {
-            throw new Exception("Unable to set secret key");
-        }
-        if (0 != bbs_sign_set_public_key(handle, bbs_public_key)) {
-            throw new Exception("Unable to set public key");
-        }
-        for (byte[] msg : messages) {
-            if (0 != bbs_sign_add_message_bytes(handle, msg)) {
-                throw new Exception("Unable to add message");
-            }
-        }
-        byte[] signature = new byte[bbs_blind_signature_size()];
-        if (0 != bbs_sign_finish(handle, signature)) {
-            throw new Exception("Unable to create signature");
+        bbs_sign_set_secret_key(handle, secret_key);
+        bbs_sign_set_public_key(handle, public_key);
+        for (String msg : messages) {
+            bbs_sign_add_message_string(handle, msg);
         }
+        byte[] signature = new byte[bbs_signature_size()];
+        bbs_sign_finish(handle, signature);
         return signature;
     }
 
-    public static boolean verify(byte[] public_key, byte[] signature, byte[][] messages) throws Exception {
+    public static byte[] blsSign(byte[] secret_key, byte[] public_key, byte[][] messages) throws BbsException {
+        return sign(secret_key, blsPublicToBbsPublicKey(public_key, messages.length), messages);
+    }
+
+    public static boolean verify(byte[] public_key, byte[] signature, byte[][] messages) throws BbsException {
         long handle = bbs_verify_init();
-        if (0 == handle) {
-            throw new Exception("Unable to create verify signature context");
-        }
-        if (0 != bbs_verify_set_public_key(handle, public_key)) {
-            throw new Exception("Unable to set public key");
-        }
-        if (0 != bbs_verify_set_signature(handle, signature)) {
-            throw new Exception("Unable to set signature");
-        }
+        bbs_verify_set_public_key(handle, public_key);
+        bbs_verify_set_signature(handle, signature);
         for (byte[] msg : messages) {
-            if (0 != bbs_verify_add_message_bytes(handle, msg)) {
-                throw new Exception("Unable to add message");
-            }
-        }
-        int res = bbs_verify_finish(handle);
-
-        switch (res) {
-            case 0:
-                return true;
-            case 1:
-                return false;
-            default:
-                throw new Exception("Unable to verify signature");
+            bbs_verify_add_message_bytes(handle, msg);
         }
+        return 0 == bbs_verify_finish(handle);
     }
 
-    public static boolean blsVerify(byte[] public_key, byte[] signature, byte[][] messages) throws Exception {
-        byte[] bbs_public_key = Bbs.blsPublicToBbsPublicKey(public_key, messages.length);
+    public static boolean verify(byte[] public_key, byte[] signature, String[] messages) throws BbsException {
         long handle = bbs_verify_init();
-        if (0 == handle) {
-            throw new Exception("Unable to create verify signature context");
-        }
-        if (0 != bbs_verify_set_public_key(handle, bbs_public_key)) {
-            throw new Exception("Unable to set public key");
-        }
-        if (0 != bbs_verify_set_signature(handle, signature)) {
-            throw new Exception("Unable to set signature");
-        }
-        for (byte[] msg : messages) {
-            if (0 != bbs_verify_add_message_bytes(handle, msg)) {
-                throw new Exception("Unable to add message");
-            }
-        }
-        int res = bbs_verify_finish(handle);
-
-        switch (res) {
-            case 0:
-                return true;
-            case 1:
-                return false;
-            default:
-                throw new Exception("Unable to verify signature");
+        bbs_verify_set_public_key(handle, public_key);
+        bbs_verify_set_signature(handle, signature);
+        for (String msg : messages) {
+            bbs_verify_add_message_string(handle, msg);
         }
+        return 0 == bbs_verify_finish(handle);
     }
 
-    public static BlindCommitmentContext blindCommitment(byte[] public_key, Map<Integer, byte[]> messages, byte[] nonce) throws Exception {
+    public static boolean blsVerify(byte[] public_key, byte[] signature, byte[][] messages) throws BbsException {
+        return verify(blsPublicToBbsPublicKey(public_key, messages.length), signature, messages);
+    }
+
+    public static BlindCommitmentContext blindCommitment(byte[] public_key, Map<Integer, byte[]> messages, byte[] nonce) throws BbsException {
         long handle = bbs_blind_commitment_init();
-        if (0 == handle) {
-            throw new Exception("Unable to create blind commitment context");
-        }
-        if (0 != bbs_blind_commitment_set_public_key(handle, public_key)) {
-            throw new Exception("Unable to set public key");
-        }
-        if (0 != bbs_blind_commitment_set_nonce_bytes(handle, nonce)) {
-            throw new Exception("Unable to set nonce");
-        }
+        bbs_blind_commitment_set_public_key(handle, public_key);
+        bbs_blind_commitment_set_nonce_bytes(handle, nonce);
         for (Map.Entry<Integer, byte[]> entry : messages.entrySet()) {
-            if (0 != bbs_blind_commitment_add_message_bytes(handle, entry.getKey(), entry.getValue())) {
-                throw new Exception("Unable to add message");
-            }
+            bbs_blind_commitment_add_message_bytes(handle, entry.getKey(), entry.getValue());
         }
-        byte[] blinding_factor = new byte[32];
-        byte[] commitment = new byte[48];
+        byte[] blinding_factor = new byte[blinding_factor_size()];
+        byte[] commitment = new byte[bls_public_key_g1_size()];
         byte[] proof = bbs_blind_commitment_finish(handle, commitment, blinding_factor);
-        if (proof == null || proof.length == 0) {
-            throw new Exception("Unable to create blind commitment");
-        }
-        BlindCommitmentContext context = new BlindCommitmentContext(commitment, proof, blinding_factor);
-        return context;
+        return new BlindCommitmentContext(commitment, proof, blinding_factor);
     }
 
-    public static byte[] blindSign(byte[] secret_key, byte[] public_key, byte[] commitment, Map<Integer, byte[]> messages) throws Exception {
+    /**
+     * Verify the proof of a holder's blind commitment before blind signing it.
+     * Throws BbsProofVerificationException when the proof does not verify
+     */
+    public static boolean verifyBlindCommitment(byte[] public_key, byte[] proof, int[] blinded, byte[] nonce) throws BbsException {
+        long handle = bbs_verify_blind_commitment_context_init();
+        bbs_verify_blind_commitment_context_set_public_key(handle, public_key);
+        bbs_verify_blind_commitment_context_set_proof(handle, proof);
+        bbs_verify_blind_commitment_context_set_nonce_bytes(handle, nonce);
+        for (int index : blinded) {
+            bbs_verify_blind_commitment_context_add_blinded(handle, index);
+        }
+        return 0 == bbs_verify_blind_commitment_context_finish(handle);
+    }
+
+    public static byte[] blindSign(byte[] secret_key, byte[] public_key, byte[] commitment, Map<Integer, byte[]> messages) throws BbsException {
         long handle = bbs_blind_sign_init();
-        if (0 == handle)
-            throw new Exception("Unable to create blind sign context");
-        if (0 != bbs_blind_sign_set_secret_key(handle, secret_key))
-            throw new Exception("Unable to set secret key");
-        if (0 != bbs_blind_sign_set_public_key(handle, public_key))
-            throw new Exception("Unable to set public key");
-        if (0 != bbs_blind_sign_set_commitment(handle, commitment))
-            throw new Exception("Unable to set commitment");
+        bbs_blind_sign_set_secret_key(handle, secret_key);
+        bbs_blind_sign_set_public_key(handle, public_key);
+        bbs_blind_sign_set_commitment(handle, commitment);
         for (Map.Entry<Integer, byte[]> entry : messages.entrySet()) {
-            if (0 != bbs_blind_sign_add_message_bytes(handle, entry.getKey(), entry.getValue())) {
-                throw new Exception("Unable to add message");
-            }
+            bbs_blind_sign_add_message_bytes(handle, entry.getKey(), entry.getValue());
         }
         byte[] blind_signature = new byte[bbs_blind_signature_size()];
-        if (0 != bbs_blind_sign_finish(handle, blind_signature))
-            throw new Exception("Unable to create blind signature");
+        bbs_blind_sign_finish(handle, blind_signature);
         return blind_signature;
     }
 
-    public static byte[] unblindSignature(byte[] blindSignature, byte[] blindingFactor) {
+    public static byte[] unblindSignature(byte[] blindSignature, byte[] blindingFactor) throws BbsException {
         byte[] signature = new byte[bbs_signature_size()];
-        if (0 != bbs_unblind_signature(blindSignature, blindingFactor, signature)) {
-            return null;
-        }
+        bbs_unblind_signature(blindSignature, blindingFactor, signature);
         return signature;
     }
 
-    public static byte[] createProof(byte[] publicKey, byte[] nonce, byte[] signature, ProofMessage[] messages) throws Exception {
+    public static byte[] createProof(byte[] publicKey, byte[] nonce, byte[] signature, ProofMessage[] messages) throws BbsException {
         long handle = bbs_create_proof_context_init();
-        if (0 == handle) {
-            throw new Exception("Unable to create proof context");
-        }
-        if (0 != bbs_create_proof_context_set_public_key(handle, publicKey)) {
-            throw new Exception("Unable to set public key");
-        }
-        if (0 != bbs_create_proof_context_set_nonce_bytes(handle, nonce)) {
-            throw new Exception("Unable to set nonce");
-        }
-        if (0 != bbs_create_proof_context_set_signature(handle, signature)) {
-            throw new Exception("Unable to set signature: " + get_last_error());
-        }
-        for (ProofMessage message : messages) {
-            if (0 != bbs_create_proof_context_add_proof_message_bytes(handle, message.message, message.type, message.blinding_factor)) {
-                throw new Exception("Unable to add proof message");
-            }
-        }
-        byte[] proof = new byte[bbs_create_proof_size(handle)];
-        if (0 != bbs_create_proof_context_finish(handle, proof)) {
-            throw new Exception("Unable to create proof");
-        }
-        return proof;
+        bbs_create_proof_context_set_public_key(handle, publicKey);
+        bbs_create_proof_context_set_nonce_bytes(handle, nonce);
+        return createProof(handle, signature, messages);
     }
 
-    public static byte[] blsCreateProof(byte[] public_key, byte[] nonce, byte[] signature, ProofMessage[] messages) throws Exception {
-        byte[] bbs_public_key = Bbs.blsPublicToBbsPublicKey(public_key, messages.length);
+    public static byte[] createProof(byte[] publicKey, String nonce, byte[] signature, ProofMessage[] messages) throws BbsException {
         long handle = bbs_create_proof_context_init();
-        if (0 == handle) {
-            throw new Exception("Unable to create proof context");
-        }
-        if (0 != bbs_create_proof_context_set_public_key(handle, bbs_public_key)) {
-            throw new Exception("Unable to set public key");
-        }
-        if (0 != bbs_create_proof_context_set_nonce_bytes(handle, nonce)) {
-            throw new Exception("Unable to set nonce");
-        }
-        if (0 != bbs_create_proof_context_set_signature(handle, signature)) {
-            throw new Exception("Unable to set signature: " + get_last_error());
-        }
+        bbs_create_proof_context_set_public_key(handle, publicKey);
+        bbs_create_proof_context_set_nonce_string(handle, nonce);
+        return createProof(handle, signature, messages);
+    }
+
+    private static byte[] createProof(long handle, byte[] signature, ProofMessage[] messages) throws BbsException {
+        bbs_create_proof_context_set_signature(handle, signature);
         for (ProofMessage message : messages) {
-            if (0 != bbs_create_proof_context_add_proof_message_bytes(handle, message.message, message.type, message.blinding_factor)) {
-                throw new Exception("Unable to add proof message");
-            }
+            bbs_create_proof_context_add_proof_message_bytes(handle, message.message, message.type, message.blinding_factor);
         }
         byte[] proof = new byte[bbs_create_proof_size(handle)];
-        if (0 != bbs_create_proof_context_finish(handle, proof)) {
-            throw new Exception("Unable to create proof");
-        }
+        bbs_create_proof_context_finish(handle, proof);
         return proof;
     }
 
-    public static boolean verifyProof(byte[] public_key, byte[] proof, byte[] nonce, byte[][] messages) throws Exception {
+    public static byte[] blsCreateProof(byte[] public_key, byte[] nonce, byte[] signature, ProofMessage[] messages) throws BbsException {
+        return createProof(blsPublicToBbsPublicKey(public_key, messages.length), nonce, signature, messages);
+    }
+
+    /**
+     * Throws BbsProofVerificationException when the proof does not verify
+     */
+    public static boolean verifyProof(byte[] public_key, byte[] proof, byte[] nonce, byte[][] messages) throws BbsException {
         long handle = bbs_verify_proof_context_init();
-        if (0 == handle) {
-            throw new Exception("Unable to create verify signature context");
-        }
-        if (0 != bbs_verify_proof_context_set_public_key(handle, public_key)) {
-            throw new Exception("Unable to set public key");
-        }
-        if (0 != bbs_verify_proof_context_set_proof(handle, proof)) {
-            throw new Exception("Unable to set proof");
-        }
-        if (0 != bbs_verify_proof_context_set_nonce_bytes(handle, nonce)) {
-            throw new Exception("Unable to set nonce");
-        }
+        bbs_verify_proof_context_set_public_key(handle, public_key);
+        bbs_verify_proof_context_set_proof(handle, proof);
+        bbs_verify_proof_context_set_nonce_bytes(handle, nonce);
         for (byte[] msg : messages) {
-            if (0 != bbs_verify_proof_context_add_message_bytes(handle, msg)) {
-                throw new Exception("Unable to add message");
-            }
+            bbs_verify_proof_context_add_message_bytes(handle, msg);
         }
-        int res = bbs_verify_proof_context_finish(handle);
-
-        return res <= 0;
+        return 0 == bbs_verify_proof_context_finish(handle);
     }
 
-    public static boolean blsVerifyProof(byte[] public_key, byte[] proof, byte[] nonce, byte[][] revealed_messages) throws Exception {
-        int messages = Bbs.getTotalMessagesCountForProof(proof);
-        byte[] bbs_public_key = Bbs.blsPublicToBbsPublicKey(public_key, messages);
+    /**
+     * Throws BbsProofVerificationException when the proof does not verify
+     */
+    public static boolean verifyProof(byte[] public_key, byte[] proof, String nonce, String[] messages) throws BbsException {
         long handle = bbs_verify_proof_context_init();
-        if (0 == handle) {
-            throw new Exception("Unable to create verify signature context");
-        }
-        if (0 != bbs_verify_proof_context_set_public_key(handle, bbs_public_key)) {
-            throw new Exception("Unable to set public key");
-        }
-        if (0 != bbs_verify_proof_context_set_proof(handle, proof)) {
-            throw new Exception("Unable to set proof");
-        }
-        if (0 != bbs_verify_proof_context_set_nonce_bytes(handle, nonce)) {
-            throw new Exception("Unable to set nonce");
+        bbs_verify_proof_context_set_public_key(handle, public_key);
+        bbs_verify_proof_context_set_proof(handle, proof);
+        bbs_verify_proof_context_set_nonce_string(handle, nonce);
+        for (String msg : messages) {
+            bbs_verify_proof_context_add_message_string(handle, msg);
         }
-        for (byte[] msg : revealed_messages) {
-            if (0 != bbs_verify_proof_context_add_message_bytes(handle, msg)) {
-                throw new Exception("Unable to add message");
-            }
+        return 0 == bbs_verify_proof_context_finish(handle);
+    }
+
+    /**
+     * Verify with the revealed messages keyed by index.
+     * Throws BbsProofVerificationException when the proof does not verify or reveals different indices
+     */
+    public static boolean verifyProof(byte[] public_key, byte[] proof, byte[] nonce, Map<Integer, byte[]> messages) throws BbsException {
+        long handle = bbs_verify_proof_context_init();
+        bbs_verify_proof_context_set_public_key(handle, public_key);
+        bbs_verify_proof_context_set_proof(handle, proof);
+        bbs_verify_proof_context_set_nonce_bytes(handle, nonce);
+        for (Map.Entry<Integer, byte[]> entry : messages.entrySet()) {
+            bbs_verify_proof_context_add_revealed_message(handle, entry.getKey(), entry.getValue());
         }
-        int res = bbs_verify_proof_context_finish(handle);
+        return 0 == bbs_verify_proof_context_finish(handle);
+    }
 
-        return res <= 0;
+    public static boolean blsVerifyProof(byte[] public_key, byte[] proof, byte[] nonce, byte[][] revealed_messages) throws BbsException {
+        int messages = Bbs.getTotalMessagesCountForProof(proof);
+        return verifyProof(blsPublicToBbsPublicKey(public_key, messages), proof, nonce, revealed_messages);
     }
-}
\ No newline at end of file
+}
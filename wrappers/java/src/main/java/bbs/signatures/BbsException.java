@@ -0,0 +1,10 @@
+package bbs.signatures;
+
+/**
+ * Thrown when a call into the native bbs library fails
+ */
+public class BbsException extends Exception {
+    public BbsException(String message) {
+        super(message);
+    }
+}
@@ -0,0 +1,10 @@
+package bbs.signatures;
+
+/**
+ * Thrown when a secret or public key cannot be generated, parsed or converted
+ */
+public class BbsInvalidKeyException extends BbsException {
+    public BbsInvalidKeyException(String message) {
+        super(message);
+    }
+}
@@ -0,0 +1,10 @@
+package bbs.signatures;
+
+/**
+ * Thrown when a signature or blind signature cannot be parsed or unblinded
+ */
+public class BbsInvalidSignatureException extends BbsException {
+    public BbsInvalidSignatureException(String message) {
+        super(message);
+    }
+}
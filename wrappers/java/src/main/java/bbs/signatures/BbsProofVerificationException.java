@@ -0,0 +1,10 @@
+package bbs.signatures;
+
+/**
+ * Thrown when a proof or blind commitment proof cannot be parsed or does not verify
+ */
+public class BbsProofVerificationException extends BbsException {
+    public BbsProofVerificationException(String message) {
+        super(message);
+    }
+}
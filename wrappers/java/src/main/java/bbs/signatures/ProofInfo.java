@@ -0,0 +1,15 @@
+package bbs.signatures;
+
+public class ProofInfo {
+    public int version;
+    public int messageCount;
+    public int hiddenCount;
+    public int[] revealed;
+
+    public ProofInfo(int version, int messageCount, int hiddenCount, int[] revealed) {
+        this.version = version;
+        this.messageCount = messageCount;
+        this.hiddenCount = hiddenCount;
+        this.revealed = revealed;
+    }
+}
@@ -7,6 +7,32 @@
 #ifdef __cplusplus
 extern "C" {
 #endif
+#undef bbs_signatures_Bbs_ABI_VERSION
+#define bbs_signatures_Bbs_ABI_VERSION 2L
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_library_version
+ * Signature: ()Ljava/lang/String;
+ */
+JNIEXPORT jstring JNICALL Java_bbs_signatures_Bbs_bbs_1library_1version
+  (JNIEnv *, jclass);
+
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_abi_version
+ * Signature: ()I
+ */
+JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1abi_1version
+  (JNIEnv *, jclass);
+
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_supported_features
+ * Signature: ()Ljava/lang/String;
+ */
+JNIEXPORT jstring JNICALL Java_bbs_signatures_Bbs_bbs_1supported_1features
+  (JNIEnv *, jclass);
+
 /*
  * Class:     bbs_signatures_Bbs
  * Method:    bls_public_key_g1_size
@@ -127,6 +153,14 @@ JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1sign_1set_1public_1key
 JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1sign_1add_1message_1bytes
   (JNIEnv *, jclass, jlong, jbyteArray);
 
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_sign_add_message_string
+ * Signature: (JLjava/lang/String;)I
+ */
+JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1sign_1add_1message_1string
+  (JNIEnv *, jclass, jlong, jstring);
+
 /*
  * Class:     bbs_signatures_Bbs
  * Method:    bbs_sign_add_message_prehashed
@@ -159,6 +193,14 @@ JNIEXPORT jlong JNICALL Java_bbs_signatures_Bbs_bbs_1verify_1init
 JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1verify_1add_1message_1bytes
   (JNIEnv *, jclass, jlong, jbyteArray);
 
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_verify_add_message_string
+ * Signature: (JLjava/lang/String;)I
+ */
+JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1verify_1add_1message_1string
+  (JNIEnv *, jclass, jlong, jstring);
+
 /*
  * Class:     bbs_signatures_Bbs
  * Method:    bbs_verify_add_message_prehashed
@@ -215,6 +257,14 @@ JNIEXPORT jlong JNICALL Java_bbs_signatures_Bbs_bbs_1blind_1commitment_1init
 JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1blind_1commitment_1add_1message_1bytes
   (JNIEnv *, jclass, jlong, jint, jbyteArray);
 
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_blind_commitment_add_message_string
+ * Signature: (JILjava/lang/String;)I
+ */
+JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1blind_1commitment_1add_1message_1string
+  (JNIEnv *, jclass, jlong, jint, jstring);
+
 /*
  * Class:     bbs_signatures_Bbs
  * Method:    bbs_blind_commitment_add_prehashed
@@ -239,6 +289,22 @@ JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1blind_1commitment_1set_1publ
 JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1blind_1commitment_1set_1nonce_1bytes
   (JNIEnv *, jclass, jlong, jbyteArray);
 
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_blind_commitment_set_nonce_string
+ * Signature: (JLjava/lang/String;)I
+ */
+JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1blind_1commitment_1set_1nonce_1string
+  (JNIEnv *, jclass, jlong, jstring);
+
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_blind_commitment_set_nonce_prehashed
+ * Signature: (J[B)I
+ */
+JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1blind_1commitment_1set_1nonce_1prehashed
+  (JNIEnv *, jclass, jlong, jbyteArray);
+
 /*
  * Class:     bbs_signatures_Bbs
  * Method:    bbs_blind_commitment_finish
@@ -287,6 +353,14 @@ JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1blind_1sign_1set_1commitment
 JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1blind_1sign_1add_1message_1bytes
   (JNIEnv *, jclass, jlong, jint, jbyteArray);
 
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_blind_sign_add_message_string
+ * Signature: (JILjava/lang/String;)I
+ */
+JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1blind_1sign_1add_1message_1string
+  (JNIEnv *, jclass, jlong, jint, jstring);
+
 /*
  * Class:     bbs_signatures_Bbs
  * Method:    bbs_blind_sign_add_prehashed
@@ -343,6 +417,22 @@ JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1create_1proof_1context_1set_
 JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1create_1proof_1context_1set_1nonce_1bytes
   (JNIEnv *, jclass, jlong, jbyteArray);
 
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_create_proof_context_set_nonce_string
+ * Signature: (JLjava/lang/String;)I
+ */
+JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1create_1proof_1context_1set_1nonce_1string
+  (JNIEnv *, jclass, jlong, jstring);
+
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_create_proof_context_set_nonce_prehashed
+ * Signature: (J[B)I
+ */
+JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1create_1proof_1context_1set_1nonce_1prehashed
+  (JNIEnv *, jclass, jlong, jbyteArray);
+
 /*
  * Class:     bbs_signatures_Bbs
  * Method:    bbs_create_proof_context_add_proof_message_bytes
@@ -351,6 +441,22 @@ JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1create_1proof_1context_1set_
 JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1create_1proof_1context_1add_1proof_1message_1bytes
   (JNIEnv *, jclass, jlong, jbyteArray, jint, jbyteArray);
 
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_create_proof_context_add_proof_message_string
+ * Signature: (JLjava/lang/String;I[B)I
+ */
+JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1create_1proof_1context_1add_1proof_1message_1string
+  (JNIEnv *, jclass, jlong, jstring, jint, jbyteArray);
+
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_create_proof_context_add_proof_message_prehashed
+ * Signature: (J[BI[B)I
+ */
+JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1create_1proof_1context_1add_1proof_1message_1prehashed
+  (JNIEnv *, jclass, jlong, jbyteArray, jint, jbyteArray);
+
 /*
  * Class:     bbs_signatures_Bbs
  * Method:    bbs_create_proof_context_finish
@@ -383,6 +489,22 @@ JNIEXPORT jlong JNICALL Java_bbs_signatures_Bbs_bbs_1verify_1proof_1context_1ini
 JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1verify_1proof_1context_1add_1message_1bytes
   (JNIEnv *, jclass, jlong, jbyteArray);
 
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_verify_proof_context_add_revealed_message
+ * Signature: (JI[B)I
+ */
+JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1verify_1proof_1context_1add_1revealed_1message
+  (JNIEnv *, jclass, jlong, jint, jbyteArray);
+
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_verify_proof_context_add_message_string
+ * Signature: (JLjava/lang/String;)I
+ */
+JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1verify_1proof_1context_1add_1message_1string
+  (JNIEnv *, jclass, jlong, jstring);
+
 /*
  * Class:     bbs_signatures_Bbs
  * Method:    bbs_verify_proof_context_add_message_prehashed
@@ -415,6 +537,22 @@ JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1verify_1proof_1context_1set_
 JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1verify_1proof_1context_1set_1nonce_1bytes
   (JNIEnv *, jclass, jlong, jbyteArray);
 
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_verify_proof_context_set_nonce_string
+ * Signature: (JLjava/lang/String;)I
+ */
+JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1verify_1proof_1context_1set_1nonce_1string
+  (JNIEnv *, jclass, jlong, jstring);
+
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_verify_proof_context_set_nonce_prehashed
+ * Signature: (J[B)I
+ */
+JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1verify_1proof_1context_1set_1nonce_1prehashed
+  (JNIEnv *, jclass, jlong, jbyteArray);
+
 /*
  * Class:     bbs_signatures_Bbs
  * Method:    bbs_verify_proof_context_finish
@@ -423,6 +561,70 @@ JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1verify_1proof_1context_1set_
 JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1verify_1proof_1context_1finish
   (JNIEnv *, jclass, jlong);
 
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_verify_blind_commitment_context_init
+ * Signature: ()J
+ */
+JNIEXPORT jlong JNICALL Java_bbs_signatures_Bbs_bbs_1verify_1blind_1commitment_1context_1init
+  (JNIEnv *, jclass);
+
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_verify_blind_commitment_context_add_blinded
+ * Signature: (JI)I
+ */
+JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1verify_1blind_1commitment_1context_1add_1blinded
+  (JNIEnv *, jclass, jlong, jint);
+
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_verify_blind_commitment_context_set_public_key
+ * Signature: (J[B)I
+ */
+JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1verify_1blind_1commitment_1context_1set_1public_1key
+  (JNIEnv *, jclass, jlong, jbyteArray);
+
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_verify_blind_commitment_context_set_nonce_bytes
+ * Signature: (J[B)I
+ */
+JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1verify_1blind_1commitment_1context_1set_1nonce_1bytes
+  (JNIEnv *, jclass, jlong, jbyteArray);
+
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_verify_blind_commitment_context_set_nonce_string
+ * Signature: (JLjava/lang/String;)I
+ */
+JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1verify_1blind_1commitment_1context_1set_1nonce_1string
+  (JNIEnv *, jclass, jlong, jstring);
+
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_verify_blind_commitment_context_set_nonce_prehashed
+ * Signature: (J[B)I
+ */
+JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1verify_1blind_1commitment_1context_1set_1nonce_1prehashed
+  (JNIEnv *, jclass, jlong, jbyteArray);
+
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_verify_blind_commitment_context_set_proof
+ * Signature: (J[B)I
+ */
+JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1verify_1blind_1commitment_1context_1set_1proof
+  (JNIEnv *, jclass, jlong, jbyteArray);
+
+/*
+ * Class:     bbs_signatures_Bbs
+ * Method:    bbs_verify_blind_commitment_context_finish
+ * Signature: (J)I
+ */
+JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1verify_1blind_1commitment_1context_1finish
+  (JNIEnv *, jclass, jlong);
+
 /*
  * Class:     bbs_signatures_Bbs
  * Method:    bbs_get_total_messages_count_for_proof
@@ -433,11 +635,11 @@ JNIEXPORT jint JNICALL Java_bbs_signatures_Bbs_bbs_1get_1total_1messages_1count_
 
 /*
  * Class:     bbs_signatures_Bbs
- * Method:    get_last_error
- * Signature: ()Ljava/lang/String;
+ * Method:    bbs_proof_inspect
+ * Signature: ([B)[I
  */
-JNIEXPORT jstring JNICALL Java_bbs_signatures_Bbs_get_1last_1error
-  (JNIEnv *, jclass);
+JNIEXPORT jintArray JNICALL Java_bbs_signatures_Bbs_bbs_1proof_1inspect
+  (JNIEnv *, jclass, jbyteArray);
 
 #ifdef __cplusplus
 }
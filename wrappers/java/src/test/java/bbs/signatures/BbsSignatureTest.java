@@ -13,7 +13,7 @@ public class BbsSignatureTest {
 
     public KeyPair keyPair;
 
-    private KeyPair getBls12381G2KeyPair() {
+    private KeyPair getBls12381G2KeyPair() throws Exception {
         byte[] seed = new byte[0];
         KeyPair keyPair = null;
 
@@ -39,37 +39,57 @@ public class BbsSignatureTest {
     }
 
     @Test
-    public void canGetCorrectBls12381G1PublicKeySize() {
+    public void loadedLibraryMatchesAbiVersion() throws Exception {
+        assertEquals(Bbs.ABI_VERSION, Bbs.getAbiVersion());
+    }
+
+    @Test
+    public void canGetLibraryVersion() throws Exception {
+        String version = Bbs.getLibraryVersion();
+        assertNotNull(version);
+        assertTrue(version.matches("\\d+\\.\\d+\\.\\d+.*"));
+    }
+
+    @Test
+    public void canGetSupportedFeatures() throws Exception {
+        String features = Bbs.getSupportedFeatures();
+        assertTrue(features.contains("\"BBS+_BLS12381G1_BLAKE2B\""));
+        assertTrue(features.contains("\"java\":true"));
+        assertTrue(features.contains("\"libraryVersion\":\"" + Bbs.getLibraryVersion() + "\""));
+    }
+
+    @Test
+    public void canGetCorrectBls12381G1PublicKeySize() throws Exception {
         int size = Bbs.getBls12381G1PublicKeySize();
         assertEquals(48, size);
     }
 
     @Test
-    public void canGetCorrectBls12381G2PublicKeySize() {
+    public void canGetCorrectBls12381G2PublicKeySize() throws Exception {
         int size = Bbs.getBls12381G2PublicKeySize();
         assertEquals(96, size);
     }
 
     @Test
-    public void canGetSecretKeySize() {
+    public void canGetSecretKeySize() throws Exception {
         int size = Bbs.getSecretKeySize();
         assertEquals(32, size);
     }
 
     @Test
-    public void canGetSignatureSize() {
+    public void canGetSignatureSize() throws Exception {
         int size = Bbs.getSignatureSize();
         assertEquals(112, size);
     }
 
     @Test
-    public void canGetBlindSignatureSize() {
+    public void canGetBlindSignatureSize() throws Exception {
         int size = Bbs.getBlindSignatureSize();
         assertEquals(112, size);
     }
 
     @Test
-    public void canGenerateBls12381G1Key() {
+    public void canGenerateBls12381G1Key() throws Exception {
         byte[] seed = new byte[0];
         KeyPair keyPair = null;
 
@@ -85,19 +105,29 @@ public class BbsSignatureTest {
     }
 
     @Test
-    public void shouldThrowExceptionMessageWhenFailToGenerateBls12381G1Key() {
+    public void shouldThrowExceptionMessageWhenFailToGenerateBls12381G1Key() throws Exception {
         byte[] seed = null;
 
         try {
             Bbs.generateBls12381G1Key(seed);
             fail("Expected an exception to be thrown");
-        } catch (Exception exception) {
-            assertEquals("Unable to generate keys", exception.getMessage());
+        } catch (BbsInvalidKeyException exception) {
+            assertTrue(exception.getMessage().startsWith("Unable to generate keys"));
         }
     }
 
     @Test
-    public void canGenerateBls12381G2Key() {
+    public void shouldThrowInvalidKeyExceptionWhenBlsPublicKeyIsInvalid() throws Exception {
+        try {
+            Bbs.blsPublicToBbsPublicKey(new byte[10], 1);
+            fail("Expected an exception to be thrown");
+        } catch (BbsInvalidKeyException exception) {
+            assertTrue(exception.getMessage().startsWith("Unable to convert public key"));
+        }
+    }
+
+    @Test
+    public void canGenerateBls12381G2Key() throws Exception {
         byte[] seed = new byte[0];
         KeyPair keyPair = null;
 
@@ -113,19 +143,19 @@ public class BbsSignatureTest {
     }
 
     @Test
-    public void shouldThrowExceptionMessageWhenFailToGenerateBls12381G2Key() {
+    public void shouldThrowExceptionMessageWhenFailToGenerateBls12381G2Key() throws Exception {
         byte[] seed = null;
 
         try {
             Bbs.generateBls12381G2Key(seed);
             fail("Expected an exception to be thrown");
-        } catch (Exception exception) {
-            assertEquals("Unable to generate keys", exception.getMessage());
+        } catch (BbsInvalidKeyException exception) {
+            assertTrue(exception.getMessage().startsWith("Unable to generate keys"));
         }
     }
 
     @Test
-    public void canSignMessage() {
+    public void canSignMessage() throws Exception {
         KeyPair keyPair = getBls12381G2KeyPair();
 
         byte[][] messages = {
@@ -148,7 +178,7 @@ public class BbsSignatureTest {
     }
 
     @Test
-    public void canBlsSignMessage() {
+    public void canBlsSignMessage() throws Exception {
         KeyPair keyPair = getBls12381G2KeyPair();
         byte[] publicKey = keyPair.publicKey;
         byte[] secretKey = keyPair.secretKey;
@@ -171,7 +201,7 @@ public class BbsSignatureTest {
     }
 
     @Test
-    public void canVerifyMessage() {
+    public void canVerifyMessage() throws Exception {
         KeyPair keyPair = getBls12381G2KeyPair();
 
         byte[][] messages = {"message1".getBytes()};
@@ -200,7 +230,7 @@ public class BbsSignatureTest {
     }
 
     @Test
-    public void canBlsVerifyMessage() {
+    public void canBlsVerifyMessage() throws Exception {
         KeyPair keyPair = getBls12381G2KeyPair();
         byte[] publicKey = keyPair.publicKey;
         byte[] secretKey = keyPair.secretKey;
@@ -228,7 +258,7 @@ public class BbsSignatureTest {
     }
 
     @Test
-    public void shouldThrowExceptionMessageWhenVerificationPublicKeyIsInvalid() {
+    public void shouldThrowExceptionMessageWhenVerificationPublicKeyIsInvalid() throws Exception {
         byte[] invalidPublicKey = new byte[96];
         byte[][] messages = {"message1".getBytes()};
         byte[] signature = new byte[112];
@@ -236,13 +266,13 @@ public class BbsSignatureTest {
         try {
             Bbs.verify(invalidPublicKey, signature, messages);
             fail("Expected an exception to be thrown");
-        } catch (Exception exception) {
-            assertEquals("Unable to set public key", exception.getMessage());
+        } catch (BbsInvalidKeyException exception) {
+            assertTrue(exception.getMessage().startsWith("Unable to set public key"));
         }
     }
 
     @Test
-    public void shouldThrowExceptionMessageWhenVerificationSignatureIsInvalid() {
+    public void shouldThrowExceptionMessageWhenVerificationSignatureIsInvalid() throws Exception {
         KeyPair keyPair = getBls12381G2KeyPair();
 
         byte[][] messages = {"message1".getBytes()};
@@ -252,13 +282,13 @@ public class BbsSignatureTest {
         try {
             Bbs.verify(bbsKey, invalidSignature, messages);
             fail("Expected an exception to be thrown");
-        } catch (Exception exception) {
-            assertEquals("Unable to set signature", exception.getMessage());
+        } catch (BbsInvalidSignatureException exception) {
+            assertTrue(exception.getMessage().startsWith("Unable to set signature"));
         }
     }
 
     @Test
-    public void shouldThrowExceptionMessageWhenUnableToVerifySignature() {
+    public void shouldThrowExceptionMessageWhenUnableToVerifySignature() throws Exception {
         KeyPair keyPair = getBls12381G2KeyPair();
 
         byte[][] messages = {"message1".getBytes()};
@@ -277,13 +307,13 @@ public class BbsSignatureTest {
         try {
             Bbs.verify(bbsKey, new byte[112], messages);
             fail("Expected an exception to be thrown");
-        } catch (Exception exception) {
-            assertEquals("Unable to set signature", exception.getMessage());
+        } catch (BbsInvalidSignatureException exception) {
+            assertTrue(exception.getMessage().startsWith("Unable to set signature"));
         }
     }
 
     @Test
-    public void canCreateBlindCommitment() {
+    public void canCreateBlindCommitment() throws Exception {
         KeyPair keyPair = getBls12381G2KeyPair();
 
         byte[] message = "message1".getBytes();
@@ -306,7 +336,7 @@ public class BbsSignatureTest {
     }
 
     @Test
-    public void shouldThrowExceptionMessageWhenBlindCommitmentPublicKeyIsInvalid() {
+    public void shouldThrowExceptionMessageWhenBlindCommitmentPublicKeyIsInvalid() throws Exception {
         byte[] invalidPublicKey = new byte[96];
         byte[] nonce = new byte[32];
         Map<Integer, byte[]> messages = Collections.emptyMap();
@@ -314,13 +344,13 @@ public class BbsSignatureTest {
         try {
             Bbs.blindCommitment(invalidPublicKey, messages, nonce);
             fail("Expected an exception to be thrown");
-        } catch (Exception exception) {
-            assertEquals("Unable to set public key", exception.getMessage());
+        } catch (BbsInvalidKeyException exception) {
+            assertTrue(exception.getMessage().startsWith("Unable to set public key"));
         }
     }
 
     @Test
-    public void shouldThrowExceptionMessageWhenBlindCommitmentNonceIsInvalid() {
+    public void shouldThrowExceptionMessageWhenBlindCommitmentNonceIsInvalid() throws Exception {
         KeyPair keyPair = getBls12381G2KeyPair();
 
         Map<Integer, byte[]> messages = Collections.emptyMap();
@@ -330,13 +360,13 @@ public class BbsSignatureTest {
         try {
             Bbs.blindCommitment(bbsKey, messages, invalidNonce);
             fail("Expected an exception to be thrown");
-        } catch (Exception exception) {
-            assertEquals("Unable to set nonce", exception.getMessage());
+        } catch (BbsException exception) {
+            assertTrue(exception.getMessage().startsWith("Unable to set nonce"));
         }
     }
 
     @Test
-    public void shouldThrowExceptionMessageWhenBlindCommitmentMessageIsInvalid() {
+    public void shouldThrowExceptionMessageWhenBlindCommitmentMessageIsInvalid() throws Exception {
         KeyPair keyPair = getBls12381G2KeyPair();
 
         byte[] invalidMessage = "".getBytes();
@@ -350,13 +380,13 @@ public class BbsSignatureTest {
         try {
             Bbs.blindCommitment(bbsKey, invalidMessages, nonce);
             fail("Expected an exception to be thrown");
-        } catch (Exception exception) {
-            assertEquals("Unable to add message", exception.getMessage());
+        } catch (BbsException exception) {
+            assertTrue(exception.getMessage().startsWith("Unable to add message"));
         }
     }
 
     @Test
-    public void canBlindSign() {
+    public void canBlindSign() throws Exception {
         KeyPair keyPair = getBls12381G2KeyPair();
 
         byte[] message = "message1".getBytes();
@@ -364,33 +394,22 @@ public class BbsSignatureTest {
             put(0, message);
         }};
 
-        byte[] bbsKey = Bbs.blsPublicToBbsPublicKey(keyPair.publicKey, messages.size());
-        byte[] secretKey = new byte[32];
-        byte[] nonce = new byte[32];
-
-        BlindCommitmentContext blindCommitmentContext = null;
+        Map<Integer, byte[]> known = new HashMap<Integer, byte[]>() {{
+            put(1, "message2".getBytes());
+        }};
 
-        try {
-            blindCommitmentContext = Bbs.blindCommitment(bbsKey, messages, nonce);
-        } catch (Exception exception) {
-            exception.printStackTrace();
-        }
+        byte[] bbsKey = Bbs.blsPublicToBbsPublicKey(keyPair.publicKey, 2);
+        byte[] nonce = new byte[32];
 
+        BlindCommitmentContext blindCommitmentContext = Bbs.blindCommitment(bbsKey, messages, nonce);
         assertNotNull(blindCommitmentContext);
 
-        byte[] blindSignature = new byte[112];
-
-        try {
-            blindSignature = Bbs.blindSign(secretKey, bbsKey, blindCommitmentContext.commitment, messages);
-        } catch (Exception exception) {
-            exception.printStackTrace();
-        }
-
-        assertNotNull(blindSignature);
+        byte[] blindSignature = Bbs.blindSign(keyPair.secretKey, bbsKey, blindCommitmentContext.commitment, known);
+        assertEquals(Bbs.getBlindSignatureSize(), blindSignature.length);
     }
 
     @Test
-    public void shouldThrowExceptionMessageWhenBlindSignSecretKeyIsInvalid() {
+    public void shouldThrowExceptionMessageWhenBlindSignSecretKeyIsInvalid() throws Exception {
         byte[] publicKey = new byte[96];
         byte[] invalidSecretKey = null;
         byte[] commitment = new byte[48];
@@ -399,13 +418,13 @@ public class BbsSignatureTest {
         try {
             Bbs.blindSign(invalidSecretKey, publicKey, commitment, messages);
             fail("Expected an exception to be thrown");
-        } catch (Exception exception) {
-            assertEquals("Unable to set secret key", exception.getMessage());
+        } catch (BbsInvalidKeyException exception) {
+            assertTrue(exception.getMessage().startsWith("Unable to set secret key"));
         }
     }
 
     @Test
-    public void shouldThrowExceptionMessageWhenBlindSignPublicKeyIsInvalid() {
+    public void shouldThrowExceptionMessageWhenBlindSignPublicKeyIsInvalid() throws Exception {
         byte[] publicKey = new byte[96];
         byte[] secretKey = new byte[32];
         byte[] commitment = new byte[48];
@@ -414,13 +433,13 @@ public class BbsSignatureTest {
         try {
             Bbs.blindSign(secretKey, publicKey, commitment, messages);
             fail("Expected an exception to be thrown");
-        } catch (Exception exception) {
-            assertEquals("Unable to set public key", exception.getMessage());
+        } catch (BbsInvalidKeyException exception) {
+            assertTrue(exception.getMessage().startsWith("Unable to set public key"));
         }
     }
 
     @Test
-    public void shouldThrowExceptionMessageWhenBlindSignCommitmentIsInvalid() {
+    public void shouldThrowExceptionMessageWhenBlindSignCommitmentIsInvalid() throws Exception {
         KeyPair keyPair = getBls12381G2KeyPair();
 
         Map<Integer, byte[]> messages = Collections.emptyMap();
@@ -431,13 +450,13 @@ public class BbsSignatureTest {
         try {
             Bbs.blindSign(secretKey, bbsKey, invalidCommitment, messages);
             fail("Expected an exception to be thrown");
-        } catch (Exception exception) {
-            assertEquals("Unable to set commitment", exception.getMessage());
+        } catch (BbsException exception) {
+            assertTrue(exception.getMessage().startsWith("Unable to set commitment"));
         }
     }
 
     @Test
-    public void shouldThrowExceptionMessageWhenBlindSignMessageIsInvalid() {
+    public void shouldThrowExceptionMessageWhenBlindSignMessageIsInvalid() throws Exception {
         KeyPair keyPair = getBls12381G2KeyPair();
 
         byte[] message = "message1".getBytes();
@@ -465,33 +484,49 @@ public class BbsSignatureTest {
         try {
             Bbs.blindSign(secretKey, bbsKey, blindCommitmentContext.commitment, invalidMessages);
             fail("Expected an exception to be thrown");
-        } catch (Exception exception) {
-            assertEquals("Unable to add message", exception.getMessage());
+        } catch (BbsException exception) {
+            assertTrue(exception.getMessage().startsWith("Unable to add message"));
         }
     }
 
     @Test
-    public void canUnblindSignature() {
-        byte[] blindSignature = new byte[112];
-        byte[] blindingFactor = new byte[32];
-        byte[] signature;
+    public void canUnblindSignature() throws Exception {
+        KeyPair keyPair = getBls12381G2KeyPair();
+
+        byte[][] messages = {"message1".getBytes(), "message2".getBytes()};
+        Map<Integer, byte[]> hidden = new HashMap<Integer, byte[]>() {{
+            put(0, messages[0]);
+        }};
+        Map<Integer, byte[]> known = new HashMap<Integer, byte[]>() {{
+            put(1, messages[1]);
+        }};
+
+        byte[] bbsKey = Bbs.blsPublicToBbsPublicKey(keyPair.publicKey, messages.length);
+        byte[] nonce = new byte[32];
+
+        BlindCommitmentContext blindCommitmentContext = Bbs.blindCommitment(bbsKey, hidden, nonce);
+        byte[] blindSignature = Bbs.blindSign(keyPair.secretKey, bbsKey, blindCommitmentContext.commitment, known);
+        byte[] signature = Bbs.unblindSignature(blindSignature, blindCommitmentContext.blinding_factor);
 
-        signature = Bbs.unblindSignature(blindSignature, blindingFactor);
         assertNotNull(signature);
+        assertTrue(Bbs.verify(bbsKey, signature, messages));
     }
 
     @Test
-    public void shouldReturnNullWhenCantUnblindSignature() {
+    public void shouldThrowExceptionWhenCantUnblindSignature() throws Exception {
         byte[] blindSignature = null;
         byte[] blindingFactor = null;
-        byte[] signature;
 
-        signature = Bbs.unblindSignature(blindSignature, blindingFactor);
-        assertNull("The signature is null", signature);
+        try {
+            Bbs.unblindSignature(blindSignature, blindingFactor);
+            fail("Expected an exception to be thrown");
+        } catch (BbsInvalidSignatureException exception) {
+            assertTrue(exception.getMessage().startsWith("Unable to unblind signature"));
+        }
     }
 
     @Test
-    public void testCreateProofRevealingSingleMessageFromSingleMessageSignature() {
+    public void testCreateProofRevealingSingleMessageFromSingleMessageSignature() throws Exception {
         byte[] nonce = Base64.getDecoder().decode("MDEyMzQ1Njc4OQ==");
         byte[] message = Base64.getDecoder().decode("dXpBb1FGcUxnUmVpZHc9PQ==");
         byte[] publicKey = Base64.getDecoder().decode("qJgttTOthlZHltz+c0PE07hx3worb/cy7QY5iwRegQ9BfwvGahdqCO9Q9xuOnF5nD/Tq6t8zm9z26EAFCiaEJnL5b50D1cHDgNxBUPEEae+4bUb3JRsHaxBdZWDOo3pb");
@@ -516,7 +551,7 @@ public class BbsSignatureTest {
     }
 
     @Test
-    public void testBlsCreateProofRevealingSingleMessageFromSingleMessageSignature() {
+    public void testBlsCreateProofRevealingSingleMessageFromSingleMessageSignature() throws Exception {
         byte[] nonce = Base64.getDecoder().decode("MDEyMzQ1Njc4OQ==");
         byte[] message = Base64.getDecoder().decode("dXpBb1FGcUxnUmVpZHc9PQ==");
         byte[] publicKey = Base64.getDecoder().decode("qJgttTOthlZHltz+c0PE07hx3worb/cy7QY5iwRegQ9BfwvGahdqCO9Q9xuOnF5nD/Tq6t8zm9z26EAFCiaEJnL5b50D1cHDgNxBUPEEae+4bUb3JRsHaxBdZWDOo3pb");
@@ -540,7 +575,7 @@ public class BbsSignatureTest {
     }
 
     @Test
-    public void shouldThrowExceptionMessageWhenProofPublicKeyIsInvalid() {
+    public void shouldThrowExceptionMessageWhenProofPublicKeyIsInvalid() throws Exception {
         byte[] nonce = Base64.getDecoder().decode("MDEyMzQ1Njc4OQ==");
         byte[] message = Base64.getDecoder().decode("dXpBb1FGcUxnUmVpZHc9PQ==");
         byte[] signature = Base64.getDecoder().decode("r00WeXEj+07DUZb3JY6fbbKhHtQcxtLZsJUVU6liFZQKCLQYu77EXFZx4Vaa5VBtKpPK6tDGovHGgrgyizOm70VUZgzzBb0emvRIGSWhAKkcLL1z1HYwApnUE6XFFb96LUF4XM//QhEM774dX4ciqQ==");
@@ -556,13 +591,13 @@ public class BbsSignatureTest {
         try {
             Bbs.createProof(invalidPublicKey, nonce, signature, proofMessage);
             fail("Expected an exception to be thrown");
-        } catch (Exception exception) {
-            assertEquals("Unable to set public key", exception.getMessage());
+        } catch (BbsInvalidKeyException exception) {
+            assertTrue(exception.getMessage().startsWith("Unable to set public key"));
         }
     }
 
     @Test
-    public void shouldThrowExceptionMessageWhenProofNonceIsInvalid() {
+    public void shouldThrowExceptionMessageWhenProofNonceIsInvalid() throws Exception {
         byte[] invalidNonce = null;
         byte[] message = Base64.getDecoder().decode("dXpBb1FGcUxnUmVpZHc9PQ==");
         byte[] publicKey = Base64.getDecoder().decode("qJgttTOthlZHltz+c0PE07hx3worb/cy7QY5iwRegQ9BfwvGahdqCO9Q9xuOnF5nD/Tq6t8zm9z26EAFCiaEJnL5b50D1cHDgNxBUPEEae+4bUb3JRsHaxBdZWDOo3pb");
@@ -579,13 +614,13 @@ public class BbsSignatureTest {
         try {
             Bbs.createProof(bbsKey, invalidNonce, signature, proofMessage);
             fail("Expected an exception to be thrown");
-        } catch (Exception exception) {
-            assertEquals("Unable to set nonce", exception.getMessage());
+        } catch (BbsException exception) {
+            assertTrue(exception.getMessage().startsWith("Unable to set nonce"));
         }
     }
 
     @Test
-    public void shouldThrowExceptionMessageWhenProofSignatureIsInvalid() {
+    public void shouldThrowExceptionMessageWhenProofSignatureIsInvalid() throws Exception {
         byte[] nonce = Base64.getDecoder().decode("MDEyMzQ1Njc4OQ==");
         byte[] message = Base64.getDecoder().decode("dXpBb1FGcUxnUmVpZHc9PQ==");
         byte[] publicKey = Base64.getDecoder().decode("qJgttTOthlZHltz+c0PE07hx3worb/cy7QY5iwRegQ9BfwvGahdqCO9Q9xuOnF5nD/Tq6t8zm9z26EAFCiaEJnL5b50D1cHDgNxBUPEEae+4bUb3JRsHaxBdZWDOo3pb");
@@ -602,13 +637,13 @@ public class BbsSignatureTest {
         try {
             Bbs.createProof(bbsKey, nonce, invalidSignature, proofMessage);
             fail("Expected an exception to be thrown");
-        } catch (Exception exception) {
-            assertEquals("Unable to create proof", exception.getMessage());
+        } catch (BbsException exception) {
+            assertTrue(exception.getMessage().startsWith("Unable to create proof"));
         }
     }
 
     @Test
-    public void shouldThrowExceptionMessageWhenProofMessageIsInvalid() {
+    public void shouldThrowExceptionMessageWhenProofMessageIsInvalid() throws Exception {
         byte[] nonce = Base64.getDecoder().decode("MDEyMzQ1Njc4OQ==");
         byte[] message = Base64.getDecoder().decode("dXpBb1FGcUxnUmVpZHc9PQ==");
         byte[] publicKey = Base64.getDecoder().decode("qJgttTOthlZHltz+c0PE07hx3worb/cy7QY5iwRegQ9BfwvGahdqCO9Q9xuOnF5nD/Tq6t8zm9z26EAFCiaEJnL5b50D1cHDgNxBUPEEae+4bUb3JRsHaxBdZWDOo3pb");
@@ -625,13 +660,13 @@ public class BbsSignatureTest {
         try {
             Bbs.createProof(bbsKey, nonce, signature, proofMessage);
             fail("Expected an exception to be thrown");
-        } catch (Exception exception) {
-            assertEquals("Unable to add proof message", exception.getMessage());
+        } catch (BbsException exception) {
+            assertTrue(exception.getMessage().startsWith("Unable to add proof message"));
         }
     }
 
     @Test
-    public void testVerifyProofRevealingSingleMessageFromSingleMessageSignature() {
+    public void testVerifyProofRevealingSingleMessageFromSingleMessageSignature() throws Exception {
         byte[] nonce = Base64.getDecoder().decode("MDEyMzQ1Njc4OQ==");
         byte[] message = Base64.getDecoder().decode("dXpBb1FGcUxnUmVpZHc9PQ==");
         byte[] publicKey = Base64.getDecoder().decode("qJgttTOthlZHltz+c0PE07hx3worb/cy7QY5iwRegQ9BfwvGahdqCO9Q9xuOnF5nD/Tq6t8zm9z26EAFCiaEJnL5b50D1cHDgNxBUPEEae+4bUb3JRsHaxBdZWDOo3pb");
@@ -668,7 +703,7 @@ public class BbsSignatureTest {
     }
 
     @Test
-    public void testBlsVerifyProofRevealingSingleMessageFromSingleMessageSignature() {
+    public void testBlsVerifyProofRevealingSingleMessageFromSingleMessageSignature() throws Exception {
         byte[] nonce = Base64.getDecoder().decode("MDEyMzQ1Njc4OQ==");
         byte[] message = Base64.getDecoder().decode("dXpBb1FGcUxnUmVpZHc9PQ==");
         byte[] publicKey = Base64.getDecoder().decode("qJgttTOthlZHltz+c0PE07hx3worb/cy7QY5iwRegQ9BfwvGahdqCO9Q9xuOnF5nD/Tq6t8zm9z26EAFCiaEJnL5b50D1cHDgNxBUPEEae+4bUb3JRsHaxBdZWDOo3pb");
@@ -704,7 +739,7 @@ public class BbsSignatureTest {
     }
 
     @Test
-    public void shouldThrowExceptionMessageWhenVerifyProofPublicKeyIsInvalid() {
+    public void shouldThrowExceptionMessageWhenVerifyProofPublicKeyIsInvalid() throws Exception {
         byte[] nonce = Base64.getDecoder().decode("MDEyMzQ1Njc4OQ==");
         byte[] message = Base64.getDecoder().decode("dXpBb1FGcUxnUmVpZHc9PQ==");
         byte[] proof = Base64.getDecoder().decode("AAEBoaFg6VxcB6O4VIKYJO0+HzKeanbXM4uwmCHLNBm3lwdeBkfpqJ6WoVTy9J0vsvtIubBEnwEv9y1azjWWQx2kawyVzN2dvUNRK9IRLQC2ut9Iz8o3Roh4KNsG1Woe1NZKltxlXl2Be0AaoA0/8c0kyssv97BEFpKRH/hrp8UqQas3X/FyUeqQ6d7yJjMnGvIdAAAAdIvOcT/XUeKc0EeUnLVvrvpbnUAtRjWduhwPWFlDVT00Wo4LwMw/lxIDvF+TNecX3QAAAAIESbWq6giuMgFEi8bxkcrmWCoS3PsEpRkfinUw0Q8azAZhg1x/B56PbJGDGmb6jRNNaCB7DPrMNM2vUcEY07yHiY8Ro37TEL8B2M6Bh8oSYZriOXDKys+yHokCQ28YV/dj1J1vNJYScfBSZpyCKOn7AAAAAklvabUJvsh4FfKc0k/gO2VbUZwf4/4qGWnF49Ck/6SJJlzMtn5ZHzjPFNPhOsua8NtHVeni1cGrRHaLTfoEGio=");
@@ -717,13 +752,13 @@ public class BbsSignatureTest {
         try {
             Bbs.verifyProof(bbsPublicKey, proof, nonce, messages);
             fail("Expected an exception to be thrown");
-        } catch (Exception exception) {
-            assertEquals("Unable to set public key", exception.getMessage());
+        } catch (BbsInvalidKeyException exception) {
+            assertTrue(exception.getMessage().startsWith("Unable to set public key"));
         }
     }
 
     @Test
-    public void shouldThrowExceptionMessageWhenVerifyProofUnableToSetProof() {
+    public void shouldThrowExceptionMessageWhenVerifyProofUnableToSetProof() throws Exception {
         byte[] nonce = Base64.getDecoder().decode("MDEyMzQ1Njc4OQ==");
         byte[] message = Base64.getDecoder().decode("dXpBb1FGcUxnUmVpZHc9PQ==");
         byte[] publicKey = Base64.getDecoder().decode("qJgttTOthlZHltz+c0PE07hx3worb/cy7QY5iwRegQ9BfwvGahdqCO9Q9xuOnF5nD/Tq6t8zm9z26EAFCiaEJnL5b50D1cHDgNxBUPEEae+4bUb3JRsHaxBdZWDOo3pb");
@@ -737,13 +772,13 @@ public class BbsSignatureTest {
         try {
             Bbs.verifyProof(bbsPublicKey, invalidProof, nonce, messages);
             fail("Expected an exception to be thrown");
-        } catch (Exception exception) {
-            assertEquals("Unable to set proof", exception.getMessage());
+        } catch (BbsProofVerificationException exception) {
+            assertTrue(exception.getMessage().startsWith("Unable to set proof"));
         }
     }
 
     @Test
-    public void shouldThrowExceptionMessageWhenVerifyProofUnableToSetNonce() {
+    public void shouldThrowExceptionMessageWhenVerifyProofUnableToSetNonce() throws Exception {
         byte[] invalidNonce = null;
         byte[] message = Base64.getDecoder().decode("dXpBb1FGcUxnUmVpZHc9PQ==");
         byte[] publicKey = Base64.getDecoder().decode("qJgttTOthlZHltz+c0PE07hx3worb/cy7QY5iwRegQ9BfwvGahdqCO9Q9xuOnF5nD/Tq6t8zm9z26EAFCiaEJnL5b50D1cHDgNxBUPEEae+4bUb3JRsHaxBdZWDOo3pb");
@@ -757,13 +792,13 @@ public class BbsSignatureTest {
         try {
             Bbs.verifyProof(bbsPublicKey, proof, invalidNonce, messages);
             fail("Expected an exception to be thrown");
-        } catch (Exception exception) {
-            assertEquals("Unable to set nonce", exception.getMessage());
+        } catch (BbsException exception) {
+            assertTrue(exception.getMessage().startsWith("Unable to set nonce"));
         }
     }
 
     @Test
-    public void shouldThrowExceptionMessageWhenVerifyProofUnableToAddMessage() {
+    public void shouldThrowExceptionMessageWhenVerifyProofUnableToAddMessage() throws Exception {
         byte[] nonce = Base64.getDecoder().decode("MDEyMzQ1Njc4OQ==");
         byte[] invalidMessage = "".getBytes();
         byte[] publicKey = Base64.getDecoder().decode("qJgttTOthlZHltz+c0PE07hx3worb/cy7QY5iwRegQ9BfwvGahdqCO9Q9xuOnF5nD/Tq6t8zm9z26EAFCiaEJnL5b50D1cHDgNxBUPEEae+4bUb3JRsHaxBdZWDOo3pb");
@@ -777,13 +812,13 @@ public class BbsSignatureTest {
         try {
             Bbs.verifyProof(bbsPublicKey, proof, nonce, messages);
             fail("Expected an exception to be thrown");
-        } catch (Exception exception) {
-            assertEquals("Unable to add message", exception.getMessage());
+        } catch (BbsException exception) {
+            assertTrue(exception.getMessage().startsWith("Unable to add message"));
         }
     }
 
     @Test
-    public void testBlsVerifyProofRevealingSingleMessageFromMultipleMessageSignature() {
+    public void testBlsVerifyProofRevealingSingleMessageFromMultipleMessageSignature() throws Exception {
         byte[] nonce = Base64.getDecoder().decode("4mmd5EVmGd0POg+/4M2l0A==");
         byte[][] messages = {
                 Base64.getDecoder().decode("oHMsObG6rdeVlAa5bWIwRA=="),
@@ -826,7 +861,7 @@ public class BbsSignatureTest {
     }
 
     @Test
-    public void testBlsVerifyProofRevealingMultipleMessagesFromMultipleMessageSignature() {
+    public void testBlsVerifyProofRevealingMultipleMessagesFromMultipleMessageSignature() throws Exception {
         byte[] nonce = Base64.getDecoder().decode("4mmd5EVmGd0POg+/4M2l0A==");
         byte[][] messages = {
                 Base64.getDecoder().decode("oHMsObG6rdeVlAa5bWIwRA=="),
@@ -870,7 +905,7 @@ public class BbsSignatureTest {
     }
 
     @Test
-    public void testBlsVerifyProofRevealingAllMessagesFromMultipleMessageSignature() {
+    public void testBlsVerifyProofRevealingAllMessagesFromMultipleMessageSignature() throws Exception {
         byte[] nonce = Base64.getDecoder().decode("4mmd5EVmGd0POg+/4M2l0A==");
         byte[][] messages = {
                 Base64.getDecoder().decode("oHMsObG6rdeVlAa5bWIwRA=="),
@@ -915,7 +950,7 @@ public class BbsSignatureTest {
     }
 
     @Test
-    public void testGetTotalMessagesCountForProof() {
+    public void testGetTotalMessagesCountForProof() throws Exception {
         KeyPair keyPair = getBls12381G2KeyPair();
 
         byte[] nonce = Base64.getDecoder().decode("NoWZhtX+u1wWLtUfPMmku1FtU2I=");
@@ -963,4 +998,167 @@ public class BbsSignatureTest {
 
         assertEquals(3, total_messages);
     }
+
+    @Test
+    public void canInspectProof() throws Exception {
+        KeyPair keyPair = getBls12381G2KeyPair();
+
+        byte[] nonce = "nonce".getBytes();
+        byte[][] messages = {
+                "message1".getBytes(),
+                "message2".getBytes(),
+                "message3".getBytes(),
+        };
+
+        byte[] publicKey = Bbs.blsPublicToBbsPublicKey(keyPair.publicKey, messages.length);
+        byte[] signature = Bbs.sign(keyPair.secretKey, publicKey, messages);
+
+        ProofMessage[] proofMessage = {
+                new ProofMessage(ProofMessage.PROOF_MESSAGE_TYPE_HIDDEN_PROOF_SPECIFIC_BLINDING, messages[0], new byte[0]),
+                new ProofMessage(ProofMessage.PROOF_MESSAGE_TYPE_REVEALED, messages[1], new byte[0]),
+                new ProofMessage(ProofMessage.PROOF_MESSAGE_TYPE_REVEALED, messages[2], new byte[0]),
+        };
+        byte[] proof = Bbs.createProof(publicKey, nonce, signature, proofMessage);
+
+        ProofInfo info = Bbs.inspectProof(proof);
+
+        assertEquals(1, info.version);
+        assertEquals(3, info.messageCount);
+        assertEquals(1, info.hiddenCount);
+        assertArrayEquals(new int[]{1, 2}, info.revealed);
+    }
+
+    @Test(expected = BbsProofVerificationException.class)
+    public void inspectProofThrowsForTruncatedProof() throws Exception {
+        Bbs.inspectProof(new byte[]{0, 3});
+    }
+
+    @Test
+    public void canVerifyProofWithRevealedMessagesByIndex() throws Exception {
+        KeyPair keyPair = getBls12381G2KeyPair();
+
+        byte[] nonce = "nonce".getBytes();
+        byte[][] messages = {
+                "message1".getBytes(),
+                "message2".getBytes(),
+                "message3".getBytes(),
+        };
+
+        byte[] publicKey = Bbs.blsPublicToBbsPublicKey(keyPair.publicKey, messages.length);
+        byte[] signature = Bbs.sign(keyPair.secretKey, publicKey, messages);
+
+        ProofMessage[] proofMessage = {
+                new ProofMessage(ProofMessage.PROOF_MESSAGE_TYPE_REVEALED, messages[0], new byte[0]),
+                new ProofMessage(ProofMessage.PROOF_MESSAGE_TYPE_HIDDEN_PROOF_SPECIFIC_BLINDING, messages[1], new byte[0]),
+                new ProofMessage(ProofMessage.PROOF_MESSAGE_TYPE_REVEALED, messages[2], new byte[0]),
+        };
+        byte[] proof = Bbs.createProof(publicKey, nonce, signature, proofMessage);
+
+        Map<Integer, byte[]> revealed = new HashMap<>();
+        revealed.put(2, messages[2]);
+        revealed.put(0, messages[0]);
+
+        assertTrue(Bbs.verifyProof(publicKey, proof, nonce, revealed));
+
+        revealed.remove(2);
+        revealed.put(1, messages[1]);
+        try {
+            Bbs.verifyProof(publicKey, proof, nonce, revealed);
+            fail("Expected BbsProofVerificationException");
+        } catch (BbsProofVerificationException exception) {
+            assertTrue(exception.getMessage().contains("missing messages for revealed indices [2]"));
+            assertTrue(exception.getMessage().contains("unexpected messages for unrevealed indices [1]"));
+        }
+    }
+
+    @Test
+    public void canSignAndVerifyStringMessages() throws Exception {
+        KeyPair keyPair = getBls12381G2KeyPair();
+
+        String[] messages = {"message1", "message2"};
+
+        byte[] publicKey = Bbs.blsPublicToBbsPublicKey(keyPair.publicKey, messages.length);
+        byte[] signature = null;
+        boolean isVerified = false;
+        boolean isVerifiedAsBytes = false;
+
+        try {
+            signature = Bbs.sign(keyPair.secretKey, publicKey, messages);
+            isVerified = Bbs.verify(publicKey, signature, messages);
+            isVerifiedAsBytes = Bbs.verify(publicKey, signature, new byte[][]{"message1".getBytes(), "message2".getBytes()});
+        } catch (Exception exception) {
+            exception.printStackTrace();
+        }
+
+        assertNotNull(signature);
+        assertTrue(isVerified);
+        assertTrue(isVerifiedAsBytes);
+    }
+
+    @Test
+    public void canVerifyBlindCommitment() throws Exception {
+        KeyPair keyPair = getBls12381G2KeyPair();
+
+        Map<Integer, byte[]> messages = new HashMap<Integer, byte[]>() {{
+            put(0, "message1".getBytes());
+        }};
+
+        byte[] bbsKey = Bbs.blsPublicToBbsPublicKey(keyPair.publicKey, 2);
+        byte[] nonce = "nonce".getBytes();
+
+        BlindCommitmentContext blindCommitmentContext = null;
+        boolean isVerified = false;
+
+        try {
+            blindCommitmentContext = Bbs.blindCommitment(bbsKey, messages, nonce);
+            isVerified = Bbs.verifyBlindCommitment(bbsKey, blindCommitmentContext.proof, new int[]{0}, nonce);
+        } catch (Exception exception) {
+            exception.printStackTrace();
+        }
+
+        assertNotNull(blindCommitmentContext);
+        assertTrue(isVerified);
+
+        try {
+            Bbs.verifyBlindCommitment(bbsKey, blindCommitmentContext.proof, new int[]{0}, "other".getBytes());
+            fail("Expected an exception to be thrown");
+        } catch (BbsProofVerificationException exception) {
+            assertTrue(exception.getMessage().startsWith("Unable to verify blind commitment"));
+        }
+    }
+
+    @Test(expected = BbsProofVerificationException.class)
+    public void shouldThrowExceptionMessageWhenVerifyBlindCommitmentProofIsInvalid() throws Exception {
+        KeyPair keyPair = getBls12381G2KeyPair();
+        byte[] bbsKey = Bbs.blsPublicToBbsPublicKey(keyPair.publicKey, 2);
+
+        Bbs.verifyBlindCommitment(bbsKey, new byte[10], new int[]{0}, "nonce".getBytes());
+    }
+
+    @Test
+    public void canVerifyProofWithStringNonceAndMessages() throws Exception {
+        KeyPair keyPair = getBls12381G2KeyPair();
+
+        String[] messages = {"message1", "message2", "message3"};
+
+        byte[] publicKey = Bbs.blsPublicToBbsPublicKey(keyPair.publicKey, messages.length);
+        byte[] proof = null;
+        boolean isVerified = false;
+
+        try {
+            byte[] signature = Bbs.sign(keyPair.secretKey, publicKey, messages);
+            ProofMessage[] proofMessage = {
+                    new ProofMessage(ProofMessage.PROOF_MESSAGE_TYPE_REVEALED, messages[0].getBytes(), new byte[0]),
+                    new ProofMessage(ProofMessage.PROOF_MESSAGE_TYPE_HIDDEN_PROOF_SPECIFIC_BLINDING, messages[1].getBytes(), new byte[0]),
+                    new ProofMessage(ProofMessage.PROOF_MESSAGE_TYPE_REVEALED, messages[2].getBytes(), new byte[0]),
+            };
+            proof = Bbs.createProof(publicKey, "nonce", signature, proofMessage);
+            isVerified = Bbs.verifyProof(publicKey, proof, "nonce", new String[]{messages[0], messages[2]});
+        } catch (Exception exception) {
+            exception.printStackTrace();
+        }
+
+        assertNotNull(proof);
+        assertTrue(isVerified);
+    }
 }
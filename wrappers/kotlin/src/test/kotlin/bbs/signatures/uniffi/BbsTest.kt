@@ -0,0 +1,87 @@
+package bbs.signatures.uniffi
+
+import kotlin.test.Test
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+import kotlin.test.assertFalse
+import kotlin.test.assertTrue
+
+class BbsTest {
+    private val messages = listOf("name", "age", "address").map { it.toByteArray() }
+    private val nonce = "nonce".toByteArray()
+
+    private fun bbsKeyPair(messageCount: Int = messages.size): Pair<ByteArray, ByteArray> {
+        val keyPair = generateKey(null)
+        return Pair(blsPublicKeyToBbsKey(keyPair.publicKey, messageCount.toUInt()), keyPair.secretKey)
+    }
+
+    @Test
+    fun generateKeyIsDeterministicWithSeed() {
+        val seed = ByteArray(32) { 1 }
+        val first = generateKey(seed)
+        val second = generateKey(seed)
+        assertEquals(96, first.publicKey.size)
+        assertEquals(32, first.secretKey.size)
+        assertTrue(first.publicKey.contentEquals(second.publicKey))
+        assertTrue(first.secretKey.contentEquals(second.secretKey))
+    }
+
+    @Test
+    fun signAndVerify() {
+        val (publicKey, secretKey) = bbsKeyPair()
+        val signature = sign(secretKey, publicKey, messages)
+        assertEquals(112, signature.size)
+        assertTrue(verify(publicKey, signature, messages))
+        assertFalse(verify(publicKey, signature, messages.reversed()))
+    }
+
+    @Test
+    fun blindSignAndUnblind() {
+        val (publicKey, secretKey) = bbsKeyPair()
+        val commitment = blindCommitment(publicKey, nonce, mapOf(0u to messages[0]))
+        assertTrue(verifyBlindCommitment(publicKey, commitment.context, nonce, listOf(0u)))
+
+        val blindSignature = blindSign(
+            secretKey,
+            publicKey,
+            commitment.commitment,
+            mapOf(1u to messages[1], 2u to messages[2])
+        )
+        val signature = unblindSignature(blindSignature, commitment.blindingFactor)
+        assertTrue(verify(publicKey, signature, messages))
+    }
+
+    @Test
+    fun createAndVerifyProof() {
+        val (publicKey, secretKey) = bbsKeyPair()
+        val signature = sign(secretKey, publicKey, messages)
+        val proof = createProof(signature, publicKey, nonce, messages, listOf(0u, 2u))
+        assertTrue(verifyProof(publicKey, proof, nonce, listOf(messages[0], messages[2])))
+        assertFalse(verifyProof(publicKey, proof, nonce, listOf(messages[0], messages[1])))
+        assertFalse(verifyProof(publicKey, proof, "other".toByteArray(), listOf(messages[0], messages[2])))
+    }
+
+    @Test
+    fun invalidKeyThrows() {
+        val (publicKey, _) = bbsKeyPair()
+        assertFailsWith<BbsException.InvalidKey> {
+            sign(ByteArray(3), publicKey, messages)
+        }
+    }
+
+    @Test
+    fun invalidSignatureThrows() {
+        val (publicKey, _) = bbsKeyPair()
+        assertFailsWith<BbsException.InvalidSignature> {
+            verify(publicKey, ByteArray(3), messages)
+        }
+    }
+
+    @Test
+    fun wrongMessageCountThrows() {
+        val (publicKey, secretKey) = bbsKeyPair(2)
+        assertFailsWith<BbsException.Failed> {
+            sign(secretKey, publicKey, messages)
+        }
+    }
+}
@@ -0,0 +1,56 @@
+export interface KeyPair {
+  publicKey: Buffer;
+  secretKey: Buffer;
+}
+
+export interface BlindCommitment {
+  commitment: Buffer;
+  context: Buffer;
+  blindingFactor: Buffer;
+}
+
+export function generateKey(seed?: Buffer): KeyPair;
+export function blsPublicKeyToBbsKey(publicKey: Buffer, messageCount: number): Buffer;
+export function sign(secretKey: Buffer, publicKey: Buffer, messages: Buffer[]): Buffer;
+export function verify(publicKey: Buffer, signature: Buffer, messages: Buffer[]): boolean;
+export function blindCommitment(
+  publicKey: Buffer,
+  nonce: Buffer,
+  indices: number[],
+  messages: Buffer[]
+): BlindCommitment;
+export function verifyBlindCommitment(
+  publicKey: Buffer,
+  context: Buffer,
+  nonce: Buffer,
+  blinded: number[]
+): boolean;
+export function blindSign(
+  secretKey: Buffer,
+  publicKey: Buffer,
+  commitment: Buffer,
+  indices: number[],
+  messages: Buffer[]
+): Buffer;
+export function unblindSignature(blindSignature: Buffer, blindingFactor: Buffer): Buffer;
+export function createProof(
+  signature: Buffer,
+  publicKey: Buffer,
+  nonce: Buffer,
+  messages: Buffer[],
+  revealed: number[]
+): Buffer;
+export function createProofAsync(
+  signature: Buffer,
+  publicKey: Buffer,
+  nonce: Buffer,
+  messages: Buffer[],
+  revealed: number[]
+): Promise<Buffer>;
+export function verifyProof(publicKey: Buffer, proof: Buffer, nonce: Buffer, messages: Buffer[]): boolean;
+export function verifyProofAsync(
+  publicKey: Buffer,
+  proof: Buffer,
+  nonce: Buffer,
+  messages: Buffer[]
+): Promise<boolean>;
@@ -0,0 +1,9 @@
+// Copies the library built with `--features napi` next to index.js as `bbs.node`
+const fs = require("fs");
+const path = require("path");
+
+const names = { darwin: "libbbs.dylib", win32: "bbs.dll" };
+const library = names[process.platform] || "libbbs.so";
+const source = path.join(__dirname, "..", "..", "..", "target", "release", library);
+
+fs.copyFileSync(source, path.join(__dirname, "..", "bbs.node"));
@@ -0,0 +1,70 @@
+const test = require("node:test");
+const assert = require("node:assert");
+const bbs = require("..");
+
+const nonce = Buffer.from("verifier nonce");
+const messages = [Buffer.from("one"), Buffer.from("two"), Buffer.from("three")];
+
+function keys(messageCount) {
+  const key = bbs.generateKey(Buffer.from("node test seed"));
+  return {
+    publicKey: bbs.blsPublicKeyToBbsKey(key.publicKey, messageCount),
+    secretKey: key.secretKey,
+  };
+}
+
+test("generates a deterministic key from a seed", () => {
+  const a = bbs.generateKey(Buffer.from("seed"));
+  const b = bbs.generateKey(Buffer.from("seed"));
+  assert.deepStrictEqual(a.publicKey, b.publicKey);
+  assert.strictEqual(bbs.generateKey().secretKey.length, 32);
+});
+
+test("signs and verifies", () => {
+  const { publicKey, secretKey } = keys(3);
+  const signature = bbs.sign(secretKey, publicKey, messages);
+  assert.ok(bbs.verify(publicKey, signature, messages));
+  assert.ok(!bbs.verify(publicKey, signature, [messages[0], messages[2], messages[1]]));
+});
+
+test("throws on an invalid public key", () => {
+  const { secretKey } = keys(3);
+  assert.throws(() => bbs.sign(secretKey, Buffer.alloc(10), messages), Error);
+});
+
+test("blind signs", () => {
+  const { publicKey, secretKey } = keys(2);
+  const commitment = bbs.blindCommitment(publicKey, nonce, [0], [Buffer.from("hidden")]);
+  assert.ok(bbs.verifyBlindCommitment(publicKey, commitment.context, nonce, [0]));
+
+  const blindSignature = bbs.blindSign(secretKey, publicKey, commitment.commitment, [1], [Buffer.from("known")]);
+  const signature = bbs.unblindSignature(blindSignature, commitment.blindingFactor);
+  assert.ok(bbs.verify(publicKey, signature, [Buffer.from("hidden"), Buffer.from("known")]));
+});
+
+test("creates and verifies a proof", () => {
+  const { publicKey, secretKey } = keys(3);
+  const signature = bbs.sign(secretKey, publicKey, messages);
+  const proof = bbs.createProof(signature, publicKey, nonce, messages, [0, 2]);
+
+  // Message count then the big-endian revealed bitvector, as from the C ABI
+  assert.deepStrictEqual([...proof.subarray(0, 3)], [0, 3, 0b101]);
+  assert.ok(bbs.verifyProof(publicKey, proof, nonce, [messages[0], messages[2]]));
+  assert.ok(!bbs.verifyProof(publicKey, proof, Buffer.from("other nonce"), [messages[0], messages[2]]));
+});
+
+test("creates and verifies a proof off the event loop", async () => {
+  const { publicKey, secretKey } = keys(3);
+  const signature = bbs.sign(secretKey, publicKey, messages);
+  const pending = bbs.createProofAsync(signature, publicKey, nonce, messages, [1]);
+  assert.ok(pending instanceof Promise);
+
+  const proof = await pending;
+  assert.strictEqual(await bbs.verifyProofAsync(publicKey, proof, nonce, [messages[1]]), true);
+  assert.strictEqual(await bbs.verifyProofAsync(publicKey, proof, nonce, [messages[0]]), false);
+});
+
+test("rejects the promise on an invalid proof", async () => {
+  const { publicKey } = keys(3);
+  await assert.rejects(bbs.verifyProofAsync(publicKey, Buffer.alloc(4), nonce, [messages[0]]));
+});
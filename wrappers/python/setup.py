@@ -30,6 +30,8 @@ if __name__ == "__main__":
                 "bbs.dll",
                 "libbbs.dylib",
                 "libbbs.so",
+                "native.abi3.so",
+                "native.pyd",
             ]
         },
         python_requires=">=3.6.3",
@@ -0,0 +1,85 @@
+import threading
+import unittest
+
+try:
+    from ursa_bbs_signatures import native
+except ImportError:
+    native = None
+
+
+@unittest.skipIf(native is None, "build with `--features python` to test the native module")
+class TestNative(unittest.TestCase):
+    def setUp(self) -> None:
+        self.nonce = b"verifier nonce"
+        self.messages = [b"message 1", b"message 2", b"message 3"]
+        public_key, self.secret_key = native.generate_key(b"native test seed")
+        self.public_key = native.bls_public_key_to_bbs_key(public_key, len(self.messages))
+
+    def test_generate_key_is_deterministic_with_seed(self):
+        self.assertEqual(native.generate_key(b"seed"), native.generate_key(b"seed"))
+        self.assertEqual(len(native.generate_key()[1]), 32)
+
+    def test_sign_and_verify(self):
+        signature = native.sign(self.secret_key, self.public_key, self.messages)
+        self.assertIsInstance(signature, bytes)
+        self.assertTrue(native.verify(self.public_key, signature, self.messages))
+        self.assertFalse(native.verify(self.public_key, signature, list(reversed(self.messages))))
+
+    def test_sign_raises_invalid_key_exception(self):
+        with self.assertRaises(native.BbsInvalidKeyException):
+            native.sign(self.secret_key, b"\x00" * 10, self.messages)
+
+    def test_sign_raises_bbs_exception_for_wrong_message_count(self):
+        with self.assertRaises(native.BbsException) as ctx:
+            native.sign(self.secret_key, self.public_key, self.messages[:2])
+        self.assertIn("expects 3 messages", str(ctx.exception))
+
+    def test_verify_raises_invalid_signature_exception(self):
+        with self.assertRaises(native.BbsInvalidSignatureException):
+            native.verify(self.public_key, b"\x00" * 10, self.messages)
+
+    def test_exceptions_share_a_base_class(self):
+        self.assertTrue(issubclass(native.BbsInvalidKeyException, native.BbsException))
+        self.assertTrue(issubclass(native.BbsInvalidSignatureException, native.BbsException))
+        self.assertTrue(issubclass(native.BbsException, Exception))
+
+    def test_blind_sign(self):
+        commitment, context, blinding_factor = native.blind_commitment(
+            self.public_key, self.nonce, {0: self.messages[0]}
+        )
+        self.assertTrue(native.verify_blind_commitment(self.public_key, context, self.nonce, [0]))
+
+        blind_signature = native.blind_sign(
+            self.secret_key, self.public_key, commitment, {1: self.messages[1], 2: self.messages[2]}
+        )
+        signature = native.unblind_signature(blind_signature, blinding_factor)
+        self.assertTrue(native.verify(self.public_key, signature, self.messages))
+
+    def test_create_and_verify_proof(self):
+        signature = native.sign(self.secret_key, self.public_key, self.messages)
+        proof = native.create_proof(signature, self.public_key, self.nonce, self.messages, [0, 2])
+
+        # Message count then the big-endian revealed bitvector, as from the C ABI
+        self.assertEqual(proof[:3], bytes([0, 3, 0b101]))
+        revealed = [self.messages[0], self.messages[2]]
+        self.assertTrue(native.verify_proof(self.public_key, proof, self.nonce, revealed))
+        self.assertFalse(native.verify_proof(self.public_key, proof, b"other nonce", revealed))
+
+    def test_proofs_run_in_parallel_threads(self):
+        signature = native.sign(self.secret_key, self.public_key, self.messages)
+        results = []
+
+        def prove():
+            proof = native.create_proof(signature, self.public_key, self.nonce, self.messages, [1])
+            results.append(native.verify_proof(self.public_key, proof, self.nonce, [self.messages[1]]))
+
+        threads = [threading.Thread(target=prove) for _ in range(4)]
+        for t in threads:
+            t.start()
+        for t in threads:
+            t.join()
+        self.assertEqual(results, [True] * 4)
+
+
+if __name__ == "__main__":
+    unittest.main()